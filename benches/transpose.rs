@@ -0,0 +1,68 @@
+//! Cost per entry of `MatOps::transpose` on CSR matrices, next to the cost of only sorting the
+//! same entries by column.
+//!
+//! Transposing is a counting pass, _O(nnz + n)_, plus copying the entries in and out. Going
+//! through a sort instead would add at least the sort column on top of those copies. Run with
+//! `cargo bench --bench transpose`.
+
+use std::time::{Duration, Instant};
+
+use gblas::prelude::*;
+
+fn per_entry(elapsed: Duration, nnz: usize) -> f64 {
+    elapsed.as_nanos() as f64 / nnz as f64
+}
+
+fn main() {
+    Context::init(Mode::Blocking).unwrap();
+
+    for shift in [14, 16, 18, 20] {
+        let n: IndexType = 1 << shift;
+        let nnz = 8 * n;
+
+        // deterministic pseudo-random positions (LCG)
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 33) as IndexType % n
+        };
+        let tuples: Vec<(IndexType, IndexType)> = (0..nnz).map(|_| (next(), next())).collect();
+
+        let a = CsrMatrix::<i64>::new(n, n)
+            .unwrap()
+            .build(
+                tuples.iter().map(|val| val.0),
+                tuples.iter().map(|val| val.1),
+                (0..nnz as i64).map(|val| val % 7),
+                nnz,
+                Addition::new(),
+            )
+            .unwrap();
+
+        let mut c = CsrMatrix::<i64>::new(n, n).unwrap();
+        let start = Instant::now();
+        c.transpose(
+            Option::<CsrMatrix<i64>>::None,
+            Option::<Addition<i64>>::None,
+            &a,
+            None,
+        )
+        .unwrap();
+        let transpose = start.elapsed();
+        assert_eq!(c.nvals().unwrap(), a.nvals().unwrap());
+
+        let mut sorted = tuples;
+        let start = Instant::now();
+        sorted.sort_by_key(|val| (val.1, val.0));
+        let sort = start.elapsed();
+
+        println!(
+            "n = 2^{shift:<2} nnz = {:>9}: transpose {:>6.1} ns/entry, sort {:>6.1} ns/entry",
+            a.nvals().unwrap(),
+            per_entry(transpose, a.nvals().unwrap()),
+            per_entry(sort, nnz),
+        );
+    }
+}
@@ -5,10 +5,12 @@
 //!
 //! All operator are defined in the (Ops)[https://doc.rust-lang.org/std/ops/index.html] documentation.
 
+use crate::types::IndexType;
+
 // Unary Operators
 
 pub trait UnaryOperator<T, O = T> {
-    fn op(value: T) -> O;
+    fn op(&self, value: T) -> O;
 }
 
 pub struct IdendityOp;
@@ -27,7 +29,7 @@ pub struct BitwiseNotOp;
 // Unary Operators Implementations
 
 impl<T> UnaryOperator<T> for IdendityOp {
-    fn op(value: T) -> T {
+    fn op(&self, value: T) -> T {
         value
     }
 }
@@ -36,7 +38,7 @@ macro_rules! impl_abs {
     ($($t:ty),*) => {
         $(
             impl UnaryOperator<$t> for AbsoluteOp {
-                fn op(value: $t) -> $t {
+                fn op(&self, value: $t) -> $t {
                     value.abs()
                 }
             }
@@ -50,7 +52,7 @@ macro_rules! impl_abs_unsigned {
     ($($t:ty),*) => {
         $(
             impl UnaryOperator<$t> for AbsoluteOp {
-                fn op(value: $t) -> $t {
+                fn op(&self, value: $t) -> $t {
                     value
                 }
             }
@@ -64,7 +66,7 @@ impl<T> UnaryOperator<T> for AdditiveInverseOp
 where
     T: std::ops::Neg<Output = T>,
 {
-    fn op(value: T) -> T {
+    fn op(&self, value: T) -> T {
         -value
     }
 }
@@ -73,7 +75,7 @@ macro_rules! impl_mul_inv {
     ($($t:ty),*) => {
         $(
             impl UnaryOperator<$t, f32> for MultiplicativeInverseOp {
-                fn op(value: $t) -> f32 {
+                fn op(&self, value: $t) -> f32 {
                     1.0f32 / value as f32
                 }
             }
@@ -85,7 +87,7 @@ macro_rules! impl_mul_inv_f64 {
     ($($t:ty),*) => {
         $(
             impl UnaryOperator<$t, f64> for MultiplicativeInverseOp {
-                fn op(value: $t) -> f64 {
+                fn op(&self, value: $t) -> f64 {
                     1.0f64 / value as f64
                 }
             }
@@ -97,7 +99,7 @@ impl_mul_inv!(i8, i16, u8, u16, f32);
 impl_mul_inv_f64!(i32, i64, u32, u64, f64);
 
 impl UnaryOperator<bool> for LogicalNotOp {
-    fn op(value: bool) -> bool {
+    fn op(&self, value: bool) -> bool {
         !value
     }
 }
@@ -106,20 +108,226 @@ impl<T> UnaryOperator<T> for BitwiseNotOp
 where
     T: std::ops::Not<Output = T>,
 {
-    fn op(value: T) -> T {
+    fn op(&self, value: T) -> T {
         !value
     }
 }
 
+//
+
+/// Any `Fn(T) -> O` closure can be used as a unary operator, which allows capturing parameters.
+///
+/// e.g. `|x: f64| x > 0.5` or `move |x: f64| alpha * x`
+impl<F, T, O> UnaryOperator<T, O> for F
+where
+    F: Fn(T) -> O,
+{
+    fn op(&self, value: T) -> O {
+        self(value)
+    }
+}
+
+// ------------------------------------------------------------------------------
+// Index Unary Operators
+
+/// Operator on a stored value and its position (`GrB_IndexUnaryOp`).
+///
+/// `thunk` is the extra scalar given to the operation, e.g. the diagonal offset of [`TrilOp`].
+/// Vectors call it with `col = 0`.
+pub trait IndexUnaryOperator<T, S = T> {
+    type Output;
+
+    fn op(&self, value: T, row: IndexType, col: IndexType, thunk: S) -> Self::Output;
+}
+
+/// Any `Fn(T, IndexType, IndexType, S) -> O` closure can be used as an index unary operator.
+impl<F, T, S, O> IndexUnaryOperator<T, S> for F
+where
+    F: Fn(T, IndexType, IndexType, S) -> O,
+{
+    type Output = O;
+
+    fn op(&self, value: T, row: IndexType, col: IndexType, thunk: S) -> Self::Output {
+        self(value, row, col, thunk)
+    }
+}
+
+/// `j <= i + thunk`
+pub struct TrilOp;
+
+/// `j >= i + thunk`
+pub struct TriuOp;
+
+/// `j == i + thunk`
+pub struct DiagOp;
+
+/// `j != i + thunk`
+pub struct OffDiagOp;
+
+/// `i + thunk`
+pub struct RowIndexOp;
+
+/// `j + thunk`
+pub struct ColIndexOp;
+
+/// `i <= thunk`
+pub struct RowLeOp;
+
+/// `i > thunk`
+pub struct RowGtOp;
+
+/// `j <= thunk`
+pub struct ColLeOp;
+
+/// `j > thunk`
+pub struct ColGtOp;
+
+/// `A(i, j) == thunk`
+pub struct ValueEqOp;
+
+/// `A(i, j) != thunk`
+pub struct ValueNeOp;
+
+/// `A(i, j) < thunk`
+pub struct ValueLtOp;
+
+/// `A(i, j) <= thunk`
+pub struct ValueLeOp;
+
+/// `A(i, j) > thunk`
+pub struct ValueGtOp;
+
+/// `A(i, j) >= thunk`
+pub struct ValueGeOp;
+
+// ------------------------------------------------------------------------------
+// Index Unary Operators Implementations
+
+/// `idx` as the signed integer the positional operators work with.
+///
+/// **Panics** if `idx` doesn't fit in an `i64`, which is beyond any dimension GraphBLAS allows
+/// (`GrB_INDEX_MAX` is _2^60_).
+fn signed_index(idx: IndexType) -> i64 {
+    i64::try_from(idx).expect("index doesn't fit in an i64")
+}
+
+macro_rules! impl_index_op {
+    ($($name:ident: |$i:ident, $j:ident, $thunk:ident| $body:block),* $(,)?) => {
+        $(
+            impl<T> IndexUnaryOperator<T, i64> for $name {
+                type Output = bool;
+
+                fn op(&self, _value: T, row: IndexType, col: IndexType, thunk: i64) -> bool {
+                    let ($i, $j, $thunk) = (signed_index(row), signed_index(col), thunk);
+                    $body
+                }
+            }
+        )*
+    };
+}
+
+impl_index_op!(
+    TrilOp: |i, j, thunk| { j <= i.saturating_add(thunk) },
+    TriuOp: |i, j, thunk| { j >= i.saturating_add(thunk) },
+    DiagOp: |i, j, thunk| { j == i.saturating_add(thunk) },
+    OffDiagOp: |i, j, thunk| { j != i.saturating_add(thunk) },
+    RowLeOp: |i, _j, thunk| { i <= thunk },
+    RowGtOp: |i, _j, thunk| { i > thunk },
+    ColLeOp: |_i, j, thunk| { j <= thunk },
+    ColGtOp: |_i, j, thunk| { j > thunk },
+);
+
+// the thunk gives the output type, as `GrB_ROWINDEX_INT32` and `GrB_ROWINDEX_INT64` do
+macro_rules! impl_position_op {
+    ($($name:ident: |$i:ident, $j:ident| $pos:expr),* $(,)?) => {
+        $(
+            /// **Panics** if the result doesn't fit in `S`.
+            impl<T, S> IndexUnaryOperator<T, S> for $name
+            where
+                S: TryFrom<i64> + Into<i64>,
+            {
+                type Output = S;
+
+                fn op(&self, _value: T, $i: IndexType, $j: IndexType, thunk: S) -> S {
+                    signed_index($pos)
+                        .checked_add(thunk.into())
+                        .and_then(|res| S::try_from(res).ok())
+                        .expect("index doesn't fit in the output type")
+                }
+            }
+        )*
+    };
+}
+
+impl_position_op!(
+    RowIndexOp: |row, _col| row,
+    ColIndexOp: |_row, col| col,
+);
+
+macro_rules! impl_value_op {
+    ($($name:ident: $cmp:tt),* $(,)?) => {
+        $(
+            impl<T> IndexUnaryOperator<T> for $name
+            where
+                T: PartialOrd,
+            {
+                type Output = bool;
+
+                fn op(&self, value: T, _row: IndexType, _col: IndexType, thunk: T) -> bool {
+                    value $cmp thunk
+                }
+            }
+        )*
+    };
+}
+
+impl_value_op!(
+    ValueEqOp: ==,
+    ValueNeOp: !=,
+    ValueLtOp: <,
+    ValueLeOp: <=,
+    ValueGtOp: >,
+    ValueGeOp: >=,
+);
+
 // ------------------------------------------------------------------------------
 // Binary Operators
 
+// `Default` for the operators built with `new()`
+macro_rules! impl_default {
+    ($($name:ident $(<$($param:ident),*>)?),* $(,)?) => {
+        $(
+            impl$(<$($param),*>)? Default for $name$(<$($param),*>)? {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+        )*
+    };
+}
+
 pub trait BinaryOperator<D1, D2 = D1> {
     type Output;
 
     fn op(&self, lhs: D1, rhs: D2) -> Self::Output;
 }
 
+/// Any `Fn(D1, D2) -> O` closure can be used as a binary operator.
+impl<F, D1, D2, O> BinaryOperator<D1, D2> for F
+where
+    F: Fn(D1, D2) -> O,
+{
+    type Output = O;
+
+    fn op(&self, lhs: D1, rhs: D2) -> Self::Output {
+        self(lhs, rhs)
+    }
+}
+
+/// Marker for binary operators that are associative and commutative, so a reduction may combine
+/// values in any order.
+pub trait AssociativeCommutative {}
+
 pub struct LogicalOr {
     _marker: std::marker::PhantomData<()>,
 }
@@ -579,9 +787,47 @@ where
     }
 }
 
+impl_default!(
+    LogicalOr,
+    LogicalAnd,
+    LogicalXor,
+    LogicalXnor,
+    BitwiseOr<D1, D2>,
+    BitwiseAnd<D1, D2>,
+    BitwiseXor<D1, D2>,
+    BitwiseXnor<D1, D2>,
+    Equal<D1, D2>,
+    NotEqual<D1, D2>,
+    GreaterThan<D1, D2>,
+    LessThan<D1, D2>,
+    GreaterThanOrEqual<D1, D2>,
+    LessThanOrEqual<D1, D2>,
+    First<D1, D2>,
+    Second<D1, D2>,
+    Minimum<D1>,
+    Maximum<D1>,
+    Addition<D1, D2>,
+    Subtraction<D1, D2>,
+    Multiplication<D1, D2>,
+    Division<D1, D2>,
+);
+
 // ------------------------------------------------------------------------------
 // Binary Operators Implementations
 
+impl AssociativeCommutative for LogicalOr {}
+impl AssociativeCommutative for LogicalAnd {}
+impl AssociativeCommutative for LogicalXor {}
+impl AssociativeCommutative for LogicalXnor {}
+impl<D1, D2> AssociativeCommutative for BitwiseOr<D1, D2> {}
+impl<D1, D2> AssociativeCommutative for BitwiseAnd<D1, D2> {}
+impl<D1, D2> AssociativeCommutative for BitwiseXor<D1, D2> {}
+impl<D1, D2> AssociativeCommutative for BitwiseXnor<D1, D2> {}
+impl<D1> AssociativeCommutative for Minimum<D1> {}
+impl<D1> AssociativeCommutative for Maximum<D1> {}
+impl<D1, D2> AssociativeCommutative for Addition<D1, D2> {}
+impl<D1, D2> AssociativeCommutative for Multiplication<D1, D2> {}
+
 // ---------------------------------------------------------------------------
 // Monoids
 
@@ -594,6 +840,31 @@ pub trait Monoid<D> {
 // ---------------------------------------------------------------------------
 // Monoids implementations
 
+/// Monoid built from a user provided associative [`BinaryOperator`] and its identity
+/// (`GrB_Monoid_new`).
+pub struct MonoidFrom<Op, D> {
+    op: Op,
+    identity: D,
+}
+impl<Op, D> MonoidFrom<Op, D> {
+    pub fn new(op: Op, identity: D) -> Self {
+        Self { op, identity }
+    }
+}
+impl<Op, D> Monoid<D> for MonoidFrom<Op, D>
+where
+    Op: BinaryOperator<D, Output = D>,
+    D: Clone,
+{
+    fn identity(&self) -> D {
+        self.identity.clone()
+    }
+
+    fn operate(&self, lhs: D, rhs: D) -> D {
+        self.op.op(lhs, rhs)
+    }
+}
+
 macro_rules! impl_monoid_def {
     ($name:ident) => {
         pub struct $name<D> {
@@ -606,6 +877,11 @@ macro_rules! impl_monoid_def {
                 }
             }
         }
+        impl<D> Default for $name<D> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
     };
 }
 
@@ -623,14 +899,6 @@ macro_rules! impl_monoid_detail {
     };
 }
 
-// Macro to associate a BinaryOp implementation with a Monoid
-macro_rules! impl_monoid {
-    ($name:ident, $bin_op:ident, $domain:ty, $identity:expr) => {
-        impl_monoid_def!($name);
-        impl_monoid_detail!($name, $bin_op, $domain, $identity);
-    };
-}
-
 macro_rules! impl_monoid_many {
     ($name:ident, $bin_op:ident, $( ($domain:ty, $identity:expr) ), *) => {
         impl_monoid_def!($name);
@@ -785,6 +1053,13 @@ impl Monoid<bool> for LogicalXnorMonoid {
     }
 }
 
+impl_default!(
+    LogicalOrMonoid,
+    LogicalAndMonoid,
+    LogicalXorMonoid,
+    LogicalXnorMonoid,
+);
+
 // ---------------------------------------------------------------------------
 // Semirings
 
@@ -976,9 +1251,59 @@ impl<D1, D2> MaxSecondSemiring<D1, D2> {
     }
 }
 
+impl_default!(
+    PlusTimesSemiring<D1, D2>,
+    MinPlusSemiring<D1, D2>,
+    MaxPlusSemiring<D1, D2>,
+    MinTimesSemiring<D1, D2>,
+    MinMaxSemiring<D1, D2>,
+    MaxMinSemiring<D1, D2>,
+    MaxTimesSemiring<D1, D2>,
+    PlusMinSemiring<D1, D2>,
+    LogicalSemiring,
+    AndOrSemiring,
+    XorAndSemiring,
+    XorOrSemiring,
+    MinFirstSemiring<D1, D2>,
+    MinSecondSemiring<D1, D2>,
+    MaxFirstSemiring<D1, D2>,
+    MaxSecondSemiring<D1, D2>,
+);
+
 // ---------------------------------------------------------------------------
 // Semirings implementations
 
+/// Semiring built from a user provided additive [`Monoid`] and multiplicative [`BinaryOperator`]
+/// (`GrB_Semiring_new`).
+pub struct SemiringFrom<M, Op> {
+    add: M,
+    mult: Op,
+}
+impl<M, Op> SemiringFrom<M, Op> {
+    pub fn new(add: M, mult: Op) -> Self {
+        Self { add, mult }
+    }
+}
+impl<M, Op, D1, D2> Semiring<D1, D2> for SemiringFrom<M, Op>
+where
+    Op: BinaryOperator<D1, D2>,
+    M: Monoid<Op::Output>,
+{
+    type Output = Op::Output;
+
+    fn add(&self, lhs: Self::Output, rhs: Self::Output) -> Self::Output {
+        self.add.operate(lhs, rhs)
+    }
+
+    fn mult(&self, lhs: D1, rhs: D2) -> Self::Output {
+        self.mult.op(lhs, rhs)
+    }
+
+    fn zero(&self) -> Self::Output {
+        self.add.identity()
+    }
+}
+
 impl<D1, D2> Semiring<D1, D2> for PlusTimesSemiring<D1, D2>
 where
     PlusMonoid<D1>: Monoid<D1>,
@@ -1303,6 +1628,82 @@ where
 mod tests {
     use super::*;
 
+    struct Threshold(f64);
+
+    impl UnaryOperator<f64, bool> for Threshold {
+        fn op(&self, value: f64) -> bool {
+            value > self.0
+        }
+    }
+
+    #[test]
+    fn test_unary_ops() {
+        assert_eq!(IdendityOp.op(3), 3);
+        assert_eq!(AbsoluteOp.op(-3), 3);
+        assert_eq!(AbsoluteOp.op(3u8), 3);
+        assert_eq!(AdditiveInverseOp.op(3), -3);
+        assert_eq!(
+            UnaryOperator::<i32, f64>::op(&MultiplicativeInverseOp, 4),
+            0.25
+        );
+        assert!(LogicalNotOp.op(false));
+        assert_eq!(BitwiseNotOp.op(0u8), u8::MAX);
+
+        let threshold = Threshold(0.5);
+        assert!(threshold.op(0.7));
+        assert!(!threshold.op(0.3));
+
+        let alpha = 2.0;
+        let scale = move |x: f64| alpha * x;
+        assert_eq!(scale.op(1.5), 3.0);
+        assert!((|x: f64| x > 0.5).op(0.7));
+    }
+
+    #[test]
+    fn test_index_unary_ops() {
+        assert!(TrilOp.op(1.0, 2, 1, 0));
+        assert!(!TrilOp.op(1.0, 2, 2, -1));
+        assert!(TriuOp.op(1.0, 1, 2, 1));
+        assert!(DiagOp.op(1.0, 1, 2, 1));
+        assert!(OffDiagOp.op(1.0, 1, 1, 1));
+        assert_eq!(RowIndexOp.op(1.0, 3, 0, -1), 2);
+        assert_eq!(ColIndexOp.op(1.0, 0, 3, 2), 5);
+        assert_eq!(RowIndexOp.op(1.0, 3, 0, 1i32), 4i32);
+        assert!(TrilOp.op(1, 0, 1, i64::MAX));
+        assert!(RowLeOp.op(1.0, 2, 0, 2));
+        assert!(RowGtOp.op(1.0, 3, 0, 2));
+        assert!(ColLeOp.op(1.0, 0, 0, 0));
+        assert!(!ColGtOp.op(1.0, 0, 0, 0));
+
+        assert!(ValueEqOp.op(3, 0, 0, 3));
+        assert!(ValueNeOp.op(3, 0, 0, 4));
+        assert!(ValueLtOp.op(3, 0, 0, 4));
+        assert!(ValueLeOp.op(3, 0, 0, 3));
+        assert!(ValueGtOp.op(3.5, 0, 0, 3.0));
+        assert!(!ValueGeOp.op(3, 0, 0, 4));
+        assert!(ValueGeOp.op(0.5f32, 0, 0, 0.5f32));
+
+        let band = |_: f64, i: IndexType, j: IndexType, width: usize| i.abs_diff(j) <= width;
+        assert!(band.op(1.0, 4, 2, 2));
+        assert!(!band.op(1.0, 0, 3, 2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_op_overflow() {
+        RowIndexOp.op(1.0, 200, 0, 0i8);
+    }
+
+    #[test]
+    fn test_closure_bin_ops() {
+        let hypot = |x: f64, y: f64| (x * x + y * y).sqrt();
+        assert_eq!(hypot.op(3.0, 4.0), 5.0);
+
+        let offset = 10;
+        let add_offset = move |x: i32, y: i32| x + y + offset;
+        assert_eq!(add_offset.op(1, 2), 13);
+    }
+
     #[test]
     fn test_bin_ops() {
         let or = LogicalOr::new();
@@ -1399,6 +1800,44 @@ mod tests {
         assert!(xnor.operate(true, true));
     }
 
+    #[test]
+    fn test_user_monoids() {
+        let plus = MonoidFrom::new(Addition::new(), 0);
+        assert_eq!(plus.identity(), 0);
+        assert_eq!(plus.operate(1, 2), 3);
+
+        let gcd = MonoidFrom::new(
+            |mut a: u32, mut b: u32| {
+                while b != 0 {
+                    (a, b) = (b, a % b);
+                }
+                a
+            },
+            0,
+        );
+        assert_eq!(gcd.operate(12, 18), 6);
+        assert_eq!(gcd.operate(gcd.identity(), 7), 7);
+    }
+
+    #[test]
+    fn test_user_semirings() {
+        // reliability semiring: the most reliable path over independent edge probabilities
+        let reliability =
+            SemiringFrom::new(MonoidFrom::new(Maximum::new(), 0.0), Multiplication::new());
+        assert_eq!(reliability.zero(), 0.0);
+        assert_eq!(reliability.mult(0.5, 0.5), 0.25);
+        assert_eq!(reliability.add(0.25, 0.9), 0.9);
+
+        let plus_times = SemiringFrom::new(PlusMonoid::new(), Multiplication::new());
+        assert_eq!(plus_times.add(2, 3), 5);
+        assert_eq!(plus_times.mult(2, 3), 6);
+        assert_eq!(plus_times.zero(), 0);
+
+        let any_pair = SemiringFrom::new(LogicalOrMonoid::new(), |_: i32, _: i32| true);
+        assert!(any_pair.mult(1, 2));
+        assert!(!any_pair.zero());
+    }
+
     #[test]
     fn test_semirings() {
         let plus_times = PlusTimesSemiring::new();
@@ -0,0 +1,263 @@
+use std::ops::Index;
+
+use crate::{
+    algebra::BinaryOperator,
+    complement_mask::MatrixComplementMask,
+    context,
+    mask::MatMask,
+    matrix::{Matrix, MatrixExtra},
+    structure_mask::MatrixStructureMask,
+    types::{IndexType, NoValue},
+    ApiError, ExecutionError, GblasResult,
+};
+
+use super::BitmapMatrix;
+
+impl<T> Matrix for BitmapMatrix<T>
+where
+    T: Clone + PartialEq,
+{
+    type Scalar = T;
+
+    fn new(rows: IndexType, cols: IndexType) -> GblasResult<Self> {
+        context::check_init()?;
+        if rows == 0 || cols == 0 {
+            return Err(ApiError::InvalidValue.into());
+        }
+        let len = rows.checked_mul(cols).ok_or(ApiError::InvalidValue)?;
+
+        Ok(Self {
+            values: vec![None; len],
+            nrows: rows,
+            ncols: cols,
+            nvals: 0,
+        })
+    }
+
+    fn resize(&mut self, rows: IndexType, cols: IndexType) -> GblasResult<NoValue> {
+        context::check_init()?;
+        if rows == 0 || cols == 0 {
+            return Err(ApiError::InvalidValue.into());
+        }
+        let len = rows.checked_mul(cols).ok_or(ApiError::InvalidValue)?;
+
+        let mut values = vec![None; len];
+        for (i, row) in self.values.chunks_mut(self.ncols).take(rows).enumerate() {
+            for (j, val) in row.iter_mut().take(cols).enumerate() {
+                values[i * cols + j] = val.take();
+            }
+        }
+        self.values = values;
+        self.nrows = rows;
+        self.ncols = cols;
+        self.nvals = self.values.iter().flatten().count();
+
+        Ok(())
+    }
+
+    fn clear(&mut self) -> GblasResult<NoValue> {
+        context::check_init()?;
+        self.values.fill(None);
+        self.nvals = 0;
+        Ok(())
+    }
+
+    fn nrows(&self) -> IndexType {
+        self.nrows
+    }
+
+    fn ncols(&self) -> IndexType {
+        self.ncols
+    }
+
+    fn nvals(&self) -> GblasResult<IndexType> {
+        context::check_init()?;
+        Ok(self.nvals)
+    }
+
+    fn build(
+        self,
+        rows: impl Iterator<Item = IndexType>,
+        cols: impl Iterator<Item = IndexType>,
+        values: impl Iterator<Item = Self::Scalar>,
+        _: IndexType,
+        dup: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
+    ) -> GblasResult<Self> {
+        context::check_init()?;
+        if self.nvals()? > 0 {
+            return Err(ApiError::OutputNotEmpty.into());
+        }
+
+        let mut s = self;
+        for ((row, col), val) in rows.zip(cols).zip(values) {
+            let idx = s.slot(row, col).ok_or(ExecutionError::IndexOutOfBounds)?;
+            s.values[idx] = match s.values[idx].take() {
+                Some(prev) => Some(dup.op(prev, val)),
+                None => {
+                    s.nvals += 1;
+                    Some(val)
+                }
+            };
+        }
+
+        Ok(s)
+    }
+
+    fn set_element(
+        &mut self,
+        row: IndexType,
+        col: IndexType,
+        value: Self::Scalar,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let idx = self.slot(row, col).ok_or(ApiError::InvalidIndex)?;
+        if self.values[idx].replace(value).is_none() {
+            self.nvals += 1;
+        }
+        Ok(())
+    }
+
+    fn remove_element(&mut self, row: IndexType, col: IndexType) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let idx = self.slot(row, col).ok_or(ApiError::InvalidIndex)?;
+        if self.values[idx].take().is_some() {
+            self.nvals -= 1;
+        }
+        Ok(())
+    }
+
+    fn extract_element(&self, row: IndexType, col: IndexType) -> GblasResult<&Self::Scalar> {
+        context::check_init()?;
+        let idx = self.slot(row, col).ok_or(ApiError::InvalidIndex)?;
+        self.values[idx]
+            .as_ref()
+            .ok_or_else(|| ApiError::NoValue.into())
+    }
+
+    fn extract_tuples(self) -> GblasResult<(Vec<IndexType>, Vec<IndexType>, Vec<Self::Scalar>)> {
+        context::check_init()?;
+        let mut rows = Vec::with_capacity(self.nvals);
+        let mut cols = Vec::with_capacity(self.nvals);
+        let mut vals = Vec::with_capacity(self.nvals);
+        for (idx, val) in self.values.into_iter().enumerate() {
+            if let Some(val) = val {
+                rows.push(idx / self.ncols);
+                cols.push(idx % self.ncols);
+                vals.push(val);
+            }
+        }
+
+        Ok((rows, cols, vals))
+    }
+
+    fn extract_major(&self, k: IndexType) -> GblasResult<Vec<(IndexType, Self::Scalar)>> {
+        context::check_init()?;
+        if k >= self.nrows {
+            return Err(ApiError::InvalidIndex.into());
+        }
+        Ok(self.values[k * self.ncols..(k + 1) * self.ncols]
+            .iter()
+            .enumerate()
+            .filter_map(|(j, val)| val.clone().map(|val| (j, val)))
+            .collect())
+    }
+}
+
+impl<T> MatrixExtra for BitmapMatrix<T>
+where
+    T: Clone + PartialEq,
+{
+    fn iter(&self) -> impl Iterator<Item = (IndexType, IndexType, &Self::Scalar)> {
+        self.values.iter().enumerate().filter_map(|(idx, val)| {
+            val.as_ref()
+                .map(|val| (idx / self.ncols, idx % self.ncols, val))
+        })
+    }
+}
+
+/// Value mask: an entry counts only if its value is not the zero (default) of `T`.
+impl<T> Index<(IndexType, IndexType)> for BitmapMatrix<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    type Output = bool;
+
+    fn index(&self, index: (IndexType, IndexType)) -> &Self::Output {
+        let found = <Self as Matrix>::extract_element(self, index.0, index.1);
+        match found {
+            Ok(val) if *val != T::default() => &true,
+            _ => &false,
+        }
+    }
+}
+
+impl<T> MatMask<Self> for BitmapMatrix<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    fn complement(&self) -> MatrixComplementMask<'_, Self> {
+        MatrixComplementMask::new(self)
+    }
+
+    fn structure(&self) -> MatrixStructureMask<'_, Self> {
+        MatrixStructureMask::new(self)
+    }
+
+    fn shape(&self) -> (IndexType, IndexType) {
+        (self.nrows(), self.ncols())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algebra::Addition, backend::test_utils::init};
+
+    #[test]
+    fn test_matrix_ops() {
+        init();
+        let mut mat = BitmapMatrix::<f64>::new(4, 5).unwrap();
+        assert!(mat.set_element(1, 4, 5.0).is_ok());
+        assert!(mat.set_element(0, 0, 1.0).is_ok());
+        assert!(mat.set_element(1, 4, 6.0).is_ok());
+        assert!(mat.set_element(3, 2, 0.0).is_ok());
+        assert!(mat.set_element(4, 0, 7.0).is_err());
+        assert!(mat.set_element(0, 5, 7.0).is_err());
+        assert_eq!(mat.nvals().unwrap(), 3);
+        assert_eq!(mat.extract_element(1, 4).unwrap(), &6.0);
+        assert!(mat.extract_element(2, 2).is_err());
+        assert_eq!(mat.extract_major(1).unwrap(), vec![(4, 6.0)]);
+
+        assert!(mat[(0, 0)]);
+        assert!(!mat[(3, 2)]);
+        assert!(mat.structure()[(3, 2)]);
+        assert!(mat.complement()[(2, 2)]);
+
+        mat.resize(5, 3).unwrap();
+        assert_eq!(mat.nvals().unwrap(), 2);
+        mat.set_element(4, 1, 2.0).unwrap();
+        mat.remove_element(3, 2).unwrap();
+
+        let entries: Vec<_> = mat.iter().map(|(i, j, v)| (i, j, *v)).collect();
+        assert_eq!(entries, vec![(0, 0, 1.0), (4, 1, 2.0)]);
+        assert_eq!(
+            mat.extract_tuples().unwrap(),
+            (vec![0, 4], vec![0, 1], vec![1.0, 2.0])
+        );
+
+        let mat = BitmapMatrix::<i32>::new(2, 2)
+            .unwrap()
+            .build(
+                [1, 0, 1].into_iter(),
+                [1, 1, 1].into_iter(),
+                [1, 2, 3].into_iter(),
+                3,
+                Addition::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            mat.extract_tuples().unwrap(),
+            (vec![0, 1], vec![1, 1], vec![2, 4])
+        );
+    }
+}
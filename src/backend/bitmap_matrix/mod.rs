@@ -0,0 +1,25 @@
+use crate::types::IndexType;
+
+/// Bitmap matrix: a slot per position, stored row by row, holding the value when the entry is
+/// present.
+///
+/// Element access is O(1) and the memory is proportional to _nrows * ncols_, which suits
+/// matrices that are mostly dense. Element updates are applied right away, even in nonblocking
+/// mode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitmapMatrix<T> {
+    values: Vec<Option<T>>,
+    nrows: IndexType,
+    ncols: IndexType,
+    nvals: IndexType,
+}
+
+impl<T> BitmapMatrix<T> {
+    /// Slot of `(row, col)`, `None` when out of bounds.
+    fn slot(&self, row: IndexType, col: IndexType) -> Option<usize> {
+        (row < self.nrows && col < self.ncols).then(|| row * self.ncols + col)
+    }
+}
+
+mod matrix_impl;
+mod ops_impl;
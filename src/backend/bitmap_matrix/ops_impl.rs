@@ -0,0 +1,124 @@
+use crate::{
+    backend::{major_ops::MajorStorage, pending::Defer},
+    types::IndexType,
+};
+
+use super::BitmapMatrix;
+
+impl<T> MajorStorage for BitmapMatrix<T>
+where
+    T: Clone + PartialEq,
+{
+    /// Rows are not known to be empty without reading their slots, so `stored` is asked for every
+    /// row not in `majors`.
+    fn update_major(
+        &mut self,
+        majors: &[IndexType],
+        stored: impl Fn(IndexType) -> bool,
+        mut f: impl FnMut(IndexType, &mut Vec<(IndexType, T)>),
+    ) {
+        let mut majors = majors.iter().copied().peekable();
+        let mut row = Vec::new();
+        for (i, slots) in self.values.chunks_mut(self.ncols).enumerate() {
+            let listed = majors.next_if_eq(&i).is_some();
+            while majors.next_if_eq(&i).is_some() {}
+            if !listed && !stored(i) {
+                continue;
+            }
+            row.extend(
+                slots
+                    .iter_mut()
+                    .enumerate()
+                    .filter_map(|(j, val)| val.take().map(|val| (j, val))),
+            );
+            self.nvals -= row.len();
+            f(i, &mut row);
+            self.nvals += row.len();
+            for (j, val) in row.drain(..) {
+                slots[j] = Some(val);
+            }
+        }
+    }
+}
+
+/// Nothing is queued on a bitmap, operations run when called.
+impl<T> Defer for BitmapMatrix<T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algebra::{Addition, MinFirstSemiring, Subtraction},
+        backend::{
+            test_utils::{check_matches_sparse, entries, init, matrix},
+            CscMatrix, SparseMatrix,
+        },
+        descriptor::Descriptor,
+        mask::MatMask,
+        matrix::Matrix,
+        operations::MatOps,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_matches_sparse() {
+        init();
+        check_matches_sparse::<BitmapMatrix<i32>>();
+    }
+
+    #[test]
+    fn test_update_nvals() {
+        init();
+        let values = [(0, 0, 1), (0, 2, 2), (1, 1, 3), (2, 0, 4), (2, 1, 5)];
+        let a: BitmapMatrix<i32> = matrix(3, 3, &values);
+        let b: SparseMatrix<i32> = matrix(3, 3, &values);
+        let csc: CscMatrix<i32> = matrix(3, 3, &values);
+
+        // every row is rebuilt, the count follows the entries of all of them
+        let mut c: BitmapMatrix<i32> = matrix(3, 3, &[(1, 0, 1), (1, 2, 1)]);
+        let mut expected: SparseMatrix<i32> = matrix(3, 3, &[(1, 0, 1), (1, 2, 1)]);
+        c.mxm(
+            Some(a.structure()),
+            Some(Addition::new()),
+            MinFirstSemiring::new(),
+            &a,
+            &csc,
+            Some(Descriptor::DESC_T0),
+        )
+        .unwrap();
+        expected
+            .mxm(
+                Some(b.structure()),
+                Some(Addition::new()),
+                MinFirstSemiring::new(),
+                &b,
+                &b,
+                Some(Descriptor::DESC_T0),
+            )
+            .unwrap();
+        assert_eq!(entries(&c), entries(&expected));
+        assert_eq!(c.nvals().unwrap(), expected.nvals().unwrap());
+
+        c.e_wise_mult_binary_op(
+            Some(a.complement()),
+            Option::<Addition<i32>>::None,
+            Subtraction::new(),
+            &c.clone(),
+            &a,
+            Some(Descriptor::DESC_RT1),
+        )
+        .unwrap();
+        expected
+            .e_wise_mult_binary_op(
+                Some(b.complement()),
+                Option::<Addition<i32>>::None,
+                Subtraction::new(),
+                &expected.clone(),
+                &b,
+                Some(Descriptor::DESC_RT1),
+            )
+            .unwrap();
+        assert_eq!(entries(&c), entries(&expected));
+        assert_eq!(c.nvals().unwrap(), expected.nvals().unwrap());
+    }
+}
@@ -0,0 +1,15 @@
+use crate::types::IndexType;
+
+/// Bitmap vector: a slot per index, holding the value when the entry is present.
+///
+/// Element access is O(1) and the memory is proportional to the size, which suits vectors that
+/// are mostly dense but may still miss some entries. Element updates are applied right away,
+/// even in nonblocking mode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitmapVec<T> {
+    values: Vec<Option<T>>,
+    nvals: IndexType,
+}
+
+mod ops_impl;
+mod vector_impl;
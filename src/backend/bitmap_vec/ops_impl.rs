@@ -0,0 +1,79 @@
+use crate::{
+    backend::{entry_ops::EntryStorage, pending::Defer},
+    types::IndexType,
+};
+
+use super::BitmapVec;
+
+impl<T> EntryStorage for BitmapVec<T>
+where
+    T: Clone + PartialEq,
+{
+    fn update_entries(&mut self, f: impl FnOnce(&mut Vec<(IndexType, T)>)) {
+        let mut entries: Vec<(IndexType, T)> = self
+            .values
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(i, val)| val.take().map(|val| (i, val)))
+            .collect();
+        f(&mut entries);
+
+        self.nvals = entries.len();
+        for (i, val) in entries {
+            self.values[i] = Some(val);
+        }
+    }
+}
+
+/// Nothing is queued on a bitmap, operations run when called.
+impl<T> Defer for BitmapVec<T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algebra::{Addition, Subtraction},
+        backend::test_utils::{check_matches_simple, init, vec_entries, vector},
+        descriptor::Descriptor,
+        indices::Indices,
+        operations::VecOps,
+        vector::Vector,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_matches_simple() {
+        init();
+        check_matches_simple::<BitmapVec<i32>>();
+    }
+
+    #[test]
+    fn test_update_nvals() {
+        init();
+        let u: BitmapVec<i32> = vector(4, &[(1, 2), (2, 3)]);
+        let mut w: BitmapVec<i32> = vector(4, &[(0, 1), (1, 1), (3, 1)]);
+
+        // the count follows the entries dropped and added by an operation
+        w.e_wise_mult_binary_op(
+            Option::<BitmapVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            Subtraction::new(),
+            &w.clone(),
+            &u,
+            Some(Descriptor::DESC_R),
+        )
+        .unwrap();
+        assert_eq!(vec_entries(&w), vec![(1, -1)]);
+        assert_eq!(w.nvals().unwrap(), 1);
+
+        w.assign_value(
+            Option::<BitmapVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            5,
+            Indices::Range { begin: 2, end: 3 },
+            None,
+        )
+        .unwrap();
+        assert_eq!(w.nvals().unwrap(), 3);
+    }
+}
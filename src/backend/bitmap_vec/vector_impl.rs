@@ -0,0 +1,220 @@
+use std::ops::Index;
+
+use crate::{
+    algebra::BinaryOperator,
+    complement_mask::VectorComplementMask,
+    context,
+    mask::VecMask,
+    structure_mask::VectorStructureMask,
+    types::{IndexType, NoValue},
+    vector::{Vector, VectorUtils},
+    ApiError, ExecutionError, GblasResult,
+};
+
+use super::BitmapVec;
+
+impl<T> Vector for BitmapVec<T>
+where
+    T: Clone + PartialEq,
+{
+    type Scalar = T;
+
+    fn new(size: IndexType) -> GblasResult<Self> {
+        context::check_init()?;
+        if size == 0 {
+            return Err(ApiError::InvalidValue.into());
+        }
+        Ok(Self {
+            values: vec![None; size],
+            nvals: 0,
+        })
+    }
+
+    fn resize(&mut self, size: IndexType) -> GblasResult<NoValue> {
+        context::check_init()?;
+        if size == 0 {
+            return Err(ApiError::InvalidValue.into());
+        }
+
+        if size < self.size() {
+            self.nvals -= self.values[size..].iter().flatten().count();
+        }
+        self.values.resize(size, None);
+
+        Ok(())
+    }
+
+    fn clear(&mut self) -> GblasResult<NoValue> {
+        context::check_init()?;
+        self.values.fill(None);
+        self.nvals = 0;
+        Ok(())
+    }
+
+    fn size(&self) -> IndexType {
+        self.values.len()
+    }
+
+    fn nvals(&self) -> GblasResult<IndexType> {
+        context::check_init()?;
+        Ok(self.nvals)
+    }
+
+    fn build(
+        self,
+        indices: impl Iterator<Item = IndexType>,
+        values: impl Iterator<Item = Self::Scalar>,
+        _: IndexType,
+        dup: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
+    ) -> GblasResult<Self> {
+        context::check_init()?;
+        if self.nvals()? > 0 {
+            return Err(ApiError::OutputNotEmpty.into());
+        }
+
+        let mut s = self;
+        for (idx, val) in indices.zip(values) {
+            let slot = s
+                .values
+                .get_mut(idx)
+                .ok_or(ExecutionError::IndexOutOfBounds)?;
+            *slot = match slot.take() {
+                Some(prev) => Some(dup.op(prev, val)),
+                None => {
+                    s.nvals += 1;
+                    Some(val)
+                }
+            };
+        }
+
+        Ok(s)
+    }
+
+    fn set_element(&mut self, index: IndexType, val: Self::Scalar) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let slot = self.values.get_mut(index).ok_or(ApiError::InvalidIndex)?;
+        if slot.replace(val).is_none() {
+            self.nvals += 1;
+        }
+        Ok(())
+    }
+
+    fn remove_element(&mut self, index: IndexType) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let slot = self.values.get_mut(index).ok_or(ApiError::InvalidIndex)?;
+        if slot.take().is_some() {
+            self.nvals -= 1;
+        }
+        Ok(())
+    }
+
+    fn extract_element(&self, index: IndexType) -> GblasResult<&Self::Scalar> {
+        context::check_init()?;
+        let slot = self.values.get(index).ok_or(ApiError::InvalidIndex)?;
+        slot.as_ref().ok_or_else(|| ApiError::NoValue.into())
+    }
+
+    fn extract_tuples(self) -> GblasResult<(Vec<IndexType>, Vec<Self::Scalar>)> {
+        context::check_init()?;
+        let mut idxs = Vec::with_capacity(self.nvals);
+        let mut vals = Vec::with_capacity(self.nvals);
+        for (idx, val) in self.values.into_iter().enumerate() {
+            if let Some(val) = val {
+                idxs.push(idx);
+                vals.push(val);
+            }
+        }
+
+        Ok((idxs, vals))
+    }
+}
+
+impl<T> VectorUtils for BitmapVec<T>
+where
+    T: Clone + PartialEq,
+{
+    fn iter(&self) -> impl Iterator<Item = (IndexType, &Self::Scalar)> {
+        self.values
+            .iter()
+            .enumerate()
+            .filter_map(|(i, val)| val.as_ref().map(|val| (i, val)))
+    }
+}
+
+/// Value mask: an entry counts only if its value is not the zero (default) of `T`.
+impl<T> Index<IndexType> for BitmapVec<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    type Output = bool;
+
+    fn index(&self, index: IndexType) -> &Self::Output {
+        let found = <Self as Vector>::extract_element(self, index);
+        match found {
+            Ok(val) if *val != T::default() => &true,
+            _ => &false,
+        }
+    }
+}
+
+impl<T> VecMask<Self> for BitmapVec<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    fn complement(&self) -> VectorComplementMask<'_, Self> {
+        VectorComplementMask::new(self)
+    }
+
+    fn structure(&self) -> VectorStructureMask<'_, Self> {
+        VectorStructureMask::new(self)
+    }
+
+    fn shape(&self) -> IndexType {
+        self.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algebra::Addition, backend::test_utils::init};
+
+    #[test]
+    fn test_vector_ops() {
+        init();
+        let mut vec = BitmapVec::<i32>::new(6).unwrap();
+        vec.set_element(4, 1).unwrap();
+        vec.set_element(1, 0).unwrap();
+        vec.set_element(4, 2).unwrap();
+        assert!(vec.set_element(6, 1).is_err());
+        assert_eq!(vec.nvals().unwrap(), 2);
+        assert_eq!(vec.extract_element(4).unwrap(), &2);
+        assert!(vec.extract_element(2).is_err());
+
+        assert!(vec[4]);
+        assert!(!vec[1]);
+        assert!(vec.structure()[1]);
+        assert!(vec.complement()[2]);
+        assert!(!vec[6]);
+
+        vec.remove_element(1).unwrap();
+        vec.remove_element(1).unwrap();
+        vec.resize(3).unwrap();
+        assert_eq!(vec.nvals().unwrap(), 0);
+        vec.resize(5).unwrap();
+        vec.set_element(3, 7).unwrap();
+        assert_eq!(vec.extract_tuples().unwrap(), (vec![3], vec![7]));
+
+        let vec = BitmapVec::<i32>::new(3)
+            .unwrap()
+            .build(
+                [2, 0, 2].into_iter(),
+                [1, 2, 3].into_iter(),
+                3,
+                Addition::new(),
+            )
+            .unwrap();
+        assert_eq!(vec.nvals().unwrap(), 2);
+        assert_eq!(vec.extract_tuples().unwrap(), (vec![0, 2], vec![2, 4]));
+    }
+}
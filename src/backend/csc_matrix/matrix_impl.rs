@@ -0,0 +1,191 @@
+use std::ops::Index;
+
+use crate::{
+    algebra::BinaryOperator,
+    complement_mask::MatrixComplementMask,
+    context::WaitMode,
+    mask::MatMask,
+    matrix::{Compressed, Matrix, MatrixExtra, Orientation},
+    structure_mask::MatrixStructureMask,
+    types::{IndexType, NoValue},
+    GblasResult,
+};
+
+use super::{CscMatrix, CsrMatrix};
+
+impl<T> Matrix for CscMatrix<T>
+where
+    T: Clone + PartialEq,
+{
+    type Scalar = T;
+
+    fn new(rows: IndexType, cols: IndexType) -> GblasResult<Self> {
+        Ok(Self {
+            csr: CsrMatrix::new(cols, rows)?,
+        })
+    }
+
+    fn resize(&mut self, rows: IndexType, cols: IndexType) -> GblasResult<NoValue> {
+        self.csr.resize(cols, rows)
+    }
+
+    fn clear(&mut self) -> GblasResult<NoValue> {
+        self.csr.clear()
+    }
+
+    fn nrows(&self) -> IndexType {
+        self.csr.ncols()
+    }
+
+    fn ncols(&self) -> IndexType {
+        self.csr.nrows()
+    }
+
+    fn nvals(&self) -> GblasResult<IndexType> {
+        self.csr.nvals()
+    }
+
+    fn build(
+        self,
+        rows: impl Iterator<Item = IndexType>,
+        cols: impl Iterator<Item = IndexType>,
+        values: impl Iterator<Item = Self::Scalar>,
+        n: IndexType,
+        dup: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
+    ) -> GblasResult<Self> {
+        Ok(Self {
+            csr: self.csr.build(cols, rows, values, n, dup)?,
+        })
+    }
+
+    fn set_element(
+        &mut self,
+        row: IndexType,
+        col: IndexType,
+        value: Self::Scalar,
+    ) -> GblasResult<NoValue> {
+        self.csr.set_element(col, row, value)
+    }
+
+    fn remove_element(&mut self, row: IndexType, col: IndexType) -> GblasResult<NoValue> {
+        self.csr.remove_element(col, row)
+    }
+
+    fn extract_element(&self, row: IndexType, col: IndexType) -> GblasResult<&Self::Scalar> {
+        self.csr.extract_element(col, row)
+    }
+
+    fn extract_tuples(self) -> GblasResult<(Vec<IndexType>, Vec<IndexType>, Vec<Self::Scalar>)> {
+        let (cols, rows, values) = self.csr.extract_tuples()?;
+        Ok((rows, cols, values))
+    }
+
+    fn orientation(&self) -> Orientation {
+        Orientation::ByCol
+    }
+
+    fn extract_major(&self, k: IndexType) -> GblasResult<Vec<(IndexType, Self::Scalar)>> {
+        self.csr.extract_major(k)
+    }
+
+    fn compressed(&self) -> Option<Compressed<'_, Self::Scalar>> {
+        // the rows of the inner CSR are the columns, as told by the orientation
+        self.csr.compressed()
+    }
+
+    fn wait(&mut self, mode: WaitMode) -> GblasResult<NoValue> {
+        self.csr.wait(mode)
+    }
+}
+
+impl<T> MatrixExtra for CscMatrix<T>
+where
+    T: Clone + PartialEq,
+{
+    /// Entries come column by column.
+    fn iter(&self) -> impl Iterator<Item = (IndexType, IndexType, &Self::Scalar)> {
+        self.csr.iter().map(|(j, i, val)| (i, j, val))
+    }
+}
+
+/// Value mask: an entry counts only if its value is not the zero (default) of `T`.
+impl<T> Index<(IndexType, IndexType)> for CscMatrix<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    type Output = bool;
+
+    fn index(&self, index: (IndexType, IndexType)) -> &Self::Output {
+        &self.csr[(index.1, index.0)]
+    }
+}
+
+impl<T> MatMask<Self> for CscMatrix<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    fn complement(&self) -> MatrixComplementMask<'_, Self> {
+        MatrixComplementMask::new(self)
+    }
+
+    fn structure(&self) -> MatrixStructureMask<'_, Self> {
+        MatrixStructureMask::new(self)
+    }
+
+    fn shape(&self) -> (IndexType, IndexType) {
+        (self.nrows(), self.ncols())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algebra::Addition, backend::test_utils::init};
+
+    #[test]
+    fn test_matrix_ops() {
+        init();
+        let mut mat = CscMatrix::<f64>::new(4, 10).unwrap();
+        assert_eq!((mat.nrows(), mat.ncols()), (4, 10));
+        assert!(mat.set_element(1, 5, 5.0).is_ok());
+        assert!(mat.set_element(0, 0, 1.0).is_ok());
+        assert!(mat.set_element(3, 5, 2.0).is_ok());
+        assert!(mat.set_element(2, 9, 7.0).is_ok());
+        assert!(mat.set_element(4, 0, 7.0).is_err());
+        assert_eq!(mat.nvals().unwrap(), 4);
+        assert_eq!(mat.extract_element(3, 5).unwrap(), &2.0);
+        assert!(mat.extract_element(5, 3).is_err());
+        assert_eq!(mat.extract_major(5).unwrap(), vec![(1, 5.0), (3, 2.0)]);
+
+        mat.resize(3, 8).unwrap();
+        assert_eq!(mat.nvals().unwrap(), 2);
+        mat.remove_element(1, 5).unwrap();
+
+        assert!(mat[(0, 0)]);
+        assert!(!mat[(1, 5)]);
+        assert!(mat.complement()[(2, 3)]);
+
+        let entries: Vec<_> = mat.iter().map(|(i, j, v)| (i, j, *v)).collect();
+        assert_eq!(entries, vec![(0, 0, 1.0)]);
+    }
+
+    #[test]
+    fn test_build() {
+        init();
+        let mat = CscMatrix::<i32>::new(3, 3)
+            .unwrap()
+            .build(
+                [2, 0, 2, 0].into_iter(),
+                [1, 2, 1, 0].into_iter(),
+                [1, 2, 3, 4].into_iter(),
+                4,
+                Addition::new(),
+            )
+            .unwrap();
+        assert_eq!(mat.orientation(), Orientation::ByCol);
+        assert_eq!(
+            mat.extract_tuples().unwrap(),
+            (vec![0, 2, 0], vec![0, 1, 2], vec![4, 4, 2])
+        );
+    }
+}
@@ -0,0 +1,14 @@
+use super::CsrMatrix;
+
+/// Compressed sparse column (CSC) matrix.
+///
+/// Stored as the [`CsrMatrix`] of its transpose, so the entries of column `j` are contiguous and
+/// sorted by row. Operations read it [`ByCol`](crate::matrix::Orientation::ByCol), which makes
+/// e.g. a transposed [`CsrMatrix`] input and a [`CscMatrix`] output match without a transpose.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CscMatrix<T> {
+    csr: CsrMatrix<T>,
+}
+
+mod matrix_impl;
+mod ops_impl;
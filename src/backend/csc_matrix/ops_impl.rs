@@ -0,0 +1,249 @@
+use crate::{
+    backend::{
+        major_ops::MajorStorage,
+        pending::{Defer, Deferred},
+    },
+    types::IndexType,
+};
+
+use super::CscMatrix;
+
+impl<T> MajorStorage for CscMatrix<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    fn update_major(
+        &mut self,
+        majors: &[IndexType],
+        stored: impl Fn(IndexType) -> bool,
+        f: impl FnMut(IndexType, &mut Vec<(IndexType, T)>),
+    ) {
+        self.csr.update_major(majors, stored, f);
+    }
+}
+
+/// Operations are queued on the [`CsrMatrix`](crate::backend::CsrMatrix) holding the transpose.
+impl<T: 'static> Defer for CscMatrix<T> {
+    fn defers(&self) -> bool {
+        self.csr.defers()
+    }
+
+    fn defer(&mut self, op: Deferred<Self>) {
+        self.csr
+            .defer_as(op, |csr| CscMatrix { csr }, |csc| csc.csr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algebra::{Addition, MinFirstSemiring, PlusTimesSemiring},
+        backend::{
+            test_utils::{check_matches_sparse, entries, init, matrix},
+            CsrMatrix, SimpleVec, SparseMatrix,
+        },
+        descriptor::Descriptor,
+        indices::Indices,
+        mask::MatMask,
+        matrix::Matrix,
+        operations::{MatOps, VecOps},
+        vector::{Vector, VectorUtils},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_matches_sparse() {
+        init();
+        check_matches_sparse::<CscMatrix<i32>>();
+    }
+
+    #[test]
+    fn test_compressed_by_col() {
+        init();
+        let csc: CscMatrix<i32> = matrix(2, 4, &[(1, 0, 1), (0, 0, 2), (1, 3, 3)]);
+        let data = csc.compressed().unwrap();
+        assert_eq!(data.ptr, &[0, 2, 2, 2, 3]);
+        assert_eq!(data.minors, &[0, 1, 1]);
+        assert_eq!(data.values, &[2, 1, 3]);
+    }
+
+    #[test]
+    fn test_mixed_orientation() {
+        init();
+        let values = [(0, 0, 1), (0, 2, 2), (1, 1, 3), (2, 0, 4), (2, 1, 5)];
+        let mask = [(0, 0, 1), (1, 2, 1), (2, 0, 1), (2, 1, 1)];
+        let sparse: SparseMatrix<i32> = matrix(3, 3, &values);
+        let csr: CsrMatrix<i32> = matrix(3, 3, &values);
+        let csc: CscMatrix<i32> = matrix(3, 3, &values);
+        let m_sparse: SparseMatrix<i32> = matrix(3, 3, &mask);
+        let m_csr: CsrMatrix<i32> = matrix(3, 3, &mask);
+        let m_csc: CscMatrix<i32> = matrix(3, 3, &mask);
+
+        for desc in [
+            Descriptor::new(),
+            Descriptor::DESC_T0,
+            Descriptor::DESC_T1,
+            Descriptor::DESC_T0T1,
+        ] {
+            let mut expected: SparseMatrix<i32> = matrix(3, 3, &[(1, 0, 1)]);
+            expected
+                .mxm(
+                    Some(m_sparse.structure()),
+                    Some(Addition::new()),
+                    MinFirstSemiring::new(),
+                    &sparse,
+                    &sparse,
+                    Some(desc),
+                )
+                .unwrap();
+
+            let mut c: CscMatrix<i32> = matrix(3, 3, &[(1, 0, 1)]);
+            c.mxm(
+                Some(m_csc.structure()),
+                Some(Addition::new()),
+                MinFirstSemiring::new(),
+                &csc,
+                &csr,
+                Some(desc),
+            )
+            .unwrap();
+            assert_eq!(entries(&c), entries(&expected));
+
+            let mut c: CsrMatrix<i32> = matrix(3, 3, &[(1, 0, 1)]);
+            c.mxm(
+                Some(m_csr.structure()),
+                Some(Addition::new()),
+                MinFirstSemiring::new(),
+                &csr,
+                &csc,
+                Some(desc),
+            )
+            .unwrap();
+            assert_eq!(entries(&c), entries(&expected));
+        }
+    }
+
+    #[test]
+    fn test_extract_col() {
+        init();
+        let values = [(0, 1, 1), (2, 1, 2), (3, 0, 3), (3, 1, 4)];
+        let csc: CscMatrix<i32> = matrix(4, 4, &values);
+        let csr: CsrMatrix<i32> = matrix(4, 4, &values);
+
+        // read from the stored column of the CSC, gathered from the rows of the CSR
+        for (desc, col, expected) in [
+            (Descriptor::new(), 1, vec![(0, 4), (1, 2), (2, 2), (3, 1)]),
+            (Descriptor::new(), 2, vec![]),
+            (Descriptor::DESC_T0, 3, vec![(3, 3)]),
+        ] {
+            let indices = Indices::List(vec![3, 2, 2, 0]);
+            let mut w = SimpleVec::new(4).unwrap();
+            w.extract_col(
+                Option::<SimpleVec<i32>>::None,
+                Option::<Addition<i32>>::None,
+                &csc,
+                indices.clone(),
+                col,
+                Some(desc),
+            )
+            .unwrap();
+            let mut w_csr = SimpleVec::new(4).unwrap();
+            w_csr
+                .extract_col(
+                    Option::<SimpleVec<i32>>::None,
+                    Option::<Addition<i32>>::None,
+                    &csr,
+                    indices,
+                    col,
+                    Some(desc),
+                )
+                .unwrap();
+            assert_eq!(w, w_csr);
+            assert_eq!(w.iter().map(|(i, v)| (i, *v)).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_vectors() {
+        init();
+        let values = [(0, 1, 1), (0, 2, 2), (1, 0, 3), (2, 2, 4)];
+        let csc: CscMatrix<i32> = matrix(3, 3, &values);
+        let sparse: SparseMatrix<i32> = matrix(3, 3, &values);
+        let mut u = SimpleVec::new(3).unwrap();
+        u.set_element(0, 2).unwrap();
+        u.set_element(2, 3).unwrap();
+
+        for desc in [Descriptor::new(), Descriptor::DESC_T0] {
+            let mut w = SimpleVec::new(3).unwrap();
+            let mut expected = SimpleVec::new(3).unwrap();
+            w.mxv(
+                Option::<SimpleVec<i32>>::None,
+                Option::<Addition<i32>>::None,
+                PlusTimesSemiring::new(),
+                &csc,
+                &u,
+                Some(desc),
+            )
+            .unwrap();
+            expected
+                .mxv(
+                    Option::<SimpleVec<i32>>::None,
+                    Option::<Addition<i32>>::None,
+                    PlusTimesSemiring::new(),
+                    &sparse,
+                    &u,
+                    Some(desc),
+                )
+                .unwrap();
+            assert_eq!(w, expected);
+
+            let mut w = SimpleVec::new(3).unwrap();
+            let mut expected = SimpleVec::new(3).unwrap();
+            w.vxm(
+                Option::<SimpleVec<i32>>::None,
+                Option::<Addition<i32>>::None,
+                PlusTimesSemiring::new(),
+                &u,
+                &csc,
+                Some(desc),
+            )
+            .unwrap();
+            expected
+                .vxm(
+                    Option::<SimpleVec<i32>>::None,
+                    Option::<Addition<i32>>::None,
+                    PlusTimesSemiring::new(),
+                    &u,
+                    &sparse,
+                    Some(desc),
+                )
+                .unwrap();
+            assert_eq!(w, expected);
+        }
+
+        let mut c: CscMatrix<i32> = matrix(3, 3, &[(0, 0, 1), (2, 2, 2)]);
+        c.assign_col(
+            Option::<SimpleVec<i32>>::None,
+            Some(Addition::new()),
+            &u,
+            Indices::All,
+            2,
+            None,
+        )
+        .unwrap();
+        c.assign_row(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &u,
+            1,
+            Indices::All,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            entries(&c),
+            vec![(0, 0, 1), (0, 2, 2), (1, 0, 2), (1, 2, 3), (2, 2, 5)]
+        );
+    }
+}
@@ -0,0 +1,371 @@
+use std::ops::Index;
+
+use crate::{
+    algebra::BinaryOperator,
+    complement_mask::MatrixComplementMask,
+    context::{self, WaitMode},
+    mask::MatMask,
+    matrix::{Compressed, Matrix, MatrixExtra},
+    structure_mask::MatrixStructureMask,
+    types::{IndexType, NoValue},
+    ApiError, ExecutionError, GblasResult,
+};
+
+use super::{Csr, CsrMatrix, Pending};
+
+impl<T> CsrMatrix<T> {
+    fn check_index(&self, row: IndexType, col: IndexType) -> GblasResult<NoValue> {
+        if row >= self.nrows || col >= self.ncols {
+            return Err(ApiError::InvalidIndex.into());
+        }
+        Ok(())
+    }
+}
+
+impl<T> Matrix for CsrMatrix<T>
+where
+    T: Clone + PartialEq,
+{
+    type Scalar = T;
+
+    fn new(rows: IndexType, cols: IndexType) -> GblasResult<Self> {
+        context::check_init()?;
+        if rows == 0 || cols == 0 {
+            return Err(ApiError::InvalidValue.into());
+        }
+
+        Ok(Self {
+            data: Pending::new(Csr::new(rows)),
+            nrows: rows,
+            ncols: cols,
+        })
+    }
+
+    fn resize(&mut self, rows: IndexType, cols: IndexType) -> GblasResult<NoValue> {
+        context::check_init()?;
+        if rows == 0 || cols == 0 {
+            return Err(ApiError::InvalidValue.into());
+        }
+
+        let csr = self.data.get_mut();
+        if rows < self.nrows {
+            let nvals = csr.row_ptr[rows];
+            csr.row_ptr.truncate(rows + 1);
+            csr.col_idx.truncate(nvals);
+            csr.values.truncate(nvals);
+        } else {
+            let nvals = csr.col_idx.len();
+            csr.row_ptr.resize(rows + 1, nvals);
+        }
+        self.nrows = rows;
+
+        if cols < self.ncols {
+            // remove all elements that are out of bounds for new col
+            csr.update_rows(&[], |_| true, |_, row| row.retain(|(col, _)| *col < cols));
+        }
+        self.ncols = cols;
+
+        Ok(())
+    }
+
+    fn clear(&mut self) -> GblasResult<NoValue> {
+        context::check_init()?;
+        // queued updates are dropped along with the old entries
+        self.data = Pending::new(Csr::new(self.nrows));
+        Ok(())
+    }
+
+    fn nrows(&self) -> IndexType {
+        self.nrows
+    }
+
+    fn ncols(&self) -> IndexType {
+        self.ncols
+    }
+
+    fn nvals(&self) -> GblasResult<IndexType> {
+        context::check_init()?;
+        Ok(self.data.get().values.len())
+    }
+
+    fn build(
+        self,
+        rows: impl Iterator<Item = IndexType>,
+        cols: impl Iterator<Item = IndexType>,
+        values: impl Iterator<Item = Self::Scalar>,
+        _: IndexType,
+        dup: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
+    ) -> GblasResult<Self> {
+        context::check_init()?;
+        if self.nvals()? > 0 {
+            return Err(ApiError::OutputNotEmpty.into());
+        }
+
+        let mut tuples: Vec<(IndexType, IndexType, T)> = Vec::new();
+        for ((row, col), value) in rows.zip(cols).zip(values) {
+            if row >= self.nrows || col >= self.ncols {
+                return Err(ExecutionError::IndexOutOfBounds.into());
+            }
+            tuples.push((row, col, value));
+        }
+        // stable, so duplicates are combined in the order they were given
+        tuples.sort_by_key(|val| (val.0, val.1));
+
+        let mut s = self;
+        let csr = s.data.get_mut();
+        for (row, col, value) in tuples {
+            match csr.col_idx.last() {
+                Some(last) if *last == col && csr.row_ptr[row + 1] > 0 => {
+                    let prev = csr.values.last_mut().unwrap();
+                    *prev = dup.op(prev.clone(), value);
+                }
+                _ => {
+                    csr.col_idx.push(col);
+                    csr.values.push(value);
+                    csr.row_ptr[row + 1] += 1;
+                }
+            }
+        }
+        // row_ptr holds the count of each row so far
+        for i in 0..s.nrows {
+            csr.row_ptr[i + 1] += csr.row_ptr[i];
+        }
+
+        Ok(s)
+    }
+
+    fn set_element(
+        &mut self,
+        row: IndexType,
+        col: IndexType,
+        value: Self::Scalar,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        self.check_index(row, col)?;
+        if context::is_nonblocking() {
+            self.data.push((row, col, Some(value)));
+            return Ok(());
+        }
+
+        let csr = self.data.get_mut();
+        let range = csr.range(row);
+        match csr.col_idx[range.clone()].binary_search(&col) {
+            Ok(idx) => csr.values[range.start + idx] = value,
+            Err(idx) => {
+                csr.col_idx.insert(range.start + idx, col);
+                csr.values.insert(range.start + idx, value);
+                for ptr in csr.row_ptr[row + 1..].iter_mut() {
+                    *ptr += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_element(&mut self, row: IndexType, col: IndexType) -> GblasResult<NoValue> {
+        context::check_init()?;
+        self.check_index(row, col)?;
+        if context::is_nonblocking() {
+            self.data.push((row, col, None));
+            return Ok(());
+        }
+
+        let csr = self.data.get_mut();
+        let range = csr.range(row);
+        if let Ok(idx) = csr.col_idx[range.clone()].binary_search(&col) {
+            csr.col_idx.remove(range.start + idx);
+            csr.values.remove(range.start + idx);
+            for ptr in csr.row_ptr[row + 1..].iter_mut() {
+                *ptr -= 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn extract_element(&self, row: IndexType, col: IndexType) -> GblasResult<&Self::Scalar> {
+        context::check_init()?;
+        self.check_index(row, col)?;
+
+        let csr = self.data.get();
+        let range = csr.range(row);
+        csr.col_idx[range.clone()]
+            .binary_search(&col)
+            .map(|idx| &csr.values[range.start + idx])
+            .map_err(|_| ApiError::NoValue.into())
+    }
+
+    fn extract_tuples(self) -> GblasResult<(Vec<IndexType>, Vec<IndexType>, Vec<Self::Scalar>)> {
+        context::check_init()?;
+        let Csr {
+            row_ptr,
+            col_idx,
+            values,
+        } = self.data.into_inner();
+
+        let mut rows = Vec::with_capacity(values.len());
+        for (i, bounds) in row_ptr.windows(2).enumerate() {
+            rows.extend(std::iter::repeat_n(i, bounds[1] - bounds[0]));
+        }
+
+        Ok((rows, col_idx, values))
+    }
+
+    fn extract_major(&self, k: IndexType) -> GblasResult<Vec<(IndexType, Self::Scalar)>> {
+        context::check_init()?;
+        if k >= self.nrows {
+            return Err(ApiError::InvalidIndex.into());
+        }
+        let csr = self.data.get();
+        let range = csr.range(k);
+        Ok(csr.col_idx[range.clone()]
+            .iter()
+            .copied()
+            .zip(csr.values[range].iter().cloned())
+            .collect())
+    }
+
+    fn compressed(&self) -> Option<Compressed<'_, Self::Scalar>> {
+        let csr = self.data.get();
+        Some(Compressed::new(
+            None,
+            &csr.row_ptr,
+            &csr.col_idx,
+            &csr.values,
+        ))
+    }
+
+    fn wait(&mut self, _mode: WaitMode) -> GblasResult<NoValue> {
+        context::check_init()?;
+        self.data.get_mut();
+        Ok(())
+    }
+}
+
+impl<T> MatrixExtra for CsrMatrix<T>
+where
+    T: Clone + PartialEq,
+{
+    fn iter(&self) -> impl Iterator<Item = (IndexType, IndexType, &Self::Scalar)> {
+        let csr = self.data.get();
+        (0..self.nrows).flat_map(move |i| {
+            let range = csr.range(i);
+            csr.col_idx[range.clone()]
+                .iter()
+                .zip(&csr.values[range])
+                .map(move |(j, v)| (i, *j, v))
+        })
+    }
+}
+
+/// Value mask: an entry counts only if its value is not the zero (default) of `T`.
+impl<T> Index<(IndexType, IndexType)> for CsrMatrix<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    type Output = bool;
+
+    fn index(&self, index: (IndexType, IndexType)) -> &Self::Output {
+        let found = <Self as Matrix>::extract_element(self, index.0, index.1);
+        match found {
+            Ok(val) if *val != T::default() => &true,
+            _ => &false,
+        }
+    }
+}
+
+impl<T> MatMask<Self> for CsrMatrix<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    fn complement(&self) -> MatrixComplementMask<'_, Self> {
+        MatrixComplementMask::new(self)
+    }
+
+    fn structure(&self) -> MatrixStructureMask<'_, Self> {
+        MatrixStructureMask::new(self)
+    }
+
+    fn shape(&self) -> (IndexType, IndexType) {
+        (self.nrows(), self.ncols())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algebra::Addition, backend::test_utils::init};
+
+    #[test]
+    fn test_matrix_ops() {
+        init();
+        let mut mat = CsrMatrix::<f64>::new(10, 10).unwrap();
+        assert!(mat.set_element(1, 5, 5.0).is_ok());
+        assert!(mat.set_element(0, 0, 1.0).is_ok());
+        assert!(mat.set_element(1, 1, 2.0).is_ok());
+        assert!(mat.set_element(1, 5, 6.0).is_ok());
+        assert!(mat.set_element(7, 7, 10.0).is_ok());
+        assert!(mat.set_element(2, 9, 7.0).is_ok());
+        assert!(mat.set_element(10, 0, 7.0).is_err());
+        assert_eq!(mat.nvals().unwrap(), 5);
+        assert_eq!(mat.extract_element(1, 5).unwrap(), &6.0);
+        assert_eq!(mat.extract_element(2, 9).unwrap(), &7.0);
+        assert!(mat.extract_element(3, 3).is_err());
+
+        mat.resize(8, 8).unwrap();
+        assert_eq!(mat.nvals().unwrap(), 4);
+        assert!(mat.extract_element(2, 9).is_err());
+        mat.resize(12, 8).unwrap();
+        mat.set_element(11, 0, 3.0).unwrap();
+
+        mat.remove_element(1, 5).unwrap();
+        mat.remove_element(1, 4).unwrap();
+        assert!(mat.extract_element(1, 5).is_err());
+
+        assert!(mat[(0, 0)]);
+        assert!(!mat[(1, 5)]);
+        assert!(mat.structure()[(7, 7)]);
+        assert!(mat.complement()[(3, 3)]);
+
+        let entries: Vec<_> = mat.iter().map(|(i, j, v)| (i, j, *v)).collect();
+        assert_eq!(
+            entries,
+            vec![(0, 0, 1.0), (1, 1, 2.0), (7, 7, 10.0), (11, 0, 3.0)]
+        );
+        assert_eq!(
+            mat.extract_tuples().unwrap(),
+            (
+                vec![0, 1, 7, 11],
+                vec![0, 1, 7, 0],
+                vec![1.0, 2.0, 10.0, 3.0]
+            )
+        );
+    }
+
+    #[test]
+    fn test_build() {
+        init();
+        let mat = CsrMatrix::<i32>::new(3, 3)
+            .unwrap()
+            .build(
+                [2, 0, 2, 0].into_iter(),
+                [1, 2, 1, 0].into_iter(),
+                [1, 2, 3, 4].into_iter(),
+                4,
+                Addition::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            mat.extract_tuples().unwrap(),
+            (vec![0, 0, 2], vec![0, 2, 1], vec![4, 2, 4])
+        );
+
+        let res = CsrMatrix::<i32>::new(3, 3).unwrap().build(
+            [3].into_iter(),
+            [0].into_iter(),
+            [1].into_iter(),
+            1,
+            Addition::new(),
+        );
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use crate::types::IndexType;
+
+use super::pending::{merge_updates, Assemble, Pending};
+
+/// Compressed sparse row (CSR) matrix.
+///
+/// The entries of row `i` are `col_idx[row_ptr[i]..row_ptr[i + 1]]`, sorted by column, with the
+/// matching `values`. All the entries share three contiguous arrays instead of one allocation per
+/// row like [`SparseMatrix`](super::SparseMatrix).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsrMatrix<T> {
+    data: Pending<Csr<T>>,
+    nrows: IndexType,
+    ncols: IndexType,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Csr<T> {
+    row_ptr: Vec<IndexType>,
+    col_idx: Vec<IndexType>,
+    values: Vec<T>,
+}
+
+impl<T> Csr<T> {
+    fn new(nrows: IndexType) -> Self {
+        Self {
+            row_ptr: vec![0; nrows + 1],
+            col_idx: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Positions of the entries of row `i` in `col_idx` and `values`.
+    fn range(&self, i: IndexType) -> std::ops::Range<usize> {
+        self.row_ptr[i]..self.row_ptr[i + 1]
+    }
+
+    /// Updates the rows in `rows` (sorted) and the non-empty ones for which `stored` holds, `f`
+    /// gets the row index and its sorted `(col, value)` entries, which must still be sorted when it
+    /// returns.
+    ///
+    /// The entries between two updated rows are moved in one go and `row_ptr` is shifted in a
+    /// single pass. Values are moved, not cloned, and a single buffer is reused for every row.
+    fn update_rows(
+        &mut self,
+        rows: &[IndexType],
+        stored: impl Fn(IndexType) -> bool,
+        mut f: impl FnMut(IndexType, &mut Vec<(IndexType, T)>),
+    ) {
+        let nrows = self.row_ptr.len() - 1;
+        let nvals = self.col_idx.len();
+        let mut old = core::mem::take(&mut self.col_idx)
+            .into_iter()
+            .zip(core::mem::take(&mut self.values));
+        self.col_idx.reserve(nvals);
+        self.values.reserve(nvals);
+
+        let mut rows = rows.iter().copied().peekable();
+        // entries of the old arrays already moved
+        let mut moved = 0;
+        let mut end = 0;
+        let mut row = Vec::new();
+        for i in 0..nrows {
+            // `row_ptr[i]` is already shifted, the old one is the previous end
+            let begin = end;
+            end = self.row_ptr[i + 1];
+            let listed = rows.next_if_eq(&i).is_some();
+            while rows.next_if_eq(&i).is_some() {}
+            if listed || (begin < end && stored(i)) {
+                for (j, val) in old.by_ref().take(begin - moved) {
+                    self.col_idx.push(j);
+                    self.values.push(val);
+                }
+                row.extend(old.by_ref().take(end - begin));
+                moved = end;
+
+                f(i, &mut row);
+                for (j, val) in row.drain(..) {
+                    self.col_idx.push(j);
+                    self.values.push(val);
+                }
+            }
+            // shifted by what the updated rows added or removed
+            self.row_ptr[i + 1] = self.col_idx.len() + end - moved;
+        }
+        for (j, val) in old {
+            self.col_idx.push(j);
+            self.values.push(val);
+        }
+    }
+}
+
+impl<T> Assemble for Csr<T> {
+    type Update = (IndexType, IndexType, Option<T>);
+
+    fn assemble(&mut self, mut updates: Vec<Self::Update>) {
+        // stable, so updates of a row keep their order
+        updates.sort_by_key(|val| val.0);
+
+        let rows: Vec<IndexType> = updates.iter().map(|val| val.0).collect();
+        let mut it = updates.into_iter().peekable();
+        self.update_rows(
+            &rows,
+            |_| false,
+            |i, row| {
+                let mut row_updates = Vec::new();
+                while let Some((_, col, val)) = it.next_if(|next| next.0 == i) {
+                    row_updates.push((col, val));
+                }
+                if !row_updates.is_empty() {
+                    merge_updates(row, row_updates);
+                }
+            },
+        );
+    }
+}
+
+mod matrix_impl;
+mod ops_impl;
@@ -0,0 +1,168 @@
+use crate::{
+    backend::{
+        major_ops::MajorStorage,
+        pending::{Defer, Deferred, Pending},
+    },
+    context,
+    types::IndexType,
+};
+
+use super::{Csr, CsrMatrix};
+
+impl<T> MajorStorage for CsrMatrix<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    fn update_major(
+        &mut self,
+        majors: &[IndexType],
+        stored: impl Fn(IndexType) -> bool,
+        f: impl FnMut(IndexType, &mut Vec<(IndexType, T)>),
+    ) {
+        self.data.get_mut().update_rows(majors, stored, f);
+    }
+}
+
+impl<T: 'static> CsrMatrix<T> {
+    /// Queues `op` on the object `wrap` builds around this matrix, `unwrap` gives the matrix back
+    /// once `op` has run. Lets [`CscMatrix`](crate::backend::CscMatrix) share the queue.
+    pub(crate) fn defer_as<O: 'static>(
+        &mut self,
+        op: Deferred<O>,
+        wrap: fn(Self) -> O,
+        unwrap: fn(O) -> Self,
+    ) {
+        let (nrows, ncols) = (self.nrows, self.ncols);
+        self.data.push_op(Box::new(move |data| {
+            // the operation runs on a matrix around the assembled storage
+            let mut obj = wrap(Self {
+                data: Pending::new(core::mem::replace(data, Csr::new(0))),
+                nrows,
+                ncols,
+            });
+            op(&mut obj);
+            *data = unwrap(obj).data.into_inner();
+        }));
+    }
+}
+
+/// Queues operations on the pending rows in nonblocking mode.
+impl<T: 'static> Defer for CsrMatrix<T> {
+    fn defers(&self) -> bool {
+        context::is_nonblocking()
+    }
+
+    fn defer(&mut self, op: Deferred<Self>) {
+        self.defer_as(op, |mat| mat, |mat| mat);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algebra::{Addition, PlusTimesSemiring},
+        backend::{
+            test_utils::{check_matches_sparse, entries, init, matrix},
+            SimpleVec,
+        },
+        descriptor::Descriptor,
+        indices::Indices,
+        matrix::Matrix,
+        operations::MatOps,
+        vector::Vector,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_matches_sparse() {
+        init();
+        check_matches_sparse::<CsrMatrix<i32>>();
+    }
+
+    #[test]
+    fn test_compressed_empty_rows() {
+        init();
+        let mut a: CsrMatrix<i32> = matrix(5, 3, &[(1, 2, 1), (1, 0, 2), (3, 1, 3)]);
+        a.resize(6, 3).unwrap();
+        let data = a.compressed().unwrap();
+        assert_eq!(data.ptr, &[0, 0, 2, 2, 3, 3, 3]);
+        assert_eq!(data.minors, &[0, 2, 1]);
+        assert!(data.get(0).is_empty() && data.get(5).is_empty());
+        assert_eq!(data.indices().collect::<Vec<_>>(), vec![1, 3]);
+
+        // read in place, empty rows of the input give empty rows of the product
+        let mut c = CsrMatrix::new(6, 6).unwrap();
+        c.mxm(
+            Option::<CsrMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &a,
+            Some(Descriptor::DESC_T1),
+        )
+        .unwrap();
+        assert_eq!(entries(&c), vec![(1, 1, 5), (3, 3, 9)]);
+        assert_eq!(c.compressed().unwrap().ptr, &[0, 0, 1, 1, 2, 2, 2]);
+    }
+
+    #[test]
+    fn test_assign() {
+        init();
+        let mut c: CsrMatrix<i32> = matrix(3, 3, &[(0, 0, 1), (2, 2, 2)]);
+        let u = {
+            let mut u = SimpleVec::new(2).unwrap();
+            u.set_element(0, 5).unwrap();
+            u
+        };
+
+        c.assign_col(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &u,
+            Indices::List(vec![1, 2]),
+            0,
+            None,
+        )
+        .unwrap();
+        c.assign_row(
+            Option::<SimpleVec<i32>>::None,
+            Some(Addition::new()),
+            &u,
+            2,
+            Indices::List(vec![2, 0]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(entries(&c), vec![(0, 0, 1), (1, 0, 5), (2, 2, 7)]);
+
+        // only row 1 is spliced, the rows after it are shifted
+        c.assign_value(
+            Option::<CsrMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            3,
+            Indices::List(vec![1]),
+            Indices::List(vec![2]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            entries(&c),
+            vec![(0, 0, 1), (1, 0, 5), (1, 2, 3), (2, 2, 7)]
+        );
+        assert_eq!(c.compressed().unwrap().ptr, &[0, 1, 3, 4]);
+
+        // a row emptied shifts the following ones back
+        c.assign_row(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &SimpleVec::new(3).unwrap(),
+            0,
+            Indices::All,
+            None,
+        )
+        .unwrap();
+        assert_eq!(entries(&c), vec![(1, 0, 5), (1, 2, 3), (2, 2, 7)]);
+        assert_eq!(c.compressed().unwrap().ptr, &[0, 0, 2, 3]);
+    }
+}
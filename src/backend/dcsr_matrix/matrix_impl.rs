@@ -0,0 +1,378 @@
+use std::ops::Index;
+
+use crate::{
+    algebra::BinaryOperator,
+    complement_mask::MatrixComplementMask,
+    context::{self, WaitMode},
+    mask::MatMask,
+    matrix::{Compressed, Matrix, MatrixExtra},
+    structure_mask::MatrixStructureMask,
+    types::{IndexType, NoValue},
+    ApiError, ExecutionError, GblasResult,
+};
+
+use super::{Dcsr, DcsrMatrix, Pending};
+
+impl<T> DcsrMatrix<T> {
+    fn check_index(&self, row: IndexType, col: IndexType) -> GblasResult<NoValue> {
+        if row >= self.nrows || col >= self.ncols {
+            return Err(ApiError::InvalidIndex.into());
+        }
+        Ok(())
+    }
+}
+
+impl<T> Matrix for DcsrMatrix<T>
+where
+    T: Clone + PartialEq,
+{
+    type Scalar = T;
+
+    fn new(rows: IndexType, cols: IndexType) -> GblasResult<Self> {
+        context::check_init()?;
+        if rows == 0 || cols == 0 {
+            return Err(ApiError::InvalidValue.into());
+        }
+
+        Ok(Self {
+            data: Pending::new(Dcsr::new()),
+            nrows: rows,
+            ncols: cols,
+        })
+    }
+
+    fn resize(&mut self, rows: IndexType, cols: IndexType) -> GblasResult<NoValue> {
+        context::check_init()?;
+        if rows == 0 || cols == 0 {
+            return Err(ApiError::InvalidValue.into());
+        }
+
+        let dcsr = self.data.get_mut();
+        if rows < self.nrows {
+            let k = dcsr.row_idx.partition_point(|i| *i < rows);
+            let nvals = dcsr.row_ptr[k];
+            dcsr.row_idx.truncate(k);
+            dcsr.row_ptr.truncate(k + 1);
+            dcsr.col_idx.truncate(nvals);
+            dcsr.values.truncate(nvals);
+        }
+        self.nrows = rows;
+
+        if cols < self.ncols {
+            // remove all elements that are out of bounds for new col
+            dcsr.update_rows(&[], |_| true, |_, row| row.retain(|(col, _)| *col < cols));
+        }
+        self.ncols = cols;
+
+        Ok(())
+    }
+
+    fn clear(&mut self) -> GblasResult<NoValue> {
+        context::check_init()?;
+        // queued updates are dropped along with the old entries
+        self.data = Pending::new(Dcsr::new());
+        Ok(())
+    }
+
+    fn nrows(&self) -> IndexType {
+        self.nrows
+    }
+
+    fn ncols(&self) -> IndexType {
+        self.ncols
+    }
+
+    fn nvals(&self) -> GblasResult<IndexType> {
+        context::check_init()?;
+        Ok(self.data.get().values.len())
+    }
+
+    fn build(
+        self,
+        rows: impl Iterator<Item = IndexType>,
+        cols: impl Iterator<Item = IndexType>,
+        values: impl Iterator<Item = Self::Scalar>,
+        _: IndexType,
+        dup: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
+    ) -> GblasResult<Self> {
+        context::check_init()?;
+        if self.nvals()? > 0 {
+            return Err(ApiError::OutputNotEmpty.into());
+        }
+
+        let mut tuples: Vec<(IndexType, IndexType, T)> = Vec::new();
+        for ((row, col), value) in rows.zip(cols).zip(values) {
+            if row >= self.nrows || col >= self.ncols {
+                return Err(ExecutionError::IndexOutOfBounds.into());
+            }
+            tuples.push((row, col, value));
+        }
+        // stable, so duplicates are combined in the order they were given
+        tuples.sort_by_key(|val| (val.0, val.1));
+
+        let mut s = self;
+        let dcsr = s.data.get_mut();
+        for (row, col, value) in tuples {
+            if dcsr.row_idx.last() != Some(&row) {
+                dcsr.row_idx.push(row);
+                dcsr.row_ptr.push(dcsr.col_idx.len());
+            }
+            let k = dcsr.row_idx.len() - 1;
+            match dcsr.col_idx.last() {
+                Some(last) if *last == col && dcsr.row_ptr[k] < dcsr.col_idx.len() => {
+                    let prev = dcsr.values.last_mut().unwrap();
+                    *prev = dup.op(prev.clone(), value);
+                }
+                _ => {
+                    dcsr.col_idx.push(col);
+                    dcsr.values.push(value);
+                }
+            }
+            dcsr.row_ptr[k + 1] = dcsr.col_idx.len();
+        }
+
+        Ok(s)
+    }
+
+    fn set_element(
+        &mut self,
+        row: IndexType,
+        col: IndexType,
+        value: Self::Scalar,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        self.check_index(row, col)?;
+        if context::is_nonblocking() {
+            self.data.push((row, col, Some(value)));
+            return Ok(());
+        }
+
+        let dcsr = self.data.get_mut();
+        let k = dcsr.find(row).unwrap_or_else(|k| {
+            // new empty row
+            dcsr.row_idx.insert(k, row);
+            dcsr.row_ptr.insert(k + 1, dcsr.row_ptr[k]);
+            k
+        });
+        let range = dcsr.range(k);
+        match dcsr.col_idx[range.clone()].binary_search(&col) {
+            Ok(idx) => dcsr.values[range.start + idx] = value,
+            Err(idx) => {
+                dcsr.col_idx.insert(range.start + idx, col);
+                dcsr.values.insert(range.start + idx, value);
+                for ptr in dcsr.row_ptr[k + 1..].iter_mut() {
+                    *ptr += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_element(&mut self, row: IndexType, col: IndexType) -> GblasResult<NoValue> {
+        context::check_init()?;
+        self.check_index(row, col)?;
+        if context::is_nonblocking() {
+            self.data.push((row, col, None));
+            return Ok(());
+        }
+
+        let dcsr = self.data.get_mut();
+        let Ok(k) = dcsr.find(row) else {
+            return Ok(());
+        };
+        let range = dcsr.range(k);
+        if let Ok(idx) = dcsr.col_idx[range.clone()].binary_search(&col) {
+            dcsr.col_idx.remove(range.start + idx);
+            dcsr.values.remove(range.start + idx);
+            for ptr in dcsr.row_ptr[k + 1..].iter_mut() {
+                *ptr -= 1;
+            }
+            if range.len() == 1 {
+                dcsr.row_idx.remove(k);
+                dcsr.row_ptr.remove(k + 1);
+            }
+        }
+        Ok(())
+    }
+
+    fn extract_element(&self, row: IndexType, col: IndexType) -> GblasResult<&Self::Scalar> {
+        context::check_init()?;
+        self.check_index(row, col)?;
+
+        let dcsr = self.data.get();
+        let k = dcsr.find(row).map_err(|_| ApiError::NoValue)?;
+        let range = dcsr.range(k);
+        dcsr.col_idx[range.clone()]
+            .binary_search(&col)
+            .map(|idx| &dcsr.values[range.start + idx])
+            .map_err(|_| ApiError::NoValue.into())
+    }
+
+    fn extract_tuples(self) -> GblasResult<(Vec<IndexType>, Vec<IndexType>, Vec<Self::Scalar>)> {
+        context::check_init()?;
+        let Dcsr {
+            row_idx,
+            row_ptr,
+            col_idx,
+            values,
+        } = self.data.into_inner();
+
+        let mut rows = Vec::with_capacity(values.len());
+        for (i, bounds) in row_idx.iter().zip(row_ptr.windows(2)) {
+            rows.extend(std::iter::repeat_n(*i, bounds[1] - bounds[0]));
+        }
+
+        Ok((rows, col_idx, values))
+    }
+
+    fn extract_major(&self, k: IndexType) -> GblasResult<Vec<(IndexType, Self::Scalar)>> {
+        context::check_init()?;
+        if k >= self.nrows {
+            return Err(ApiError::InvalidIndex.into());
+        }
+        let dcsr = self.data.get();
+        let Ok(pos) = dcsr.find(k) else {
+            return Ok(Vec::new());
+        };
+        let range = dcsr.range(pos);
+        Ok(dcsr.col_idx[range.clone()]
+            .iter()
+            .copied()
+            .zip(dcsr.values[range].iter().cloned())
+            .collect())
+    }
+
+    fn compressed(&self) -> Option<Compressed<'_, Self::Scalar>> {
+        let dcsr = self.data.get();
+        Some(Compressed::new(
+            Some(&dcsr.row_idx),
+            &dcsr.row_ptr,
+            &dcsr.col_idx,
+            &dcsr.values,
+        ))
+    }
+
+    fn wait(&mut self, _mode: WaitMode) -> GblasResult<NoValue> {
+        context::check_init()?;
+        self.data.get_mut();
+        Ok(())
+    }
+}
+
+impl<T> MatrixExtra for DcsrMatrix<T>
+where
+    T: Clone + PartialEq,
+{
+    fn iter(&self) -> impl Iterator<Item = (IndexType, IndexType, &Self::Scalar)> {
+        let dcsr = self.data.get();
+        dcsr.row_idx.iter().enumerate().flat_map(move |(k, i)| {
+            let range = dcsr.range(k);
+            dcsr.col_idx[range.clone()]
+                .iter()
+                .zip(&dcsr.values[range])
+                .map(move |(j, v)| (*i, *j, v))
+        })
+    }
+}
+
+/// Value mask: an entry counts only if its value is not the zero (default) of `T`.
+impl<T> Index<(IndexType, IndexType)> for DcsrMatrix<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    type Output = bool;
+
+    fn index(&self, index: (IndexType, IndexType)) -> &Self::Output {
+        let found = <Self as Matrix>::extract_element(self, index.0, index.1);
+        match found {
+            Ok(val) if *val != T::default() => &true,
+            _ => &false,
+        }
+    }
+}
+
+impl<T> MatMask<Self> for DcsrMatrix<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    fn complement(&self) -> MatrixComplementMask<'_, Self> {
+        MatrixComplementMask::new(self)
+    }
+
+    fn structure(&self) -> MatrixStructureMask<'_, Self> {
+        MatrixStructureMask::new(self)
+    }
+
+    fn shape(&self) -> (IndexType, IndexType) {
+        (self.nrows(), self.ncols())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algebra::Addition, backend::test_utils::init};
+
+    const N: IndexType = 1 << 40;
+
+    #[test]
+    fn test_matrix_ops() {
+        init();
+        let mut mat = DcsrMatrix::<f64>::new(N, N).unwrap();
+        assert!(mat.set_element(1, 5, 5.0).is_ok());
+        assert!(mat.set_element(N - 1, N - 1, 1.0).is_ok());
+        assert!(mat.set_element(1, 1, 2.0).is_ok());
+        assert!(mat.set_element(1, 5, 6.0).is_ok());
+        assert!(mat.set_element(1 << 30, 7, 10.0).is_ok());
+        assert!(mat.set_element(N, 0, 7.0).is_err());
+        assert_eq!(mat.nvals().unwrap(), 4);
+        assert_eq!(mat.extract_element(1, 5).unwrap(), &6.0);
+        assert!(mat.extract_element(3, 3).is_err());
+        assert_eq!(mat.extract_major(1).unwrap(), vec![(1, 2.0), (5, 6.0)]);
+        assert!(mat.extract_major(2).unwrap().is_empty());
+
+        mat.remove_element(1 << 30, 7).unwrap();
+        mat.remove_element(1 << 30, 7).unwrap();
+        assert!(mat.extract_element(1 << 30, 7).is_err());
+        assert_eq!(mat.data.get().row_idx, vec![1, N - 1]);
+
+        assert!(mat[(1, 1)]);
+        assert!(!mat[(1, 4)]);
+        assert!(mat.structure()[(N - 1, N - 1)]);
+        assert!(mat.complement()[(3, 3)]);
+
+        mat.resize(N - 1, 3).unwrap();
+        assert_eq!(mat.nvals().unwrap(), 1);
+        let entries: Vec<_> = mat.iter().map(|(i, j, v)| (i, j, *v)).collect();
+        assert_eq!(entries, vec![(1, 1, 2.0)]);
+        assert_eq!(mat.extract_tuples().unwrap(), (vec![1], vec![1], vec![2.0]));
+    }
+
+    #[test]
+    fn test_build() {
+        init();
+        let mat = DcsrMatrix::<i32>::new(N, 3)
+            .unwrap()
+            .build(
+                [N - 1, 0, N - 1, 0].into_iter(),
+                [1, 2, 1, 0].into_iter(),
+                [1, 2, 3, 4].into_iter(),
+                4,
+                Addition::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            mat.extract_tuples().unwrap(),
+            (vec![0, 0, N - 1], vec![0, 2, 1], vec![4, 2, 4])
+        );
+
+        let res = DcsrMatrix::<i32>::new(3, 3).unwrap().build(
+            [3].into_iter(),
+            [0].into_iter(),
+            [1].into_iter(),
+            1,
+            Addition::new(),
+        );
+        assert!(res.is_err());
+    }
+}
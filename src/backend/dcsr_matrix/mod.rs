@@ -0,0 +1,119 @@
+use crate::types::IndexType;
+
+use super::pending::{merge_updates, Assemble, Pending};
+
+/// Doubly compressed sparse row (DCSR) matrix, for hypersparse matrices.
+///
+/// Like [`CsrMatrix`](super::CsrMatrix), but only the non-empty rows are listed: the entries of
+/// row `row_idx[k]` are `col_idx[row_ptr[k]..row_ptr[k + 1]]`. Memory is proportional to the
+/// number of entries and not to the number of rows, so e.g. a _2^40 x 2^40_ matrix is fine.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DcsrMatrix<T> {
+    data: Pending<Dcsr<T>>,
+    nrows: IndexType,
+    ncols: IndexType,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Dcsr<T> {
+    row_idx: Vec<IndexType>,
+    row_ptr: Vec<IndexType>,
+    col_idx: Vec<IndexType>,
+    values: Vec<T>,
+}
+
+impl<T> Dcsr<T> {
+    fn new() -> Self {
+        Self {
+            row_idx: Vec::new(),
+            row_ptr: vec![0],
+            col_idx: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Position of row `i` in `row_idx`, or where it would be inserted.
+    fn find(&self, i: IndexType) -> Result<usize, usize> {
+        self.row_idx.binary_search(&i)
+    }
+
+    /// Positions of the entries of the `k`-th non-empty row in `col_idx` and `values`.
+    fn range(&self, k: usize) -> std::ops::Range<usize> {
+        self.row_ptr[k]..self.row_ptr[k + 1]
+    }
+
+    /// Rebuilds the arrays row by row, `f` gets the row index and its sorted `(col, value)`
+    /// entries, which must still be sorted when it returns.
+    ///
+    /// Only the rows in `rows` (sorted) and the stored ones for which `stored` holds are visited,
+    /// the other stored rows are moved as they are. Rows left empty are dropped.
+    fn update_rows(
+        &mut self,
+        rows: &[IndexType],
+        stored: impl Fn(IndexType) -> bool,
+        mut f: impl FnMut(IndexType, &mut Vec<(IndexType, T)>),
+    ) {
+        let old_idx = core::mem::take(&mut self.row_idx);
+        let old_ptr = core::mem::take(&mut self.row_ptr);
+        let mut old = core::mem::take(&mut self.col_idx)
+            .into_iter()
+            .zip(core::mem::take(&mut self.values));
+
+        self.row_ptr.push(0);
+        let mut stored_rows = old_idx.iter().copied().enumerate().peekable();
+        let mut extra = rows.iter().copied().peekable();
+        let mut row = Vec::new();
+        loop {
+            let i = match (stored_rows.peek(), extra.peek()) {
+                (Some((_, i1)), Some(i2)) => *i1.min(i2),
+                (Some((_, i)), None) | (None, Some(i)) => *i,
+                (None, None) => break,
+            };
+            let listed = extra.next_if_eq(&i).is_some();
+            while extra.next_if_eq(&i).is_some() {}
+            if let Some((k, _)) = stored_rows.next_if(|val| val.1 == i) {
+                row.extend(old.by_ref().take(old_ptr[k + 1] - old_ptr[k]));
+            }
+
+            if listed || stored(i) {
+                f(i, &mut row);
+            }
+            if !row.is_empty() {
+                self.row_idx.push(i);
+                for (j, val) in row.drain(..) {
+                    self.col_idx.push(j);
+                    self.values.push(val);
+                }
+                self.row_ptr.push(self.col_idx.len());
+            }
+        }
+    }
+}
+
+impl<T> Assemble for Dcsr<T> {
+    type Update = (IndexType, IndexType, Option<T>);
+
+    fn assemble(&mut self, mut updates: Vec<Self::Update>) {
+        // stable, so updates of a row keep their order
+        updates.sort_by_key(|val| val.0);
+
+        let rows: Vec<IndexType> = updates.iter().map(|val| val.0).collect();
+        let mut it = updates.into_iter().peekable();
+        self.update_rows(
+            &rows,
+            |_| false,
+            |i, row| {
+                let mut row_updates = Vec::new();
+                while let Some((_, col, val)) = it.next_if(|next| next.0 == i) {
+                    row_updates.push((col, val));
+                }
+                if !row_updates.is_empty() {
+                    merge_updates(row, row_updates);
+                }
+            },
+        );
+    }
+}
+
+mod matrix_impl;
+mod ops_impl;
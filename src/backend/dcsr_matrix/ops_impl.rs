@@ -0,0 +1,262 @@
+use crate::{
+    backend::{
+        major_ops::MajorStorage,
+        pending::{Defer, Deferred, Pending},
+    },
+    context,
+    types::IndexType,
+};
+
+use super::{Dcsr, DcsrMatrix};
+
+impl<T> MajorStorage for DcsrMatrix<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    fn update_major(
+        &mut self,
+        majors: &[IndexType],
+        stored: impl Fn(IndexType) -> bool,
+        f: impl FnMut(IndexType, &mut Vec<(IndexType, T)>),
+    ) {
+        self.data.get_mut().update_rows(majors, stored, f);
+    }
+}
+
+/// Operations wait in the queue with the element updates in nonblocking mode.
+impl<T: 'static> Defer for DcsrMatrix<T> {
+    fn defers(&self) -> bool {
+        context::is_nonblocking()
+    }
+
+    fn defer(&mut self, op: Deferred<Self>) {
+        let (nrows, ncols) = (self.nrows, self.ncols);
+        self.data.push_op(Box::new(move |data| {
+            // the operation runs on a matrix around the assembled storage
+            let mut mat = Self {
+                data: Pending::new(core::mem::replace(data, Dcsr::new())),
+                nrows,
+                ncols,
+            };
+            op(&mut mat);
+            *data = mat.data.into_inner();
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algebra::{Addition, PlusTimesSemiring},
+        backend::{
+            test_utils::{check_matches_sparse, entries, init, matrix},
+            SimpleVec, SparseMatrix,
+        },
+        descriptor::Descriptor,
+        indices::Indices,
+        matrix::Matrix,
+        operations::{MatOps, VecOps},
+        vector::Vector,
+    };
+
+    use super::*;
+
+    const N: IndexType = 1 << 40;
+
+    #[test]
+    fn test_matches_sparse() {
+        init();
+        check_matches_sparse::<DcsrMatrix<i32>>();
+
+        // only the non-empty rows of the output are listed afterwards
+        let values = [(0, 0, 1), (0, 2, 2), (2, 1, 3), (3, 0, 4), (3, 3, 5)];
+        let a: DcsrMatrix<i32> = matrix(4, 4, &values);
+        let b: SparseMatrix<i32> = matrix(4, 4, &values);
+        let mut c: DcsrMatrix<i32> = matrix(4, 4, &[(1, 1, 1), (2, 2, 2)]);
+        let mut expected: SparseMatrix<i32> = matrix(4, 4, &[(1, 1, 1), (2, 2, 2)]);
+        c.assign(
+            Option::<DcsrMatrix<i32>>::None,
+            Some(Addition::new()),
+            &a,
+            Indices::All,
+            Indices::All,
+            Some(Descriptor::DESC_T0),
+        )
+        .unwrap();
+        expected
+            .assign(
+                Option::<SparseMatrix<i32>>::None,
+                Some(Addition::new()),
+                &b,
+                Indices::All,
+                Indices::All,
+                Some(Descriptor::DESC_T0),
+            )
+            .unwrap();
+        assert_eq!(entries(&c), entries(&expected));
+        assert_eq!(c.compressed().unwrap().majors, Some(&[0, 1, 2, 3][..]));
+    }
+
+    #[test]
+    fn test_hypersparse_all() {
+        init();
+        let values = [(0, N - 1, 2), (N - 1, 7, 3), (1 << 30, 0, 4)];
+        let a: DcsrMatrix<i32> = matrix(N, N, &values);
+
+        // `Indices::All` selects every index of a 2^40 dimension without listing them
+        let mut c: DcsrMatrix<i32> = DcsrMatrix::new(N, N).unwrap();
+        c.extract(
+            Option::<DcsrMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &a,
+            Indices::All,
+            Indices::All,
+            Some(Descriptor::DESC_T0),
+        )
+        .unwrap();
+        assert_eq!(
+            entries(&c),
+            vec![(0, 1 << 30, 4), (7, N - 1, 3), (N - 1, 0, 2)]
+        );
+
+        let mut c: DcsrMatrix<i32> = DcsrMatrix::new(N, 2).unwrap();
+        c.extract(
+            Option::<DcsrMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &a,
+            Indices::All,
+            Indices::List(vec![7, N - 1]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(entries(&c), vec![(0, 1, 2), (N - 1, 0, 3)]);
+
+        let mut c: DcsrMatrix<i32> = matrix(N, N, &[(0, N - 1, 1), (5, 5, 1)]);
+        c.assign(
+            Option::<DcsrMatrix<i32>>::None,
+            Some(Addition::new()),
+            &a,
+            Indices::All,
+            Indices::All,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            entries(&c),
+            vec![(0, N - 1, 3), (5, 5, 1), (1 << 30, 0, 4), (N - 1, 7, 3)]
+        );
+
+        let mut w = SimpleVec::new(N).unwrap();
+        w.extract_col(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &a,
+            Indices::All,
+            0,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w.extract_tuples().unwrap(), (vec![1 << 30], vec![4]));
+
+        // nor do ranges, their positions are mapped with arithmetic
+        let (rows, cols) = (
+            Indices::Range {
+                begin: 1,
+                end: N - 1,
+            },
+            Indices::Backwards {
+                begin: N - 1,
+                end: 0,
+                inc: 1,
+            },
+        );
+        let mut c: DcsrMatrix<i32> = DcsrMatrix::new(N - 1, N).unwrap();
+        c.extract(
+            Option::<DcsrMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &a,
+            rows.clone(),
+            cols.clone(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            entries(&c),
+            vec![((1 << 30) - 1, N - 1, 4), (N - 2, N - 8, 3)]
+        );
+
+        let mut d: DcsrMatrix<i32> = DcsrMatrix::new(N, N).unwrap();
+        d.assign(
+            Option::<DcsrMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &c,
+            rows,
+            cols,
+            None,
+        )
+        .unwrap();
+        assert_eq!(entries(&d), vec![(1 << 30, 0, 4), (N - 1, 7, 3)]);
+    }
+
+    #[test]
+    fn test_hypersparse() {
+        init();
+        let a: DcsrMatrix<i32> = matrix(N, N, &[(0, N - 1, 2), (N - 1, 7, 3), (1 << 30, 0, 4)]);
+
+        let mut c: DcsrMatrix<i32> = DcsrMatrix::new(N, N).unwrap();
+        c.mxm(
+            Option::<DcsrMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &a,
+            None,
+        )
+        .unwrap();
+        assert_eq!(entries(&c), vec![(0, 7, 6), (1 << 30, N - 1, 8)]);
+
+        c.transpose(
+            Option::<DcsrMatrix<i32>>::None,
+            Some(Addition::new()),
+            &a,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            entries(&c),
+            vec![
+                (0, 7, 6),
+                (0, 1 << 30, 4),
+                (7, N - 1, 3),
+                (1 << 30, N - 1, 8),
+                (N - 1, 0, 2)
+            ]
+        );
+
+        let mut u = SimpleVec::new(2).unwrap();
+        u.set_element(1, 9).unwrap();
+        c.assign_row(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &u,
+            N - 2,
+            Indices::List(vec![0, N - 1]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(c.extract_element(N - 2, N - 1).unwrap(), &9);
+        assert_eq!(c.nvals().unwrap(), 6);
+
+        let mut w = SimpleVec::new(3).unwrap();
+        w.extract_col(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &c,
+            Indices::List(vec![N - 2, 7, 0]),
+            N - 1,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w.extract_tuples().unwrap(), (vec![0, 1], vec![9, 3]));
+    }
+}
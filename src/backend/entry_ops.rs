@@ -0,0 +1,775 @@
+use crate::{
+    algebra::{
+        AssociativeCommutative, BinaryOperator, IndexUnaryOperator, Monoid, Semiring, UnaryOperator,
+    },
+    backend::{
+        pending::Defer,
+        utils::{
+            accum_mult_vec, accum_scalar, assign_vec, check_vals, check_vec_mask, dims, dot_vec,
+            ewise_and_vec, ewise_or_accum_vec, extract_vec, full_entries, is_by_col, iter_entries,
+            masked_write_vec, run_vec_op, scalar_value, to_entries, to_rows, Flipped, IndexMap,
+            InputEntries, Positions,
+        },
+    },
+    context,
+    descriptor::Descriptor,
+    indices::Indices,
+    mask::VecMask,
+    matrix::Matrix,
+    operations::VecOps,
+    scalar::Scalar,
+    types::{IndexType, NoValue},
+    vector::{Vector, VectorUtils},
+    ApiError, GblasResult,
+};
+
+/// Vector backends whose entries can be handed out as sorted `(index, value)` pairs.
+///
+/// The inputs of an operation are read through [`Vector`] and the result is merged into the
+/// output in one go, so implementing this trait is enough to get [`VecOps`]. Operations go
+/// through [`Defer`], so a backend queuing them only has to override it.
+pub(crate) trait EntryStorage: VectorUtils + Defer {
+    /// `f` gets the `(index, value)` entries sorted by index, which must still be sorted when it
+    /// returns.
+    fn update_entries(&mut self, f: impl FnOnce(&mut Vec<(IndexType, Self::Scalar)>));
+}
+
+/// _w<m, accum> = t_
+///
+/// Writes `t` into `w`, going through the mask predicate, the accumulator and replace.
+fn write_masked<T, V>(
+    w: &mut V,
+    mask: impl Fn(IndexType) -> bool,
+    accum: Option<&impl BinaryOperator<T, Output = T>>,
+    t: Vec<(IndexType, T)>,
+    replace: bool,
+) where
+    T: Clone + PartialEq,
+    V: EntryStorage<Scalar = T>,
+{
+    w.update_entries(|w_data| masked_write_vec(w_data, iter_entries(&t), mask, accum, replace));
+}
+
+/// _w<m>(I) = w(I) ⊙ u_, or _w(I)<m> = w(I) ⊙ u_ when `sub` is set.
+///
+/// `u` holds the entries of the _|I|_ input.
+#[allow(clippy::too_many_arguments)]
+fn assign_entries<T, V>(
+    w: &mut V,
+    mask: Option<&impl VecMask<V>>,
+    accum: Option<impl BinaryOperator<T, Output = T> + Send + 'static>,
+    u: InputEntries<'_, T>,
+    map: IndexMap,
+    desc: &Descriptor,
+    sub: bool,
+) -> GblasResult<NoValue>
+where
+    T: Clone + PartialEq + Send + 'static,
+    V: EntryStorage<Scalar = T>,
+{
+    let replace = desc.replace;
+    run_vec_op(w, mask, desc, [], [u], move |w, [], [u], mask| {
+        w.update_entries(|w_data| {
+            assign_vec(
+                w_data,
+                iter_entries(u),
+                &map,
+                mask,
+                accum.as_ref(),
+                replace,
+                sub,
+            )
+        });
+    })
+}
+
+/// _w<m, accum>(i) = f(u(i), i, 0)_, applied to the stored entries of _u_ only.
+///
+/// Entries mapped to `None` are dropped.
+fn apply_with<T, V>(
+    w: &mut V,
+    mask: Option<&impl VecMask<V>>,
+    accum: Option<impl BinaryOperator<T, Output = T> + Send + 'static>,
+    f: impl Fn(T, IndexType, IndexType) -> Option<T> + Send + 'static,
+    u: &impl Vector<Scalar = T>,
+    desc: Option<Descriptor>,
+) -> GblasResult<NoValue>
+where
+    T: Clone + PartialEq + Send + 'static,
+    V: EntryStorage<Scalar = T>,
+{
+    let desc = context::descriptor_or_default(desc);
+
+    check_vals(w.size(), u.size())?;
+    check_vec_mask(mask, w.size())?;
+
+    let u_data = to_entries(u)?;
+    let replace = desc.replace;
+    run_vec_op(
+        w,
+        mask,
+        &desc,
+        [],
+        [u_data],
+        move |w, [], [u_data], mask| {
+            let t = u_data
+                .iter()
+                .filter_map(|(i, val)| f(val.clone(), *i, 0).map(|val| (*i, val)))
+                .collect();
+            write_masked(w, mask, accum.as_ref(), t, replace);
+        },
+    )
+}
+
+/// _w<m, accum>(i) = ⊕ A(i, :)_ over the rows of _A_ (or _A'_).
+fn reduce_rows<T, V>(
+    w: &mut V,
+    mask: Option<&impl VecMask<V>>,
+    accum: Option<impl BinaryOperator<T, Output = T> + Send + 'static>,
+    op: impl BinaryOperator<T, Output = T> + Send + 'static,
+    a: &impl Matrix<Scalar = T>,
+    desc: Option<Descriptor>,
+) -> GblasResult<NoValue>
+where
+    T: Clone + PartialEq + Send + 'static,
+    V: EntryStorage<Scalar = T>,
+{
+    let desc = context::descriptor_or_default(desc);
+    let transpose_a = desc.transpose_inp0;
+
+    let (a_rows, _) = dims(a, transpose_a);
+    check_vals(w.size(), a_rows)?;
+    check_vec_mask(mask, w.size())?;
+
+    let a_data = to_rows(a, transpose_a)?;
+    let replace = desc.replace;
+    run_vec_op(
+        w,
+        mask,
+        &desc,
+        [a_data],
+        [],
+        move |w, [a_data], [], mask| {
+            let t = a_data
+                .iter()
+                .filter_map(|(i, a_row)| {
+                    a_row
+                        .iter()
+                        .map(|(_, val)| val.clone())
+                        .reduce(|acc, val| op.op(acc, val))
+                        .map(|val| (i, val))
+                })
+                .collect();
+            write_masked(w, mask, accum.as_ref(), t, replace);
+        },
+    )
+}
+
+/// _w<m, accum> = u ∪ v_ when `union` is set, _w<m, accum> = u ∩ v_ otherwise.
+#[allow(clippy::too_many_arguments)]
+fn e_wise<T, V>(
+    w: &mut V,
+    mask: Option<&impl VecMask<V>>,
+    accum: Option<impl BinaryOperator<T, Output = T> + Send + 'static>,
+    op: impl BinaryOperator<T, Output = T> + Send + 'static,
+    u: &impl Vector<Scalar = T>,
+    v: &impl Vector<Scalar = T>,
+    desc: Option<Descriptor>,
+    union: bool,
+) -> GblasResult<NoValue>
+where
+    T: Clone + PartialEq + Send + 'static,
+    V: EntryStorage<Scalar = T>,
+{
+    let desc = context::descriptor_or_default(desc);
+
+    check_vals(u.size(), v.size())?;
+    check_vals(w.size(), u.size())?;
+    check_vec_mask(mask, w.size())?;
+
+    let u_data = to_entries(u)?;
+    let v_data = to_entries(v)?;
+
+    let replace = desc.replace;
+    run_vec_op(
+        w,
+        mask,
+        &desc,
+        [],
+        [u_data, v_data],
+        move |w, [], [u_data, v_data], mask| {
+            let mut t = Vec::new();
+            if union {
+                ewise_or_accum_vec(&mut t, iter_entries(u_data), iter_entries(v_data), &op);
+            } else {
+                ewise_and_vec(&mut t, iter_entries(u_data), iter_entries(v_data), &op);
+            }
+            write_masked(w, mask, accum.as_ref(), t, replace);
+        },
+    )
+}
+
+impl<T, V> VecOps for V
+where
+    T: Clone + PartialEq + Send + 'static,
+    V: EntryStorage<Scalar = T>,
+{
+    fn vxm(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Semiring<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let desc = context::descriptor_or_default(desc);
+        let transpose_a = desc.transpose_inp1;
+
+        let (a_rows, a_cols) = dims(a, transpose_a);
+        check_vals(u.size(), a_rows)?;
+        check_vals(self.size(), a_cols)?;
+        check_vec_mask(mask.as_ref(), self.size())?;
+
+        let u_data = to_entries(u)?;
+        // dot products with the columns of op(A) when they are stored
+        let by_col = is_by_col(a, transpose_a);
+        let a_data = to_rows(a, transpose_a != by_col)?;
+
+        let replace = desc.replace;
+        run_vec_op(
+            self,
+            mask.as_ref(),
+            &desc,
+            [a_data],
+            [u_data],
+            move |w, [a_data], [u_data], mask| {
+                let t = if by_col {
+                    a_data
+                        .iter()
+                        .filter_map(|(j, a_col)| {
+                            dot_vec(iter_entries(u_data), a_col.iter(), &op).map(|val| (j, val))
+                        })
+                        .collect()
+                } else {
+                    let mut t = Vec::new();
+                    for (k, u_k) in u_data.iter() {
+                        accum_mult_vec(&mut t, a_data.get(*k).iter(), u_k, &op);
+                    }
+                    t
+                };
+                write_masked(w, mask, accum.as_ref(), t, replace);
+            },
+        )
+    }
+
+    fn mxv(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Semiring<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let desc = context::descriptor_or_default(desc);
+        let transpose_a = desc.transpose_inp0;
+
+        let (a_rows, a_cols) = dims(a, transpose_a);
+        check_vals(u.size(), a_cols)?;
+        check_vals(self.size(), a_rows)?;
+        check_vec_mask(mask.as_ref(), self.size())?;
+
+        let u_data = to_entries(u)?;
+        // sum of the columns of op(A) scaled by u when they are stored
+        let by_col = is_by_col(a, transpose_a);
+        let a_data = to_rows(a, transpose_a != by_col)?;
+
+        let replace = desc.replace;
+        run_vec_op(
+            self,
+            mask.as_ref(),
+            &desc,
+            [a_data],
+            [u_data],
+            move |w, [a_data], [u_data], mask| {
+                let t = if by_col {
+                    let mut t = Vec::new();
+                    for (k, u_k) in u_data.iter() {
+                        accum_mult_vec(&mut t, a_data.get(*k).iter(), u_k, &Flipped(&op));
+                    }
+                    t
+                } else {
+                    a_data
+                        .iter()
+                        .filter_map(|(i, a_row)| {
+                            dot_vec(a_row.iter(), iter_entries(u_data), &op).map(|val| (i, val))
+                        })
+                        .collect()
+                };
+                write_masked(w, mask, accum.as_ref(), t, replace);
+            },
+        )
+    }
+
+    fn e_wise_mult_binary_op(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        v: &impl Vector<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        e_wise(self, mask.as_ref(), accum, op, u, v, desc, false)
+    }
+
+    fn e_wise_mult_monoid(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Monoid<Self::Scalar> + Send + 'static,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        v: &impl Vector<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        e_wise(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, y| op.operate(x, y),
+            u,
+            v,
+            desc,
+            false,
+        )
+    }
+
+    fn e_wise_mult_semiring(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Semiring<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        v: &impl Vector<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        e_wise(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, y| op.mult(x, y),
+            u,
+            v,
+            desc,
+            false,
+        )
+    }
+
+    fn e_wise_add_binary_op(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        v: &impl Vector<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        e_wise(self, mask.as_ref(), accum, op, u, v, desc, true)
+    }
+
+    fn e_wise_add_monoid(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Monoid<Self::Scalar> + Send + 'static,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        v: &impl Vector<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        e_wise(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, y| op.operate(x, y),
+            u,
+            v,
+            desc,
+            true,
+        )
+    }
+
+    fn e_wise_add_semiring(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Semiring<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        v: &impl Vector<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        e_wise(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, y| op.add(x, y),
+            u,
+            v,
+            desc,
+            true,
+        )
+    }
+
+    fn extract(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        indices: Indices,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let desc = context::descriptor_or_default(desc);
+
+        check_vals(self.size(), indices.len(u.size())?)?;
+        check_vec_mask(mask.as_ref(), self.size())?;
+        let positions = Positions::new(&indices, u.size())?;
+
+        let u_data = to_entries(u)?;
+        let replace = desc.replace;
+        run_vec_op(
+            self,
+            mask.as_ref(),
+            &desc,
+            [],
+            [u_data],
+            move |w, [], [u_data], mask| {
+                let t = extract_vec(iter_entries(u_data), &positions);
+                write_masked(w, mask, accum.as_ref(), t, replace);
+            },
+        )
+    }
+
+    fn extract_col(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        row_indices: Indices,
+        col_index: IndexType,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let desc = context::descriptor_or_default(desc);
+        let transpose_a = desc.transpose_inp0;
+
+        let (a_rows, a_cols) = dims(a, transpose_a);
+        if col_index >= a_cols {
+            return Err(ApiError::InvalidIndex.into());
+        }
+        check_vals(self.size(), row_indices.len(a_rows)?)?;
+        check_vec_mask(mask.as_ref(), self.size())?;
+        let positions = Positions::new(&row_indices, a_rows)?;
+
+        let col = if is_by_col(a, transpose_a) {
+            // stored columns of op(A) are read directly
+            a.extract_major(col_index)?
+        } else {
+            // the column is gathered from the sorted rows, without transposing the whole of op(A)
+            let a_data = to_rows(a, transpose_a)?;
+            a_data
+                .view()
+                .iter()
+                .filter_map(|(i, row)| row.find(col_index).map(|val| (i, val.clone())))
+                .collect()
+        };
+        let replace = desc.replace;
+        run_vec_op(
+            self,
+            mask.as_ref(),
+            &desc,
+            [],
+            [InputEntries::Owned(col)],
+            move |w, [], [col], mask| {
+                let t = extract_vec(iter_entries(col), &positions);
+                write_masked(w, mask, accum.as_ref(), t, replace);
+            },
+        )
+    }
+
+    fn assign(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        indices: Indices,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let desc = context::descriptor_or_default(desc);
+
+        let map = IndexMap::new(&indices, self.size())?;
+        check_vals(map.len(), u.size())?;
+        check_vec_mask(mask.as_ref(), self.size())?;
+
+        let u_data = to_entries(u)?;
+        assign_entries(self, mask.as_ref(), accum, u_data, map, &desc, false)
+    }
+
+    fn assign_value(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        value: impl Into<Scalar<Self::Scalar>>,
+        indices: Indices,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let desc = context::descriptor_or_default(desc);
+
+        let map = IndexMap::new(&indices, self.size())?;
+        check_vec_mask(mask.as_ref(), self.size())?;
+
+        let u_data = InputEntries::Owned(full_entries(map.len(), value.into()));
+        assign_entries(self, mask.as_ref(), accum, u_data, map, &desc, false)
+    }
+
+    fn subassign(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        indices: Indices,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let desc = context::descriptor_or_default(desc);
+
+        let map = IndexMap::new(&indices, self.size())?;
+        check_vals(map.len(), u.size())?;
+        check_vec_mask(mask.as_ref(), map.len())?;
+
+        let u_data = to_entries(u)?;
+        assign_entries(self, mask.as_ref(), accum, u_data, map, &desc, true)
+    }
+
+    fn subassign_value(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        value: impl Into<Scalar<Self::Scalar>>,
+        indices: Indices,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let desc = context::descriptor_or_default(desc);
+
+        let map = IndexMap::new(&indices, self.size())?;
+        check_vec_mask(mask.as_ref(), map.len())?;
+
+        let u_data = InputEntries::Owned(full_entries(map.len(), value.into()));
+        assign_entries(self, mask.as_ref(), accum, u_data, map, &desc, true)
+    }
+
+    fn apply(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl UnaryOperator<Self::Scalar> + Send + 'static,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        apply_with(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, _, _| Some(op.op(x)),
+            u,
+            desc,
+        )
+    }
+
+    fn apply_1st(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        value: impl Into<Scalar<Self::Scalar>>,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let value = scalar_value(value.into())?;
+
+        apply_with(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, _, _| Some(op.op(value.clone(), x)),
+            u,
+            desc,
+        )
+    }
+
+    fn apply_2nd(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        value: impl Into<Scalar<Self::Scalar>>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let value = scalar_value(value.into())?;
+
+        apply_with(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, _, _| Some(op.op(x, value.clone())),
+            u,
+            desc,
+        )
+    }
+
+    fn apply_index_op<S: Clone + Send + 'static>(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl IndexUnaryOperator<Self::Scalar, S, Output = Self::Scalar> + Send + 'static,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        thunk: impl Into<Scalar<S>>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let thunk = scalar_value(thunk.into())?;
+
+        apply_with(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, i, j| Some(op.op(x, i, j, thunk.clone())),
+            u,
+            desc,
+        )
+    }
+
+    fn select<S: Clone + Send + 'static>(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl IndexUnaryOperator<Self::Scalar, S, Output = bool> + Send + 'static,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        thunk: impl Into<Scalar<S>>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let thunk = scalar_value(thunk.into())?;
+
+        apply_with(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, i, j| op.op(x.clone(), i, j, thunk.clone()).then_some(x),
+            u,
+            desc,
+        )
+    }
+
+    fn reduce(
+        &self,
+        val: &mut Self::Scalar,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
+        op: impl Monoid<Self::Scalar>,
+        _desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let res = self
+            .iter()
+            .fold(op.identity(), |acc, (_, val)| op.operate(acc, val.clone()));
+        *val = match accum {
+            Some(accum) => accum.op(val.clone(), res),
+            None => res,
+        };
+
+        Ok(())
+    }
+
+    fn reduce_scalar(
+        &self,
+        s: &mut Scalar<Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
+        op: impl Monoid<Self::Scalar>,
+        _desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let t = self
+            .iter()
+            .map(|(_, val)| val.clone())
+            .reduce(|acc, val| op.operate(acc, val));
+        accum_scalar(s, t, accum.as_ref());
+
+        Ok(())
+    }
+
+    fn reduce_scalar_binary_op(
+        &self,
+        s: &mut Scalar<Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + AssociativeCommutative,
+        _desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let t = self
+            .iter()
+            .map(|(_, val)| val.clone())
+            .reduce(|acc, val| op.op(acc, val));
+        accum_scalar(s, t, accum.as_ref());
+
+        Ok(())
+    }
+
+    fn reduce_binary_op(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>
+            + AssociativeCommutative
+            + Send
+            + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        reduce_rows(self, mask.as_ref(), accum, op, a, desc)
+    }
+
+    fn reduce_monoid(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Monoid<Self::Scalar> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        reduce_rows(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, y| op.operate(x, y),
+            a,
+            desc,
+        )
+    }
+}
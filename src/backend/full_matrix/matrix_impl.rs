@@ -0,0 +1,284 @@
+use std::ops::Index;
+
+use crate::{
+    algebra::BinaryOperator,
+    complement_mask::MatrixComplementMask,
+    context,
+    mask::MatMask,
+    matrix::{Matrix, MatrixExtra},
+    structure_mask::MatrixStructureMask,
+    types::{IndexType, NoValue},
+    ApiError, ExecutionError, GblasResult,
+};
+
+use super::FullMatrix;
+
+impl<T> Matrix for FullMatrix<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    type Scalar = T;
+
+    fn new(rows: IndexType, cols: IndexType) -> GblasResult<Self> {
+        context::check_init()?;
+        if rows == 0 || cols == 0 {
+            return Err(ApiError::InvalidValue.into());
+        }
+        let len = rows.checked_mul(cols).ok_or(ApiError::InvalidValue)?;
+
+        Ok(Self {
+            values: vec![T::default(); len],
+            present: vec![false; len],
+            nrows: rows,
+            ncols: cols,
+            nvals: 0,
+        })
+    }
+
+    /// Growing leaves the new entries missing.
+    fn resize(&mut self, rows: IndexType, cols: IndexType) -> GblasResult<NoValue> {
+        context::check_init()?;
+        if rows == 0 || cols == 0 {
+            return Err(ApiError::InvalidValue.into());
+        }
+        let len = rows.checked_mul(cols).ok_or(ApiError::InvalidValue)?;
+
+        let mut values = vec![T::default(); len];
+        let mut present = vec![false; len];
+        let old_rows = self
+            .values
+            .chunks_mut(self.ncols)
+            .zip(self.present.chunks(self.ncols));
+        for (i, (row, flags)) in old_rows.take(rows).enumerate() {
+            for (j, (val, &flag)) in row.iter_mut().zip(flags).take(cols).enumerate() {
+                values[i * cols + j] = core::mem::take(val);
+                present[i * cols + j] = flag;
+            }
+        }
+        self.nvals = present.iter().filter(|&&p| p).count();
+        self.values = values;
+        self.present = present;
+        self.nrows = rows;
+        self.ncols = cols;
+
+        Ok(())
+    }
+
+    fn clear(&mut self) -> GblasResult<NoValue> {
+        context::check_init()?;
+        self.values.fill(T::default());
+        self.present.fill(false);
+        self.nvals = 0;
+        Ok(())
+    }
+
+    fn nrows(&self) -> IndexType {
+        self.nrows
+    }
+
+    fn ncols(&self) -> IndexType {
+        self.ncols
+    }
+
+    fn nvals(&self) -> GblasResult<IndexType> {
+        context::check_init()?;
+        Ok(self.nvals)
+    }
+
+    fn build(
+        self,
+        rows: impl Iterator<Item = IndexType>,
+        cols: impl Iterator<Item = IndexType>,
+        values: impl Iterator<Item = Self::Scalar>,
+        _: IndexType,
+        dup: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
+    ) -> GblasResult<Self> {
+        context::check_init()?;
+        if self.nvals()? > 0 {
+            return Err(ApiError::OutputNotEmpty.into());
+        }
+
+        let mut s = self;
+        for ((row, col), val) in rows.zip(cols).zip(values) {
+            let idx = s.slot(row, col).ok_or(ExecutionError::IndexOutOfBounds)?;
+            if s.present[idx] {
+                let prev = core::mem::take(&mut s.values[idx]);
+                s.values[idx] = dup.op(prev, val);
+            } else {
+                s.values[idx] = val;
+                s.present[idx] = true;
+                s.nvals += 1;
+            }
+        }
+
+        Ok(s)
+    }
+
+    fn set_element(
+        &mut self,
+        row: IndexType,
+        col: IndexType,
+        value: Self::Scalar,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let idx = self.slot(row, col).ok_or(ApiError::InvalidIndex)?;
+        self.values[idx] = value;
+        if !core::mem::replace(&mut self.present[idx], true) {
+            self.nvals += 1;
+        }
+        Ok(())
+    }
+
+    /// Clears the flag of the entry and puts the zero back in its slot.
+    fn remove_element(&mut self, row: IndexType, col: IndexType) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let idx = self.slot(row, col).ok_or(ApiError::InvalidIndex)?;
+        if core::mem::replace(&mut self.present[idx], false) {
+            self.values[idx] = T::default();
+            self.nvals -= 1;
+        }
+        Ok(())
+    }
+
+    fn extract_element(&self, row: IndexType, col: IndexType) -> GblasResult<&Self::Scalar> {
+        context::check_init()?;
+        let idx = self.slot(row, col).ok_or(ApiError::InvalidIndex)?;
+        if !self.present[idx] {
+            return Err(ApiError::NoValue.into());
+        }
+        Ok(&self.values[idx])
+    }
+
+    fn extract_tuples(self) -> GblasResult<(Vec<IndexType>, Vec<IndexType>, Vec<Self::Scalar>)> {
+        context::check_init()?;
+        let mut rows = Vec::with_capacity(self.nvals);
+        let mut cols = Vec::with_capacity(self.nvals);
+        let mut vals = Vec::with_capacity(self.nvals);
+        let entries = self.values.into_iter().zip(self.present).enumerate();
+        for (idx, (val, present)) in entries {
+            if present {
+                rows.push(idx / self.ncols);
+                cols.push(idx % self.ncols);
+                vals.push(val);
+            }
+        }
+
+        Ok((rows, cols, vals))
+    }
+
+    fn extract_major(&self, k: IndexType) -> GblasResult<Vec<(IndexType, Self::Scalar)>> {
+        context::check_init()?;
+        if k >= self.nrows {
+            return Err(ApiError::InvalidIndex.into());
+        }
+        let range = k * self.ncols..(k + 1) * self.ncols;
+        Ok(self.values[range.clone()]
+            .iter()
+            .zip(&self.present[range])
+            .enumerate()
+            .filter(|(_, (_, &present))| present)
+            .map(|(j, (val, _))| (j, val.clone()))
+            .collect())
+    }
+}
+
+impl<T> MatrixExtra for FullMatrix<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    fn iter(&self) -> impl Iterator<Item = (IndexType, IndexType, &Self::Scalar)> {
+        self.values
+            .iter()
+            .zip(&self.present)
+            .enumerate()
+            .filter(|(_, (_, &present))| present)
+            .map(|(idx, (val, _))| (idx / self.ncols, idx % self.ncols, val))
+    }
+}
+
+/// Value mask: an entry counts only if its value is not the zero (default) of `T`.
+impl<T> Index<(IndexType, IndexType)> for FullMatrix<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    type Output = bool;
+
+    fn index(&self, index: (IndexType, IndexType)) -> &Self::Output {
+        match self.slot(index.0, index.1) {
+            Some(idx) if self.present[idx] && self.values[idx] != T::default() => &true,
+            _ => &false,
+        }
+    }
+}
+
+impl<T> MatMask<Self> for FullMatrix<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    fn complement(&self) -> MatrixComplementMask<'_, Self> {
+        MatrixComplementMask::new(self)
+    }
+
+    fn structure(&self) -> MatrixStructureMask<'_, Self> {
+        MatrixStructureMask::new(self)
+    }
+
+    fn shape(&self) -> (IndexType, IndexType) {
+        (self.nrows(), self.ncols())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algebra::Addition, backend::test_utils::init};
+
+    #[test]
+    fn test_matrix_ops() {
+        init();
+        let mut mat = FullMatrix::<i32>::new(2, 3).unwrap();
+        assert_eq!(mat.nvals().unwrap(), 0);
+        assert!(mat.extract_element(1, 2).is_err());
+        for (idx, v) in [0, 5, 1, 2, 0, 3].into_iter().enumerate() {
+            mat.set_element(idx / 3, idx % 3, v).unwrap();
+        }
+        assert_eq!(mat.nvals().unwrap(), 6);
+        assert!(mat.set_element(2, 0, 1).is_err());
+        assert!(mat.extract_element(0, 3).is_err());
+        assert_eq!(mat.extract_major(1).unwrap(), vec![(0, 2), (1, 0), (2, 3)]);
+
+        assert!(mat[(0, 1)]);
+        assert!(!mat[(0, 0)]);
+        assert!(mat.structure()[(0, 0)]);
+        assert!(mat.complement()[(1, 1)]);
+
+        // removing an entry clears its flag only, growing leaves entries missing
+        mat.remove_element(0, 1).unwrap();
+        assert_eq!(mat.present, vec![true, false, true, true, true, true]);
+        assert_eq!(mat.nvals().unwrap(), 5);
+        assert!(!mat.structure()[(0, 1)]);
+        assert_eq!(mat.extract_major(0).unwrap(), vec![(0, 0), (2, 1)]);
+        mat.resize(3, 2).unwrap();
+        mat.set_element(2, 1, 4).unwrap();
+        assert_eq!(mat.nvals().unwrap(), 4);
+        let entries: Vec<_> = mat.iter().map(|(i, j, v)| (i, j, *v)).collect();
+        assert_eq!(entries, vec![(0, 0, 0), (1, 0, 2), (1, 1, 0), (2, 1, 4)]);
+        mat.clear().unwrap();
+        assert_eq!(mat.nvals().unwrap(), 0);
+
+        let mat = FullMatrix::<i32>::new(2, 2)
+            .unwrap()
+            .build(
+                [1, 0, 1].into_iter(),
+                [1, 1, 1].into_iter(),
+                [1, 2, 3].into_iter(),
+                3,
+                Addition::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            mat.extract_tuples().unwrap(),
+            (vec![0, 1], vec![1, 1], vec![2, 4])
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use crate::types::IndexType;
+
+/// Full matrix: the values are stored row by row in a plain array, next to a presence flag per
+/// position.
+///
+/// A missing entry, as in a new matrix or one left by `remove_element` or an operation that
+/// doesn't produce every entry, only has its flag cleared: its slot holds the zero (default) of
+/// `T` and is never read, so no value is ever made up. Element access is O(1), operations update
+/// the array in place and element updates are applied right away, even in nonblocking mode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FullMatrix<T> {
+    values: Vec<T>,
+    present: Vec<bool>,
+    nrows: IndexType,
+    ncols: IndexType,
+    nvals: IndexType,
+}
+
+impl<T> FullMatrix<T> {
+    /// Position of `(row, col)` in `values`, `None` when out of bounds.
+    fn slot(&self, row: IndexType, col: IndexType) -> Option<usize> {
+        (row < self.nrows && col < self.ncols).then(|| row * self.ncols + col)
+    }
+}
+
+mod matrix_impl;
+mod ops_impl;
@@ -0,0 +1,125 @@
+use crate::{
+    backend::{major_ops::MajorStorage, pending::Defer},
+    types::IndexType,
+};
+
+use super::FullMatrix;
+
+impl<T> MajorStorage for FullMatrix<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    /// Rows are updated in place, the entries left missing by `f` have their flag cleared. Rows
+    /// not in `majors` are only visited when `stored` holds for them.
+    fn update_major(
+        &mut self,
+        majors: &[IndexType],
+        stored: impl Fn(IndexType) -> bool,
+        mut f: impl FnMut(IndexType, &mut Vec<(IndexType, T)>),
+    ) {
+        let mut majors = majors.iter().copied().peekable();
+        let mut row = Vec::new();
+        let rows = self
+            .values
+            .chunks_mut(self.ncols)
+            .zip(self.present.chunks_mut(self.ncols));
+        for (i, (slots, flags)) in rows.enumerate() {
+            let listed = majors.next_if_eq(&i).is_some();
+            while majors.next_if_eq(&i).is_some() {}
+            if !listed && !stored(i) {
+                continue;
+            }
+            row.extend(
+                slots
+                    .iter_mut()
+                    .zip(flags.iter_mut())
+                    .enumerate()
+                    .filter(|(_, (_, flag))| **flag)
+                    .map(|(j, (val, flag))| {
+                        *flag = false;
+                        (j, core::mem::take(val))
+                    }),
+            );
+            self.nvals -= row.len();
+            f(i, &mut row);
+            self.nvals += row.len();
+            for (j, val) in row.drain(..) {
+                slots[j] = val;
+                flags[j] = true;
+            }
+        }
+    }
+}
+
+/// Operations run right away, like the element updates.
+impl<T> Defer for FullMatrix<T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algebra::{Addition, PlusTimesSemiring},
+        backend::{
+            test_utils::{check_matches_sparse, entries, init, matrix},
+            BitmapMatrix, SparseMatrix,
+        },
+        descriptor::Descriptor,
+        indices::Indices,
+        matrix::Matrix,
+        operations::MatOps,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_matches_sparse() {
+        init();
+        check_matches_sparse::<FullMatrix<i32>>();
+    }
+
+    #[test]
+    fn test_no_made_up_values() {
+        init();
+        let a: SparseMatrix<i32> = matrix(2, 2, &[(0, 1, 2), (1, 1, 3)]);
+        let b: FullMatrix<i32> = matrix(2, 2, &[(0, 0, 1), (0, 1, 1), (1, 0, 4), (1, 1, 5)]);
+        assert_eq!(b.nvals().unwrap(), 4);
+
+        // row 0 of A' is empty, so row 0 of the product is left missing
+        let mut c: FullMatrix<i32> = b.clone();
+        c.mxm(
+            Option::<FullMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &b,
+            Some(Descriptor::DESC_T0),
+        )
+        .unwrap();
+        assert_eq!(entries(&c), vec![(1, 0, 14), (1, 1, 17)]);
+        assert_eq!(c.nvals().unwrap(), 2);
+        assert_eq!(c.present, vec![false, false, true, true]);
+        assert!(c.extract_element(0, 1).is_err());
+
+        // only the assigned row gets its flags back
+        c.assign_value(
+            Option::<FullMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            0,
+            Indices::List(vec![0]),
+            Indices::All,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            entries(&c),
+            vec![(0, 0, 0), (0, 1, 0), (1, 0, 14), (1, 1, 17)]
+        );
+        assert_eq!(c.nvals().unwrap(), 4);
+
+        let mut bitmap: BitmapMatrix<i32> = c.clone().convert().unwrap();
+        assert_eq!(bitmap.nvals().unwrap(), 4);
+        bitmap.remove_element(0, 0).unwrap();
+        let f: FullMatrix<i32> = bitmap.convert().unwrap();
+        assert_eq!(f.nvals().unwrap(), 3);
+        assert!(f.extract_element(0, 0).is_err());
+    }
+}
@@ -0,0 +1,17 @@
+use crate::types::IndexType;
+
+/// Full vector: the values are stored in a plain array, next to a presence flag per index.
+///
+/// A missing entry, as in a new vector or one left by `remove_element` or an operation that
+/// doesn't produce every entry, only has its flag cleared: its slot holds the zero (default) of
+/// `T` and is never read, so no value is ever made up. Element access is O(1), operations update
+/// the array in place and element updates are applied right away, even in nonblocking mode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FullVec<T> {
+    values: Vec<T>,
+    present: Vec<bool>,
+    nvals: IndexType,
+}
+
+mod ops_impl;
+mod vector_impl;
@@ -0,0 +1,99 @@
+use crate::{
+    backend::{entry_ops::EntryStorage, pending::Defer},
+    types::IndexType,
+};
+
+use super::FullVec;
+
+impl<T> EntryStorage for FullVec<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    /// The array is updated in place, the entries left missing by `f` have their flag cleared.
+    fn update_entries(&mut self, f: impl FnOnce(&mut Vec<(IndexType, T)>)) {
+        let mut entries: Vec<(IndexType, T)> = self
+            .values
+            .iter_mut()
+            .zip(self.present.iter_mut())
+            .enumerate()
+            .filter(|(_, (_, flag))| **flag)
+            .map(|(i, (val, flag))| {
+                *flag = false;
+                (i, core::mem::take(val))
+            })
+            .collect();
+        f(&mut entries);
+
+        self.nvals = entries.len();
+        for (i, val) in entries {
+            self.values[i] = val;
+            self.present[i] = true;
+        }
+    }
+}
+
+/// Operations run right away, like the element updates.
+impl<T> Defer for FullVec<T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algebra::{Addition, MinPlusSemiring},
+        backend::{
+            test_utils::{check_matches_simple, init, matrix, vec_entries, vector},
+            SimpleVec, SparseMatrix,
+        },
+        indices::Indices,
+        operations::VecOps,
+        vector::Vector,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_matches_simple() {
+        init();
+        check_matches_simple::<FullVec<i32>>();
+    }
+
+    #[test]
+    fn test_no_made_up_values() {
+        init();
+        let a: SparseMatrix<i32> = matrix(3, 3, &[(0, 1, 3), (2, 1, 4)]);
+        let u: FullVec<i32> = vector(3, &[(0, 2), (1, 7), (2, 9)]);
+        assert_eq!(u.nvals().unwrap(), 3);
+
+        // w(0) and w(2) have no entry in the product, so they are left missing
+        let mut w: FullVec<i32> = vector(3, &[(0, 1), (1, 1), (2, 1)]);
+        w.vxm(
+            Option::<FullVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            MinPlusSemiring::new(),
+            &u,
+            &a,
+            None,
+        )
+        .unwrap();
+        assert_eq!(vec_entries(&w), vec![(1, 5)]);
+        assert_eq!(w.nvals().unwrap(), 1);
+        assert_eq!(w.present, vec![false, true, false]);
+        assert!(w.extract_element(0).is_err());
+
+        // the accumulator only sees the entry left in w
+        w.assign_value(
+            Option::<FullVec<i32>>::None,
+            Some(Addition::new()),
+            1,
+            Indices::All,
+            None,
+        )
+        .unwrap();
+        assert_eq!(vec_entries(&w), vec![(0, 1), (1, 6), (2, 1)]);
+        assert_eq!(w.nvals().unwrap(), 3);
+
+        let s: SimpleVec<i32> = w.clone().convert().unwrap();
+        assert_eq!(s.nvals().unwrap(), 3);
+        let f: FullVec<i32> = s.convert().unwrap();
+        assert_eq!(f, w);
+    }
+}
@@ -0,0 +1,246 @@
+use std::ops::Index;
+
+use crate::{
+    algebra::BinaryOperator,
+    complement_mask::VectorComplementMask,
+    context,
+    mask::VecMask,
+    structure_mask::VectorStructureMask,
+    types::{IndexType, NoValue},
+    vector::{Vector, VectorUtils},
+    ApiError, ExecutionError, GblasResult,
+};
+
+use super::FullVec;
+
+impl<T> Vector for FullVec<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    type Scalar = T;
+
+    fn new(size: IndexType) -> GblasResult<Self> {
+        context::check_init()?;
+        if size == 0 {
+            return Err(ApiError::InvalidValue.into());
+        }
+        Ok(Self {
+            values: vec![T::default(); size],
+            present: vec![false; size],
+            nvals: 0,
+        })
+    }
+
+    /// Growing leaves the new entries missing.
+    fn resize(&mut self, size: IndexType) -> GblasResult<NoValue> {
+        context::check_init()?;
+        if size == 0 {
+            return Err(ApiError::InvalidValue.into());
+        }
+
+        if size < self.size() {
+            self.nvals -= self.present[size..].iter().filter(|&&p| p).count();
+        }
+        self.values.resize(size, T::default());
+        self.present.resize(size, false);
+
+        Ok(())
+    }
+
+    fn clear(&mut self) -> GblasResult<NoValue> {
+        context::check_init()?;
+        self.values.fill(T::default());
+        self.present.fill(false);
+        self.nvals = 0;
+        Ok(())
+    }
+
+    fn size(&self) -> IndexType {
+        self.values.len()
+    }
+
+    fn nvals(&self) -> GblasResult<IndexType> {
+        context::check_init()?;
+        Ok(self.nvals)
+    }
+
+    fn build(
+        self,
+        indices: impl Iterator<Item = IndexType>,
+        values: impl Iterator<Item = Self::Scalar>,
+        _: IndexType,
+        dup: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
+    ) -> GblasResult<Self> {
+        context::check_init()?;
+        if self.nvals()? > 0 {
+            return Err(ApiError::OutputNotEmpty.into());
+        }
+
+        let mut s = self;
+        for (idx, val) in indices.zip(values) {
+            if idx >= s.size() {
+                return Err(ExecutionError::IndexOutOfBounds.into());
+            }
+            if s.present[idx] {
+                let prev = core::mem::take(&mut s.values[idx]);
+                s.values[idx] = dup.op(prev, val);
+            } else {
+                s.values[idx] = val;
+                s.present[idx] = true;
+                s.nvals += 1;
+            }
+        }
+
+        Ok(s)
+    }
+
+    fn set_element(&mut self, index: IndexType, val: Self::Scalar) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let slot = self.values.get_mut(index).ok_or(ApiError::InvalidIndex)?;
+        *slot = val;
+        if !core::mem::replace(&mut self.present[index], true) {
+            self.nvals += 1;
+        }
+        Ok(())
+    }
+
+    /// Clears the flag of the entry and puts the zero back in its slot.
+    fn remove_element(&mut self, index: IndexType) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let flag = self.present.get_mut(index).ok_or(ApiError::InvalidIndex)?;
+        if core::mem::replace(flag, false) {
+            self.values[index] = T::default();
+            self.nvals -= 1;
+        }
+        Ok(())
+    }
+
+    fn extract_element(&self, index: IndexType) -> GblasResult<&Self::Scalar> {
+        context::check_init()?;
+        match self.present.get(index) {
+            Some(true) => Ok(&self.values[index]),
+            Some(false) => Err(ApiError::NoValue.into()),
+            None => Err(ApiError::InvalidIndex.into()),
+        }
+    }
+
+    fn extract_tuples(self) -> GblasResult<(Vec<IndexType>, Vec<Self::Scalar>)> {
+        context::check_init()?;
+        let mut indices = Vec::with_capacity(self.nvals);
+        let mut vals = Vec::with_capacity(self.nvals);
+        let entries = self.values.into_iter().zip(self.present).enumerate();
+        for (idx, (val, present)) in entries {
+            if present {
+                indices.push(idx);
+                vals.push(val);
+            }
+        }
+
+        Ok((indices, vals))
+    }
+}
+
+impl<T> VectorUtils for FullVec<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    fn iter(&self) -> impl Iterator<Item = (IndexType, &Self::Scalar)> {
+        self.values
+            .iter()
+            .zip(&self.present)
+            .enumerate()
+            .filter(|(_, (_, &present))| present)
+            .map(|(idx, (val, _))| (idx, val))
+    }
+}
+
+/// Value mask: an entry counts only if its value is not the zero (default) of `T`.
+impl<T> Index<IndexType> for FullVec<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    type Output = bool;
+
+    fn index(&self, index: IndexType) -> &Self::Output {
+        match self.present.get(index) {
+            Some(true) if self.values[index] != T::default() => &true,
+            _ => &false,
+        }
+    }
+}
+
+impl<T> VecMask<Self> for FullVec<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    fn complement(&self) -> VectorComplementMask<'_, Self> {
+        VectorComplementMask::new(self)
+    }
+
+    fn structure(&self) -> VectorStructureMask<'_, Self> {
+        VectorStructureMask::new(self)
+    }
+
+    fn shape(&self) -> IndexType {
+        self.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algebra::Addition, backend::test_utils::init};
+
+    #[test]
+    fn test_vector_ops() {
+        init();
+        let mut vec = FullVec::<i32>::new(4).unwrap();
+        assert_eq!(vec.nvals().unwrap(), 0);
+        assert!(vec.extract_element(2).is_err());
+        for (i, v) in [(0, 0), (1, 5), (2, 3), (3, 2)] {
+            vec.set_element(i, v).unwrap();
+        }
+        assert_eq!(vec.nvals().unwrap(), 4);
+        assert_eq!(vec.extract_element(0).unwrap(), &0);
+        assert!(vec.set_element(4, 1).is_err());
+        assert!(vec.extract_element(4).is_err());
+
+        assert!(vec[1]);
+        assert!(!vec[0]);
+        assert!(vec.structure()[0]);
+        assert!(!vec.complement()[3]);
+
+        // removing an entry clears its flag only, growing leaves entries missing
+        vec.remove_element(3).unwrap();
+        assert_eq!(vec.present, vec![true, true, true, false]);
+        assert_eq!(vec.nvals().unwrap(), 3);
+        assert!(vec.extract_element(3).is_err());
+        assert!(!vec.structure()[3]);
+        vec.resize(3).unwrap();
+        vec.resize(4).unwrap();
+        assert_eq!(vec.nvals().unwrap(), 3);
+        assert_eq!(
+            vec.clone().extract_tuples().unwrap(),
+            (vec![0, 1, 2], vec![0, 5, 3])
+        );
+        vec.clear().unwrap();
+        assert_eq!((vec.size(), vec.nvals().unwrap()), (4, 0));
+
+        let vec = FullVec::<i32>::new(3)
+            .unwrap()
+            .build(
+                [2, 0, 2].into_iter(),
+                [1, 2, 3].into_iter(),
+                3,
+                Addition::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            vec.clone().extract_tuples().unwrap(),
+            (vec![0, 2], vec![2, 4])
+        );
+        assert!(vec
+            .build([1].into_iter(), [1].into_iter(), 1, Addition::new())
+            .is_err());
+    }
+}
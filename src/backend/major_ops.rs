@@ -0,0 +1,1205 @@
+use crate::{
+    algebra::{
+        AssociativeCommutative, BinaryOperator, IndexUnaryOperator, Monoid, Semiring, UnaryOperator,
+    },
+    backend::{
+        pending::Defer,
+        utils::{
+            accum_mult_vec, accum_scalar, assign_vec, check_mat_mask, check_vals, check_vec_mask,
+            dims, ewise_and_vec, ewise_or_accum_vec, extract_vec, full_rows, is_by_col,
+            iter_entries, masked_write_vec, run_mat_op, run_vec_op, scalar_value, to_entries,
+            to_rows, transpose_rows, Flipped, IndexMap, InputRows, Positions, Rows,
+        },
+    },
+    context,
+    descriptor::Descriptor,
+    indices::Indices,
+    mask::{MatMask, VecMask},
+    matrix::{Compressed, Matrix, MatrixExtra, Orientation},
+    operations::MatOps,
+    scalar::Scalar,
+    types::{IndexType, NoValue},
+    vector::Vector,
+    ApiError, GblasResult,
+};
+
+/// Matrix backends storing their entries by major vector: rows, or columns when stored
+/// [`Orientation::ByCol`].
+///
+/// The inputs of an operation are read through [`Matrix`] and the result is merged into the
+/// output one major vector at a time, so implementing this trait is enough to get [`MatOps`].
+/// Operations go through [`Defer`], so a backend queuing them only has to override it.
+pub(crate) trait MajorStorage: MatrixExtra + Defer {
+    /// Visits the major vectors in order, `f` gets its index and its `(index, value)` entries
+    /// sorted by index, which must still be sorted when it returns.
+    ///
+    /// The vectors in `majors` (sorted) are visited even when empty, along with the stored ones for
+    /// which `stored` holds. The others may be skipped, so `f` must leave them as they are: an
+    /// empty vector stays empty unless its index is in `majors`.
+    fn update_major(
+        &mut self,
+        majors: &[IndexType],
+        stored: impl Fn(IndexType) -> bool,
+        f: impl FnMut(IndexType, &mut Vec<(IndexType, Self::Scalar)>),
+    );
+}
+
+#[inline]
+fn stores_cols(c: &impl Matrix) -> bool {
+    c.orientation() == Orientation::ByCol
+}
+
+/// Whether a kernel should compute the columns of its result instead of the rows.
+///
+/// `inputs` tells for each input if it's read by column (see [`is_by_col`]). The orientation of
+/// most of the inputs and the output wins, so the fewest of them need a transpose. Ties go to
+/// the output.
+fn kernel_by_col(c: &impl Matrix, inputs: &[bool]) -> bool {
+    let c_by_col = stores_cols(c);
+    let by_col = inputs.iter().filter(|by_col| **by_col).count() + usize::from(c_by_col);
+    let by_row = inputs.len() + 1 - by_col;
+    if by_col == by_row {
+        c_by_col
+    } else {
+        by_col > by_row
+    }
+}
+
+/// _T = A B_ (Gustavson), `a` and `b` hold rows.
+fn mxm_rows<T: Clone>(
+    a: Compressed<'_, T>,
+    b: Compressed<'_, T>,
+    op: &impl Semiring<T, Output = T>,
+) -> Rows<T> {
+    let mut t = Rows::new();
+    let mut c_row = Vec::new();
+    for (i, a_row) in a.iter() {
+        for (k, a_ik) in a_row.iter() {
+            accum_mult_vec(&mut c_row, b.get(k).iter(), a_ik, op);
+        }
+        t.push(i, c_row.drain(..));
+    }
+    t
+}
+
+/// _C<M, accum> = T_
+///
+/// Writes `t` into `c`, going through the mask predicate, the accumulator and replace. `t` holds
+/// the columns of _T_ when `t_by_col` is set, its rows otherwise, and is only transposed if `c` is
+/// stored the other way.
+fn write_masked<T, M>(
+    c: &mut M,
+    mask: impl Fn(IndexType, IndexType) -> bool,
+    accum: Option<&impl BinaryOperator<T, Output = T>>,
+    t: Rows<T>,
+    t_by_col: bool,
+    replace: bool,
+) where
+    T: Clone + PartialEq,
+    M: MajorStorage<Scalar = T>,
+{
+    let c_by_col = stores_cols(c);
+    let (major, minor) = if c_by_col {
+        (c.ncols(), c.nrows())
+    } else {
+        (c.nrows(), c.ncols())
+    };
+    let t = if t_by_col == c_by_col {
+        t
+    } else {
+        transpose_rows(t.view(), major)
+    };
+    let t = t.view();
+    debug_assert!(t
+        .iter()
+        .all(|(k, t_vec)| k < major && t_vec.iter().all(|(m, _)| m < minor)));
+
+    // without accumulator the entries allowed by the mask are dropped from the vectors missing
+    // from `t`, replace drops the others
+    let every = accum.is_none() || replace;
+    let majors: Vec<IndexType> = t.indices().collect();
+    c.update_major(
+        &majors,
+        |_| every,
+        |k, c_vec| {
+            let mask = |m| if c_by_col { mask(m, k) } else { mask(k, m) };
+            masked_write_vec(c_vec, t.get(k).iter(), mask, accum, replace);
+        },
+    );
+}
+
+/// _C<M>(I, J) = C(I, J) ⊙ A_, or _C(I, J)<M> = C(I, J) ⊙ A_ when `sub` is set.
+///
+/// `a` holds the major vectors of the _|I| x |J|_ input in the orientation of `c`.
+#[allow(clippy::too_many_arguments)]
+fn assign_region<T, M>(
+    c: &mut M,
+    mask: impl Fn(IndexType, IndexType) -> bool,
+    accum: Option<&impl BinaryOperator<T, Output = T>>,
+    a: Compressed<'_, T>,
+    rows: &IndexMap,
+    cols: &IndexMap,
+    replace: bool,
+    sub: bool,
+) where
+    T: Clone + PartialEq,
+    M: MajorStorage<Scalar = T>,
+{
+    let c_by_col = stores_cols(c);
+    let (majors, minors) = if c_by_col { (cols, rows) } else { (rows, cols) };
+
+    let mask = |k, m| if c_by_col { mask(m, k) } else { mask(k, m) };
+
+    let mut a_majors: Vec<IndexType> = a.indices().filter_map(|s| majors.dest(s)).collect();
+    a_majors.sort_unstable();
+    a_majors.dedup();
+
+    // vectors of C(I, :) are rewritten, the others only change with replace
+    let every = !sub && replace;
+    let stored = |k| every || majors.src(k).is_some();
+    c.update_major(&a_majors, stored, |k, c_vec| match majors.src(k) {
+        Some(s1) if sub => {
+            let mask = |s2| mask(s1, s2);
+            assign_vec(c_vec, a.get(s1).iter(), minors, mask, accum, replace, true);
+        }
+        Some(s1) => {
+            let mask = |m| mask(k, m);
+            assign_vec(c_vec, a.get(s1).iter(), minors, mask, accum, replace, false);
+        }
+        // outside of C(I, J) only replace can change C
+        None if !sub && replace => c_vec.retain(|(m, _)| mask(k, *m)),
+        None => {}
+    });
+}
+
+/// _C(k, I)<m> = C(k, I) ⊙ u_ on the major vector `k` (a row, or a column when `c` is stored by
+/// column), or on the minor vector `k` when `minor` is set.
+#[allow(clippy::too_many_arguments)]
+fn assign_vector<T, M>(
+    c: &mut M,
+    k: IndexType,
+    u: &[(IndexType, T)],
+    map: &IndexMap,
+    mask: impl Fn(IndexType) -> bool,
+    accum: Option<&impl BinaryOperator<T, Output = T>>,
+    replace: bool,
+    minor: bool,
+) where
+    T: Clone + PartialEq,
+    M: MajorStorage<Scalar = T>,
+{
+    if !minor {
+        c.update_major(
+            &[k],
+            |_| false,
+            |_, c_vec| {
+                assign_vec(c_vec, iter_entries(u), map, &mask, accum, replace, false);
+            },
+        );
+        return;
+    }
+
+    // every entry of the minor vector is assigned on its own, so only the major vectors getting
+    // a value of `u` and the stored ones in `I` are visited, all the stored ones with replace
+    let mut majors: Vec<IndexType> = u.iter().filter_map(|(s, _)| map.dest(*s)).collect();
+    majors.sort_unstable();
+    majors.dedup();
+    c.update_major(
+        &majors,
+        |i| replace || map.src(i).is_some(),
+        |i, c_vec| {
+            let found = c_vec.binary_search_by_key(&k, |val| val.0);
+            let mut vec = match found {
+                Ok(idx) => vec![(i, c_vec.remove(idx).1)],
+                Err(_) => Vec::new(),
+            };
+            let u_i = map
+                .src(i)
+                .and_then(|s| u.binary_search_by_key(&s, |val| val.0).ok())
+                .map(|idx| (u[idx].0, &u[idx].1));
+            assign_vec(&mut vec, u_i.into_iter(), map, &mask, accum, replace, false);
+
+            if let Some((_, val)) = vec.pop() {
+                let idx = found.unwrap_or_else(|idx| idx);
+                c_vec.insert(idx, (k, val));
+            }
+        },
+    );
+}
+
+/// _C<M, accum>(i, j) = f(A(i, j), i, j)_, applied to the stored entries of _A_ only.
+///
+/// Entries mapped to `None` are dropped.
+fn apply_with<T, M>(
+    c: &mut M,
+    mask: Option<&impl MatMask<M>>,
+    accum: Option<impl BinaryOperator<T, Output = T> + Send + 'static>,
+    f: impl Fn(T, IndexType, IndexType) -> Option<T> + Send + 'static,
+    a: &impl Matrix<Scalar = T>,
+    transpose_a: bool,
+    desc: &Descriptor,
+) -> GblasResult<NoValue>
+where
+    T: Clone + PartialEq + Send + 'static,
+    M: MajorStorage<Scalar = T>,
+{
+    let (a_rows, a_cols) = dims(a, transpose_a);
+    check_vals(c.nrows(), a_rows)?;
+    check_vals(c.ncols(), a_cols)?;
+    check_mat_mask(mask, c.nrows(), c.ncols())?;
+
+    let by_col = kernel_by_col(c, &[is_by_col(a, transpose_a)]);
+    let a_data = to_rows(a, transpose_a != by_col)?;
+    let replace = desc.replace;
+    run_mat_op(c, mask, desc, [a_data], [], move |c, [a_data], [], mask| {
+        let f = &f;
+        let t = a_data
+            .iter()
+            .map(|(k, a_vec)| {
+                let t_vec = a_vec.iter().filter_map(move |(m, val)| {
+                    let (i, j) = if by_col { (m, k) } else { (k, m) };
+                    f(val.clone(), i, j).map(|val| (m, val))
+                });
+                (k, t_vec)
+            })
+            .collect();
+        write_masked(c, mask, accum.as_ref(), t, by_col, replace);
+    })
+}
+
+/// _C<M, accum> = kron(A, B)_
+///
+/// _C(iA * nrows(B) + iB, jA * ncols(B) + jB) = op(A(iA, jA), B(iB, jB))_. Output vectors are
+/// produced already sorted, so the cost is linear in the number of entries of the product.
+fn kronecker<T, M>(
+    c: &mut M,
+    mask: Option<&impl MatMask<M>>,
+    accum: Option<impl BinaryOperator<T, Output = T> + Send + 'static>,
+    op: impl BinaryOperator<T, Output = T> + Send + 'static,
+    a: &impl Matrix<Scalar = T>,
+    b: &impl Matrix<Scalar = T>,
+    desc: Option<Descriptor>,
+) -> GblasResult<NoValue>
+where
+    T: Clone + PartialEq + Send + 'static,
+    M: MajorStorage<Scalar = T>,
+{
+    let desc = context::descriptor_or_default(desc);
+    let transpose_a = desc.transpose_inp0;
+    let transpose_b = desc.transpose_inp1;
+
+    let (a_rows, a_cols) = dims(a, transpose_a);
+    let (b_rows, b_cols) = dims(b, transpose_b);
+    let nrows = a_rows
+        .checked_mul(b_rows)
+        .ok_or(ApiError::DimensionMismatch)?;
+    let ncols = a_cols
+        .checked_mul(b_cols)
+        .ok_or(ApiError::DimensionMismatch)?;
+    check_vals(c.nrows(), nrows)?;
+    check_vals(c.ncols(), ncols)?;
+    check_mat_mask(mask, nrows, ncols)?;
+
+    // kron(A, B)' = kron(A', B')
+    let by_col = kernel_by_col(c, &[is_by_col(a, transpose_a), is_by_col(b, transpose_b)]);
+    let a_data = to_rows(a, transpose_a != by_col)?;
+    let b_data = to_rows(b, transpose_b != by_col)?;
+    let (b_major, b_minor) = if by_col {
+        (b_cols, b_rows)
+    } else {
+        (b_rows, b_cols)
+    };
+
+    let replace = desc.replace;
+    run_mat_op(
+        c,
+        mask,
+        &desc,
+        [a_data, b_data],
+        [],
+        move |c, [a_data, b_data], [], mask| {
+            let op = &op;
+            let mut t = Rows::new();
+            for (ka, a_vec) in a_data.iter() {
+                for (kb, b_vec) in b_data.iter() {
+                    let c_vec = a_vec.iter().flat_map(|(ma, a_val)| {
+                        b_vec.iter().map(move |(mb, b_val)| {
+                            (ma * b_minor + mb, op.op(a_val.clone(), b_val.clone()))
+                        })
+                    });
+                    t.push(ka * b_major + kb, c_vec);
+                }
+            }
+            write_masked(c, mask, accum.as_ref(), t, by_col, replace);
+        },
+    )
+}
+
+/// _C<M, accum> = u ⊗ v'_
+fn outer<T, M>(
+    c: &mut M,
+    mask: Option<&impl MatMask<M>>,
+    accum: Option<impl BinaryOperator<T, Output = T> + Send + 'static>,
+    op: impl BinaryOperator<T, Output = T> + Send + 'static,
+    u: &impl Vector<Scalar = T>,
+    v: &impl Vector<Scalar = T>,
+    desc: Option<Descriptor>,
+) -> GblasResult<NoValue>
+where
+    T: Clone + PartialEq + Send + 'static,
+    M: MajorStorage<Scalar = T>,
+{
+    let desc = context::descriptor_or_default(desc);
+
+    check_vals(c.nrows(), u.size())?;
+    check_vals(c.ncols(), v.size())?;
+    check_mat_mask(mask, c.nrows(), c.ncols())?;
+
+    let u_data = to_entries(u)?;
+    let v_data = to_entries(v)?;
+
+    let by_col = stores_cols(c);
+    let replace = desc.replace;
+    run_mat_op(
+        c,
+        mask,
+        &desc,
+        [],
+        [u_data, v_data],
+        move |c, [], [u_data, v_data], mask| {
+            let (majors, minors) = if by_col {
+                (v_data, u_data)
+            } else {
+                (u_data, v_data)
+            };
+            let mut t = Rows::new();
+            for (k, x) in majors {
+                let t_vec = minors.iter().map(|(m, y)| {
+                    let val = if by_col {
+                        op.op(y.clone(), x.clone())
+                    } else {
+                        op.op(x.clone(), y.clone())
+                    };
+                    (*m, val)
+                });
+                t.push(*k, t_vec);
+            }
+            write_masked(c, mask, accum.as_ref(), t, by_col, replace);
+        },
+    )
+}
+
+/// _C<M, accum> = A ∪ B_ when `union` is set, _C<M, accum> = A ∩ B_ otherwise.
+#[allow(clippy::too_many_arguments)]
+fn e_wise<T, M>(
+    c: &mut M,
+    mask: Option<&impl MatMask<M>>,
+    accum: Option<impl BinaryOperator<T, Output = T> + Send + 'static>,
+    op: impl BinaryOperator<T, Output = T> + Send + 'static,
+    a: &impl Matrix<Scalar = T>,
+    b: &impl Matrix<Scalar = T>,
+    desc: Option<Descriptor>,
+    union: bool,
+) -> GblasResult<NoValue>
+where
+    T: Clone + PartialEq + Send + 'static,
+    M: MajorStorage<Scalar = T>,
+{
+    let desc = context::descriptor_or_default(desc);
+    let transpose_a = desc.transpose_inp0;
+    let transpose_b = desc.transpose_inp1;
+
+    let (a_rows, a_cols) = dims(a, transpose_a);
+    let (b_rows, b_cols) = dims(b, transpose_b);
+    check_vals(a_rows, b_rows)?;
+    check_vals(a_cols, b_cols)?;
+    check_vals(c.nrows(), a_rows)?;
+    check_vals(c.ncols(), a_cols)?;
+    check_mat_mask(mask, c.nrows(), c.ncols())?;
+
+    let by_col = kernel_by_col(c, &[is_by_col(a, transpose_a), is_by_col(b, transpose_b)]);
+    let a_data = to_rows(a, transpose_a != by_col)?;
+    let b_data = to_rows(b, transpose_b != by_col)?;
+
+    let replace = desc.replace;
+    run_mat_op(
+        c,
+        mask,
+        &desc,
+        [a_data, b_data],
+        [],
+        move |c, [a_data, b_data], [], mask| {
+            let mut t = Rows::new();
+            let mut c_vec = Vec::new();
+            let mut a_it = a_data.iter().peekable();
+            let mut b_it = b_data.iter().peekable();
+            loop {
+                let k = match (a_it.peek(), b_it.peek()) {
+                    (Some((ka, _)), Some((kb, _))) => *ka.min(kb),
+                    (Some((ka, _)), None) if union => *ka,
+                    (None, Some((kb, _))) if union => *kb,
+                    _ => break,
+                };
+                let a_vec = a_it.next_if(|val| val.0 == k).map(|val| val.1);
+                let b_vec = b_it.next_if(|val| val.0 == k).map(|val| val.1);
+                let a_vec = a_vec.iter().flat_map(|vec| vec.iter());
+                let b_vec = b_vec.iter().flat_map(|vec| vec.iter());
+
+                if union {
+                    ewise_or_accum_vec(&mut c_vec, a_vec, b_vec, &op);
+                } else {
+                    ewise_and_vec(&mut c_vec, a_vec, b_vec, &op);
+                }
+                t.push(k, c_vec.drain(..));
+            }
+
+            write_masked(c, mask, accum.as_ref(), t, by_col, replace);
+        },
+    )
+}
+
+impl<T, M> MatOps for M
+where
+    T: Clone + PartialEq + Send + 'static,
+    M: MajorStorage<Scalar = T>,
+{
+    fn mxm(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Semiring<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        b: &impl Matrix<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let desc = context::descriptor_or_default(desc);
+        let transpose_a = desc.transpose_inp0;
+        let transpose_b = desc.transpose_inp1;
+
+        let (a_rows, a_cols) = dims(a, transpose_a);
+        let (b_rows, b_cols) = dims(b, transpose_b);
+        check_vals(a_cols, b_rows)?;
+        check_vals(self.nrows(), a_rows)?;
+        check_vals(self.ncols(), b_cols)?;
+        check_mat_mask(mask.as_ref(), self.nrows(), self.ncols())?;
+
+        let by_col = kernel_by_col(
+            self,
+            &[is_by_col(a, transpose_a), is_by_col(b, transpose_b)],
+        );
+        let inputs = if by_col {
+            // the rows of C' = B' A' are the columns of C
+            [to_rows(b, !transpose_b)?, to_rows(a, !transpose_a)?]
+        } else {
+            [to_rows(a, transpose_a)?, to_rows(b, transpose_b)?]
+        };
+
+        let replace = desc.replace;
+        run_mat_op(
+            self,
+            mask.as_ref(),
+            &desc,
+            inputs,
+            [],
+            move |c, [x, y], [], mask| {
+                let t = if by_col {
+                    mxm_rows(x, y, &Flipped(&op))
+                } else {
+                    mxm_rows(x, y, &op)
+                };
+                write_masked(c, mask, accum.as_ref(), t, by_col, replace);
+            },
+        )
+    }
+
+    fn e_wise_mult_binary_op(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        b: &impl Matrix<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        e_wise(self, mask.as_ref(), accum, op, a, b, desc, false)
+    }
+
+    fn e_wise_mult_monoid(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Monoid<Self::Scalar> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        b: &impl Matrix<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        e_wise(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, y| op.operate(x, y),
+            a,
+            b,
+            desc,
+            false,
+        )
+    }
+
+    fn e_wise_mult_semiring(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Semiring<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        b: &impl Matrix<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        e_wise(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, y| op.mult(x, y),
+            a,
+            b,
+            desc,
+            false,
+        )
+    }
+
+    fn e_wise_add_binary_op(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        b: &impl Matrix<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        e_wise(self, mask.as_ref(), accum, op, a, b, desc, true)
+    }
+
+    fn e_wise_add_monoid(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Monoid<Self::Scalar> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        b: &impl Matrix<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        e_wise(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, y| op.operate(x, y),
+            a,
+            b,
+            desc,
+            true,
+        )
+    }
+
+    fn e_wise_add_semiring(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Semiring<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        b: &impl Matrix<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        e_wise(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, y| op.add(x, y),
+            a,
+            b,
+            desc,
+            true,
+        )
+    }
+
+    fn extract(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        row_indices: Indices,
+        col_indices: Indices,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let desc = context::descriptor_or_default(desc);
+        let transpose_a = desc.transpose_inp0;
+
+        let (a_rows, a_cols) = dims(a, transpose_a);
+        check_vals(self.nrows(), row_indices.len(a_rows)?)?;
+        check_vals(self.ncols(), col_indices.len(a_cols)?)?;
+        check_mat_mask(mask.as_ref(), self.nrows(), self.ncols())?;
+        let by_col = kernel_by_col(self, &[is_by_col(a, transpose_a)]);
+        let (majors, minors) = if by_col {
+            (
+                Positions::new(&col_indices, a_cols)?,
+                Positions::new(&row_indices, a_rows)?,
+            )
+        } else {
+            (
+                Positions::new(&row_indices, a_rows)?,
+                Positions::new(&col_indices, a_cols)?,
+            )
+        };
+
+        let a_data = to_rows(a, transpose_a != by_col)?;
+        let replace = desc.replace;
+        run_mat_op(
+            self,
+            mask.as_ref(),
+            &desc,
+            [a_data],
+            [],
+            move |c, [a_data], [], mask| {
+                // only the stored major vectors are visited, however large the selection
+                let t = majors
+                    .select(a_data.iter())
+                    .into_iter()
+                    .map(|(k, a_vec)| (k, extract_vec(a_vec.iter(), &minors)))
+                    .collect();
+                write_masked(c, mask, accum.as_ref(), t, by_col, replace);
+            },
+        )
+    }
+
+    fn assign(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        row_indices: Indices,
+        col_indices: Indices,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let desc = context::descriptor_or_default(desc);
+        let transpose_a = desc.transpose_inp0;
+
+        let rows = IndexMap::new(&row_indices, self.nrows())?;
+        let cols = IndexMap::new(&col_indices, self.ncols())?;
+        let (a_rows, a_cols) = dims(a, transpose_a);
+        check_vals(rows.len(), a_rows)?;
+        check_vals(cols.len(), a_cols)?;
+        check_mat_mask(mask.as_ref(), self.nrows(), self.ncols())?;
+
+        let a_data = to_rows(a, transpose_a != stores_cols(self))?;
+        let replace = desc.replace;
+        run_mat_op(
+            self,
+            mask.as_ref(),
+            &desc,
+            [a_data],
+            [],
+            move |c, [a_data], [], mask| {
+                assign_region(
+                    c,
+                    mask,
+                    accum.as_ref(),
+                    a_data,
+                    &rows,
+                    &cols,
+                    replace,
+                    false,
+                );
+            },
+        )
+    }
+
+    fn assign_col<V>(
+        &mut self,
+        mask: Option<impl VecMask<V>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        u: &V,
+        row_indices: Indices,
+        col_index: IndexType,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue>
+    where
+        V: Vector<Scalar = Self::Scalar>,
+    {
+        context::check_init()?;
+        let desc = context::descriptor_or_default(desc);
+        if col_index >= self.ncols() {
+            return Err(ApiError::InvalidIndex.into());
+        }
+
+        let rows = IndexMap::new(&row_indices, self.nrows())?;
+        check_vals(rows.len(), u.size())?;
+        check_vec_mask(mask.as_ref(), self.nrows())?;
+        let u_data = to_entries(u)?;
+
+        let minor = !stores_cols(self);
+        let replace = desc.replace;
+        run_vec_op(
+            self,
+            mask.as_ref(),
+            &desc,
+            [],
+            [u_data],
+            move |c, [], [u_data], mask| {
+                assign_vector(
+                    c,
+                    col_index,
+                    u_data,
+                    &rows,
+                    mask,
+                    accum.as_ref(),
+                    replace,
+                    minor,
+                );
+            },
+        )
+    }
+
+    fn assign_row<V>(
+        &mut self,
+        mask: Option<impl VecMask<V>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        u: &V,
+        row_index: IndexType,
+        col_indices: Indices,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue>
+    where
+        V: Vector<Scalar = Self::Scalar>,
+    {
+        context::check_init()?;
+        let desc = context::descriptor_or_default(desc);
+        if row_index >= self.nrows() {
+            return Err(ApiError::InvalidIndex.into());
+        }
+
+        let cols = IndexMap::new(&col_indices, self.ncols())?;
+        check_vals(cols.len(), u.size())?;
+        check_vec_mask(mask.as_ref(), self.ncols())?;
+        let u_data = to_entries(u)?;
+
+        let minor = stores_cols(self);
+        let replace = desc.replace;
+        run_vec_op(
+            self,
+            mask.as_ref(),
+            &desc,
+            [],
+            [u_data],
+            move |c, [], [u_data], mask| {
+                assign_vector(
+                    c,
+                    row_index,
+                    u_data,
+                    &cols,
+                    mask,
+                    accum.as_ref(),
+                    replace,
+                    minor,
+                );
+            },
+        )
+    }
+
+    fn assign_value(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        value: impl Into<Scalar<Self::Scalar>>,
+        row_indices: Indices,
+        col_indices: Indices,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let desc = context::descriptor_or_default(desc);
+
+        let rows = IndexMap::new(&row_indices, self.nrows())?;
+        let cols = IndexMap::new(&col_indices, self.ncols())?;
+        check_mat_mask(mask.as_ref(), self.nrows(), self.ncols())?;
+
+        let a_data = if stores_cols(self) {
+            full_rows(cols.len(), rows.len(), value.into())
+        } else {
+            full_rows(rows.len(), cols.len(), value.into())
+        };
+        let replace = desc.replace;
+        run_mat_op(
+            self,
+            mask.as_ref(),
+            &desc,
+            [InputRows::Owned(a_data)],
+            [],
+            move |c, [a_data], [], mask| {
+                assign_region(
+                    c,
+                    mask,
+                    accum.as_ref(),
+                    a_data,
+                    &rows,
+                    &cols,
+                    replace,
+                    false,
+                );
+            },
+        )
+    }
+
+    fn subassign(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        row_indices: Indices,
+        col_indices: Indices,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let desc = context::descriptor_or_default(desc);
+        let transpose_a = desc.transpose_inp0;
+
+        let rows = IndexMap::new(&row_indices, self.nrows())?;
+        let cols = IndexMap::new(&col_indices, self.ncols())?;
+        let (a_rows, a_cols) = dims(a, transpose_a);
+        check_vals(rows.len(), a_rows)?;
+        check_vals(cols.len(), a_cols)?;
+        check_mat_mask(mask.as_ref(), rows.len(), cols.len())?;
+
+        let a_data = to_rows(a, transpose_a != stores_cols(self))?;
+        let replace = desc.replace;
+        run_mat_op(
+            self,
+            mask.as_ref(),
+            &desc,
+            [a_data],
+            [],
+            move |c, [a_data], [], mask| {
+                assign_region(c, mask, accum.as_ref(), a_data, &rows, &cols, replace, true);
+            },
+        )
+    }
+
+    fn subassign_value(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        value: impl Into<Scalar<Self::Scalar>>,
+        row_indices: Indices,
+        col_indices: Indices,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let desc = context::descriptor_or_default(desc);
+
+        let rows = IndexMap::new(&row_indices, self.nrows())?;
+        let cols = IndexMap::new(&col_indices, self.ncols())?;
+        check_mat_mask(mask.as_ref(), rows.len(), cols.len())?;
+
+        let a_data = if stores_cols(self) {
+            full_rows(cols.len(), rows.len(), value.into())
+        } else {
+            full_rows(rows.len(), cols.len(), value.into())
+        };
+        let replace = desc.replace;
+        run_mat_op(
+            self,
+            mask.as_ref(),
+            &desc,
+            [InputRows::Owned(a_data)],
+            [],
+            move |c, [a_data], [], mask| {
+                assign_region(c, mask, accum.as_ref(), a_data, &rows, &cols, replace, true);
+            },
+        )
+    }
+
+    fn apply(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl UnaryOperator<Self::Scalar> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let desc = context::descriptor_or_default(desc);
+        apply_with(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, _, _| Some(op.op(x)),
+            a,
+            desc.transpose_inp0,
+            &desc,
+        )
+    }
+
+    fn apply_1st(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        value: impl Into<Scalar<Self::Scalar>>,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let value = scalar_value(value.into())?;
+
+        let desc = context::descriptor_or_default(desc);
+        apply_with(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, _, _| Some(op.op(value.clone(), x)),
+            a,
+            desc.transpose_inp0,
+            &desc,
+        )
+    }
+
+    fn apply_2nd(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        value: impl Into<Scalar<Self::Scalar>>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let value = scalar_value(value.into())?;
+
+        let desc = context::descriptor_or_default(desc);
+        apply_with(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, _, _| Some(op.op(x, value.clone())),
+            a,
+            desc.transpose_inp0,
+            &desc,
+        )
+    }
+
+    fn apply_index_op<S: Clone + Send + 'static>(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl IndexUnaryOperator<Self::Scalar, S, Output = Self::Scalar> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        thunk: impl Into<Scalar<S>>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let thunk = scalar_value(thunk.into())?;
+
+        let desc = context::descriptor_or_default(desc);
+        apply_with(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, i, j| Some(op.op(x, i, j, thunk.clone())),
+            a,
+            desc.transpose_inp0,
+            &desc,
+        )
+    }
+
+    fn select<S: Clone + Send + 'static>(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl IndexUnaryOperator<Self::Scalar, S, Output = bool> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        thunk: impl Into<Scalar<S>>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let thunk = scalar_value(thunk.into())?;
+
+        let desc = context::descriptor_or_default(desc);
+        apply_with(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, i, j| op.op(x.clone(), i, j, thunk.clone()).then_some(x),
+            a,
+            desc.transpose_inp0,
+            &desc,
+        )
+    }
+
+    fn reduce(
+        &self,
+        val: &mut Self::Scalar,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
+        op: impl Monoid<Self::Scalar>,
+        _desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let res = self.iter().fold(op.identity(), |acc, (_, _, val)| {
+            op.operate(acc, val.clone())
+        });
+        *val = match accum {
+            Some(accum) => accum.op(val.clone(), res),
+            None => res,
+        };
+
+        Ok(())
+    }
+
+    fn reduce_scalar(
+        &self,
+        s: &mut Scalar<Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
+        op: impl Monoid<Self::Scalar>,
+        _desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let t = self
+            .iter()
+            .map(|(_, _, val)| val.clone())
+            .reduce(|acc, val| op.operate(acc, val));
+        accum_scalar(s, t, accum.as_ref());
+
+        Ok(())
+    }
+
+    fn reduce_scalar_binary_op(
+        &self,
+        s: &mut Scalar<Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + AssociativeCommutative,
+        _desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let t = self
+            .iter()
+            .map(|(_, _, val)| val.clone())
+            .reduce(|acc, val| op.op(acc, val));
+        accum_scalar(s, t, accum.as_ref());
+
+        Ok(())
+    }
+
+    fn outer_binary_op(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        v: &impl Vector<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        outer(self, mask.as_ref(), accum, op, u, v, desc)
+    }
+
+    fn outer_semiring(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Semiring<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        v: &impl Vector<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        outer(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, y| op.mult(x, y),
+            u,
+            v,
+            desc,
+        )
+    }
+
+    fn transpose(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        let desc = context::descriptor_or_default(desc);
+        // A' unless the input is already asked to be transposed
+        apply_with(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, _, _| Some(x),
+            a,
+            !desc.transpose_inp0,
+            &desc,
+        )
+    }
+
+    fn kronecker_binary_op(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        b: &impl Matrix<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        kronecker(self, mask.as_ref(), accum, op, a, b, desc)
+    }
+
+    fn kronecker_monoid(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Monoid<Self::Scalar> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        b: &impl Matrix<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        kronecker(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, y| op.operate(x, y),
+            a,
+            b,
+            desc,
+        )
+    }
+
+    fn kronecker_semiring(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Semiring<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        b: &impl Matrix<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        kronecker(
+            self,
+            mask.as_ref(),
+            accum,
+            move |x, y| op.mult(x, y),
+            a,
+            b,
+            desc,
+        )
+    }
+}
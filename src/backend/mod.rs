@@ -1,7 +1,26 @@
+pub mod bitmap_matrix;
+pub mod bitmap_vec;
+pub mod csc_matrix;
+pub mod csr_matrix;
+pub mod dcsr_matrix;
+pub mod full_matrix;
+pub mod full_vec;
 pub mod simple_vec;
 pub mod sparse_matrix;
 
+pub(crate) mod entry_ops;
+pub(crate) mod major_ops;
+pub(crate) mod pending;
+#[cfg(test)]
+pub(crate) mod test_utils;
 pub(crate) mod utils;
 
+pub use bitmap_matrix::BitmapMatrix;
+pub use bitmap_vec::BitmapVec;
+pub use csc_matrix::CscMatrix;
+pub use csr_matrix::CsrMatrix;
+pub use dcsr_matrix::DcsrMatrix;
+pub use full_matrix::FullMatrix;
+pub use full_vec::FullVec;
 pub use simple_vec::SimpleVec;
 pub use sparse_matrix::SparseMatrix;
@@ -0,0 +1,205 @@
+use std::sync::{Mutex, OnceLock};
+
+use crate::types::IndexType;
+
+/// Storage that can queue element updates and whole operations, and run them later (nonblocking
+/// mode).
+///
+/// Holds either the assembled data or the last assembled data plus the work queued since.
+/// Reads through `&self` run the queue on first access, so every method observes the same
+/// values it would in blocking mode.
+pub(crate) struct Pending<D: Assemble> {
+    data: OnceLock<D>,
+    queue: Mutex<Option<(D, Vec<Work<D>>)>>,
+}
+
+/// Data that knows how to apply a batch of queued updates.
+pub(crate) trait Assemble {
+    type Update;
+
+    /// Applies `updates` in order, later updates of a position override earlier ones.
+    fn assemble(&mut self, updates: Vec<Self::Update>);
+}
+
+/// Operation queued on an object, run on it when it is read.
+pub(crate) type Deferred<O> = Box<dyn FnOnce(&mut O) + Send>;
+
+/// Work queued on a [`Pending`], run in order.
+enum Work<D: Assemble> {
+    /// Consecutive element updates, assembled in one go
+    Updates(Vec<D::Update>),
+    Op(Deferred<D>),
+}
+
+/// Objects that can queue the operations writing into them.
+///
+/// Operations check [`Defer::defers`] once their arguments are validated and hand their kernel
+/// to [`Defer::defer`], with copies of their inputs and mask when it is queued.
+pub(crate) trait Defer: Sized {
+    /// Whether operations are queued instead of run, never by default.
+    fn defers(&self) -> bool {
+        false
+    }
+
+    /// Queues `op` to run before the object is next read, by default it runs right away.
+    fn defer(&mut self, op: Deferred<Self>) {
+        op(self)
+    }
+}
+
+impl<D: Assemble> Pending<D> {
+    pub(crate) fn new(data: D) -> Self {
+        Self {
+            data: OnceLock::from(data),
+            queue: Mutex::new(None),
+        }
+    }
+
+    /// Assembled data, running the queued work if any.
+    pub(crate) fn get(&self) -> &D {
+        self.data.get_or_init(|| {
+            let (mut data, queue) = self
+                .queue
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .take()
+                .expect("pending storage holds either data or a queue");
+            for work in queue {
+                match work {
+                    Work::Updates(updates) => data.assemble(updates),
+                    Work::Op(op) => op(&mut data),
+                }
+            }
+            data
+        })
+    }
+
+    pub(crate) fn get_mut(&mut self) -> &mut D {
+        self.get();
+        self.data.get_mut().unwrap()
+    }
+
+    pub(crate) fn into_inner(mut self) -> D {
+        self.get();
+        self.data.take().unwrap()
+    }
+
+    /// Queues `update` without touching the data.
+    pub(crate) fn push(&mut self, update: D::Update) {
+        let queue = self.queue_mut();
+        match queue.last_mut() {
+            Some(Work::Updates(updates)) => updates.push(update),
+            _ => queue.push(Work::Updates(vec![update])),
+        }
+    }
+
+    /// Queues `op` without touching the data.
+    pub(crate) fn push_op(&mut self, op: Deferred<D>) {
+        self.queue_mut().push(Work::Op(op));
+    }
+
+    /// Queue to push to, moving the data into it if it was assembled.
+    fn queue_mut(&mut self) -> &mut Vec<Work<D>> {
+        let queue = self.queue.get_mut().unwrap_or_else(|err| err.into_inner());
+        if let Some(data) = self.data.take() {
+            *queue = Some((data, Vec::new()));
+        }
+        &mut queue.as_mut().unwrap().1
+    }
+}
+
+impl<D: Assemble + Clone> Clone for Pending<D> {
+    fn clone(&self) -> Self {
+        Self::new(self.get().clone())
+    }
+}
+
+impl<D: Assemble + PartialEq> PartialEq for Pending<D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl<D: Assemble + std::fmt::Debug> std::fmt::Debug for Pending<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+/// Applies `updates` to the sorted entries `v`, returning the change in the number of entries.
+///
+/// `updates` holds `(index, value)` pairs in arrival order, `None` removes the entry.
+pub(crate) fn merge_updates<T>(
+    v: &mut Vec<(IndexType, T)>,
+    mut updates: Vec<(IndexType, Option<T>)>,
+) -> isize {
+    debug_assert!(v.is_sorted_by_key(|val| val.0));
+
+    // stable, so the last update of an index stays last
+    updates.sort_by_key(|val| val.0);
+
+    let before = v.len() as isize;
+    let old = core::mem::take(v);
+    let mut v_it = old.into_iter().peekable();
+    let mut up_it = updates.into_iter().peekable();
+
+    while let Some((idx, val)) = up_it.next() {
+        if up_it.peek().is_some_and(|next| next.0 == idx) {
+            continue;
+        }
+        while let Some(entry) = v_it.next_if(|entry| entry.0 < idx) {
+            v.push(entry);
+        }
+        v_it.next_if(|entry| entry.0 == idx);
+        if let Some(val) = val {
+            v.push((idx, val));
+        }
+    }
+    v.extend(v_it);
+
+    v.len() as isize - before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_updates() {
+        let mut v = vec![(1, 'a'), (3, 'b'), (5, 'c')];
+        let delta = merge_updates(
+            &mut v,
+            vec![
+                (4, Some('x')),
+                (1, None),
+                (5, Some('y')),
+                (4, None),
+                (0, Some('z')),
+            ],
+        );
+        assert_eq!(v, vec![(0, 'z'), (3, 'b'), (5, 'y')]);
+        assert_eq!(delta, 0);
+
+        let delta = merge_updates(&mut v, vec![(7, Some('w')), (2, None)]);
+        assert_eq!(v, vec![(0, 'z'), (3, 'b'), (5, 'y'), (7, 'w')]);
+        assert_eq!(delta, 1);
+    }
+
+    #[test]
+    fn test_pending() {
+        let mut data = Pending::new(vec![(0, 1), (2, 3)]);
+        data.push((1, Some(2)));
+        data.push((0, None));
+        data.push((1, Some(4)));
+
+        let copy = data.clone();
+        assert_eq!(data.get(), &vec![(1, 4), (2, 3)]);
+        assert_eq!(copy, data);
+
+        // operations run in order with the updates around them
+        data.push((2, None));
+        data.push_op(Box::new(|v| v.iter_mut().for_each(|val| val.1 *= 10)));
+        data.push((3, Some(5)));
+        assert_eq!(data.into_inner(), vec![(1, 40), (3, 5)]);
+    }
+}
@@ -1,9 +1,20 @@
 use crate::types::IndexType;
 
+use super::pending::{merge_updates, Assemble, Pending};
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct SimpleVec<T> {
-    pub data: Vec<(IndexType, T)>,
+    data: Pending<Vec<(IndexType, T)>>,
     pub size: IndexType,
 }
 
+impl<T> Assemble for Vec<(IndexType, T)> {
+    type Update = (IndexType, Option<T>);
+
+    fn assemble(&mut self, updates: Vec<Self::Update>) {
+        merge_updates(self, updates);
+    }
+}
+
+mod ops_impl;
 mod vector_impl;
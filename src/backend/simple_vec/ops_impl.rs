@@ -0,0 +1,896 @@
+use crate::{
+    backend::{
+        entry_ops::EntryStorage,
+        pending::{Defer, Deferred, Pending},
+    },
+    context,
+    types::IndexType,
+};
+
+use super::SimpleVec;
+
+impl<T> EntryStorage for SimpleVec<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    fn update_entries(&mut self, f: impl FnOnce(&mut Vec<(IndexType, T)>)) {
+        f(self.data.get_mut());
+    }
+}
+
+/// Operations are queued in nonblocking mode, along with the element updates.
+impl<T: 'static> Defer for SimpleVec<T> {
+    fn defers(&self) -> bool {
+        context::is_nonblocking()
+    }
+
+    fn defer(&mut self, op: Deferred<Self>) {
+        let size = self.size;
+        self.data.push_op(Box::new(move |data| {
+            // the operation runs on a vector around the assembled entries
+            let mut vec = Self {
+                data: Pending::new(core::mem::take(data)),
+                size,
+            };
+            op(&mut vec);
+            *data = vec.data.into_inner();
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algebra::{
+            AbsoluteOp, Addition, LogicalOr, LogicalSemiring, MaxMonoid, Maximum, MinPlusSemiring,
+            Minimum, Multiplication, PlusMonoid, PlusTimesSemiring, RowLeOp, Subtraction,
+            TimesMonoid, ValueGeOp, ValueLtOp,
+        },
+        backend::{test_utils::init, SparseMatrix},
+        descriptor::Descriptor,
+        indices::Indices,
+        mask::VecMask,
+        matrix::Matrix,
+        operations::VecOps,
+        scalar::Scalar,
+        vector::Vector,
+    };
+
+    use super::*;
+
+    fn vector(size: IndexType, values: &[(IndexType, i32)]) -> SimpleVec<i32> {
+        let mut vec = SimpleVec::new(size).unwrap();
+        for (i, v) in values {
+            vec.set_element(*i, *v).unwrap();
+        }
+        vec
+    }
+
+    fn matrix(
+        nrows: IndexType,
+        ncols: IndexType,
+        values: &[(IndexType, IndexType, i32)],
+    ) -> SparseMatrix<i32> {
+        let mut mat = SparseMatrix::new(nrows, ncols).unwrap();
+        for (i, j, v) in values {
+            mat.set_element(*i, *j, *v).unwrap();
+        }
+        mat
+    }
+
+    #[test]
+    fn test_mxv() {
+        init();
+        // [1 2 0]   [1]   [ 1]
+        // [0 0 3] * [0] = [ 6]
+        //           [2]
+        let a = matrix(2, 3, &[(0, 0, 1), (0, 1, 2), (1, 2, 3)]);
+        let u = vector(3, &[(0, 1), (2, 2)]);
+        let mut w = SimpleVec::new(2).unwrap();
+
+        w.mxv(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &u,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(2, &[(0, 1), (1, 6)]));
+
+        // A' * v with accumulation
+        let v = vector(2, &[(1, 1)]);
+        let mut w = vector(3, &[(0, 10), (2, 10)]);
+        w.mxv(
+            Option::<SimpleVec<i32>>::None,
+            Some(Addition::new()),
+            PlusTimesSemiring::new(),
+            &a,
+            &v,
+            Some(Descriptor::DESC_T0),
+        )
+        .unwrap();
+        assert_eq!(w, vector(3, &[(0, 10), (2, 13)]));
+
+        let res = w.mxv(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &v,
+            None,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_vxm() {
+        init();
+        // Single source shortest path step over min.+
+        let a = matrix(3, 3, &[(0, 1, 4), (0, 2, 1), (2, 1, 2)]);
+        let u = vector(3, &[(0, 0)]);
+
+        let mut w = u.clone();
+        w.vxm(
+            Option::<SimpleVec<i32>>::None,
+            Some(Minimum::new()),
+            MinPlusSemiring::new(),
+            &u,
+            &a,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(3, &[(0, 0), (1, 4), (2, 1)]));
+
+        let u = w.clone();
+        w.vxm(
+            Option::<SimpleVec<i32>>::None,
+            Some(Minimum::new()),
+            MinPlusSemiring::new(),
+            &u,
+            &a,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(3, &[(0, 0), (1, 3), (2, 1)]));
+
+        // u * A' without a mask overwrites all of w
+        let mut w = vector(3, &[(1, 7)]);
+        w.vxm(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &vector(3, &[(1, 1)]),
+            &a,
+            Some(Descriptor::DESC_T1),
+        )
+        .unwrap();
+        assert_eq!(w, vector(3, &[(0, 4), (2, 2)]));
+    }
+
+    #[test]
+    fn test_vxm_bfs_frontier() {
+        init();
+        // 0 -> 1, 0 -> 2, 1 -> 3, 2 -> 3, 3 -> 0
+        let mut a = SparseMatrix::<bool>::new(4, 4).unwrap();
+        for (i, j) in [(0, 1), (0, 2), (1, 3), (2, 3), (3, 0)] {
+            a.set_element(i, j, true).unwrap();
+        }
+
+        let mut visited = SimpleVec::<bool>::new(4).unwrap();
+        let mut q = SimpleVec::<bool>::new(4).unwrap();
+        q.set_element(0, true).unwrap();
+
+        let mut levels = vec![];
+        while q.nvals().unwrap() > 0 {
+            let (frontier, _) = q.clone().extract_tuples().unwrap();
+            for i in frontier.iter() {
+                visited.set_element(*i, true).unwrap();
+            }
+            levels.push(frontier);
+
+            // q<!visited> = q * A
+            let u = q.clone();
+            q.vxm(
+                Some(visited.structure()),
+                Option::<LogicalOr>::None,
+                LogicalSemiring::new(),
+                &u,
+                &a,
+                Some(Descriptor::DESC_RSC),
+            )
+            .unwrap();
+        }
+
+        assert_eq!(levels, vec![vec![0], vec![1, 2], vec![3]]);
+    }
+
+    #[test]
+    fn test_mxv_value_mask() {
+        init();
+        let a = matrix(3, 3, &[(0, 0, 1), (1, 1, 2), (2, 2, 3)]);
+        let u = vector(3, &[(0, 1), (1, 1), (2, 1)]);
+        let mask = vector(3, &[(0, 1), (1, 0)]);
+
+        let mut w = SimpleVec::new(3).unwrap();
+        w.mxv(
+            Some(mask.clone()),
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &u,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(3, &[(0, 1)]));
+
+        let mut w = SimpleVec::new(3).unwrap();
+        w.mxv(
+            Some(mask.structure()),
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &u,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(3, &[(0, 1), (1, 2)]));
+
+        let mut w = SimpleVec::new(3).unwrap();
+        w.mxv(
+            Some(mask.complement()),
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &u,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(3, &[(1, 2), (2, 3)]));
+    }
+
+    #[test]
+    fn test_e_wise_add() {
+        init();
+        let u = vector(4, &[(0, 1), (2, 2)]);
+        let v = vector(4, &[(0, 3), (3, 4)]);
+        let expected = vector(4, &[(0, 4), (2, 2), (3, 4)]);
+
+        let mut w = SimpleVec::new(4).unwrap();
+        w.e_wise_add_binary_op(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            Addition::new(),
+            &u,
+            &v,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, expected);
+
+        let mut w = SimpleVec::new(4).unwrap();
+        w.e_wise_add_monoid(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            PlusMonoid::new(),
+            &u,
+            &v,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, expected);
+
+        // semiring variant uses the additive monoid (min)
+        let mut w = SimpleVec::new(4).unwrap();
+        w.e_wise_add_semiring(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            MinPlusSemiring::new(),
+            &u,
+            &v,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(4, &[(0, 1), (2, 2), (3, 4)]));
+    }
+
+    #[test]
+    fn test_e_wise_mult() {
+        init();
+        let u = vector(4, &[(0, 2), (2, 2)]);
+        let v = vector(4, &[(0, 3), (3, 4)]);
+
+        let mut w = SimpleVec::new(4).unwrap();
+        w.e_wise_mult_binary_op(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            Multiplication::new(),
+            &u,
+            &v,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(4, &[(0, 6)]));
+
+        let mut w = SimpleVec::new(4).unwrap();
+        w.e_wise_mult_monoid(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            TimesMonoid::new(),
+            &u,
+            &v,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(4, &[(0, 6)]));
+
+        // semiring variant uses the multiplicative operator (plus)
+        let mut w = SimpleVec::new(4).unwrap();
+        w.e_wise_mult_semiring(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            MinPlusSemiring::new(),
+            &u,
+            &v,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(4, &[(0, 5)]));
+    }
+
+    #[test]
+    fn test_e_wise_mask_accum_replace() {
+        init();
+        let u = vector(4, &[(0, 1), (1, 5), (2, 2)]);
+        let v = vector(4, &[(0, 3), (1, 1), (3, 4)]);
+        let mask = vector(4, &[(0, 1), (1, 1), (3, 1)]);
+
+        // w<mask> max= u ∪ v
+        let mut w = vector(4, &[(1, 10), (2, 10)]);
+        w.e_wise_add_monoid(
+            Some(mask.clone()),
+            Some(Addition::new()),
+            MaxMonoid::new(),
+            &u,
+            &v,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(4, &[(0, 3), (1, 15), (2, 10), (3, 4)]));
+
+        // w<!mask, replace> = u ∩ v
+        let mut w = vector(4, &[(1, 10), (2, 10)]);
+        w.e_wise_mult_binary_op(
+            Some(mask.clone()),
+            Option::<Addition<i32>>::None,
+            Addition::new(),
+            &u,
+            &v,
+            Some(Descriptor::DESC_RC),
+        )
+        .unwrap();
+        assert_eq!(w, vector(4, &[]));
+
+        let res = w.e_wise_add_binary_op(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            Addition::new(),
+            &u,
+            &vector(3, &[]),
+            None,
+        );
+        assert!(res.is_err());
+
+        // the mask must have the size of w
+        let res = w.e_wise_add_binary_op(
+            Some(vector(5, &[(0, 1)])),
+            Option::<Addition<i32>>::None,
+            Addition::new(),
+            &u,
+            &v,
+            None,
+        );
+        assert!(res.is_err());
+        let res = w.assign_value(
+            Some(vector(3, &[(0, 1)]).structure()),
+            Option::<Addition<i32>>::None,
+            1,
+            Indices::All,
+            None,
+        );
+        assert!(res.is_err());
+        assert_eq!(w, vector(4, &[]));
+    }
+
+    #[test]
+    fn test_extract() {
+        init();
+        let u = vector(5, &[(0, 1), (2, 3), (4, 5)]);
+
+        // duplicates are allowed
+        let mut w = SimpleVec::new(4).unwrap();
+        w.extract(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &u,
+            Indices::List(vec![4, 0, 0, 1]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(4, &[(0, 5), (1, 1), (2, 1)]));
+
+        // w<mask> += u(4:-2:0)
+        let mut w = vector(3, &[(0, 10), (2, 10)]);
+        w.extract(
+            Some(vector(3, &[(0, 1), (1, 1)])),
+            Some(Addition::new()),
+            &u,
+            Indices::Backwards {
+                begin: 4,
+                end: 0,
+                inc: 2,
+            },
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(3, &[(0, 15), (1, 3), (2, 10)]));
+
+        let mut w = SimpleVec::new(4).unwrap();
+        let res = w.extract(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &u,
+            Indices::All,
+            None,
+        );
+        assert!(res.is_err());
+        let res = w.extract(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &u,
+            Indices::List(vec![0, 1, 2, 5]),
+            None,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_extract_col() {
+        init();
+        // [0 1]
+        // [2 0]
+        // [0 3]
+        let a = matrix(3, 2, &[(0, 1, 1), (1, 0, 2), (2, 1, 3)]);
+
+        let mut w = SimpleVec::new(2).unwrap();
+        w.extract_col(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &a,
+            Indices::List(vec![2, 0]),
+            1,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(2, &[(0, 3), (1, 1)]));
+
+        // first column of A' is the first row of A
+        let mut w = SimpleVec::new(2).unwrap();
+        w.extract_col(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &a,
+            Indices::All,
+            0,
+            Some(Descriptor::DESC_T0),
+        )
+        .unwrap();
+        assert_eq!(w, vector(2, &[(1, 1)]));
+
+        let res = w.extract_col(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &a,
+            Indices::All,
+            3,
+            Some(Descriptor::DESC_T0),
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_assign_subassign() {
+        init();
+        let u = vector(2, &[(0, 1), (1, 2)]);
+        let indices = Indices::List(vec![3, 1]);
+
+        // w<mask, replace>([3, 1]) = u
+        let mut w = vector(5, &[(0, 10), (1, 10), (4, 10)]);
+        w.assign(
+            Some(vector(5, &[(1, 1), (3, 1)])),
+            Option::<Addition<i32>>::None,
+            &u,
+            indices.clone(),
+            Some(Descriptor::DESC_R),
+        )
+        .unwrap();
+        assert_eq!(w, vector(5, &[(1, 2), (3, 1)]));
+
+        // the subassign mask is |I| long and only the region is affected
+        let mut w = vector(5, &[(0, 10), (1, 10), (4, 10)]);
+        w.subassign(
+            Some(vector(2, &[(0, 1)])),
+            Option::<Addition<i32>>::None,
+            &u,
+            indices.clone(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(5, &[(0, 10), (1, 10), (3, 1), (4, 10)]));
+
+        let mut w = vector(5, &[(0, 10), (1, 10), (4, 10)]);
+        w.subassign(
+            Some(vector(2, &[(0, 1)])),
+            Option::<Addition<i32>>::None,
+            &u,
+            indices,
+            Some(Descriptor::DESC_R),
+        )
+        .unwrap();
+        assert_eq!(w, vector(5, &[(0, 10), (3, 1), (4, 10)]));
+
+        // missing entries of u delete, unless accumulated
+        let mut w = vector(3, &[(0, 9), (1, 9)]);
+        w.assign(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &vector(2, &[(1, 5)]),
+            Indices::List(vec![0, 1]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(3, &[(1, 5)]));
+
+        let mut w = vector(3, &[(0, 9), (1, 9)]);
+        w.assign(
+            Option::<SimpleVec<i32>>::None,
+            Some(Addition::new()),
+            &vector(2, &[(1, 5)]),
+            Indices::List(vec![0, 1]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(3, &[(0, 9), (1, 14)]));
+
+        // last one wins
+        let mut w = SimpleVec::new(3).unwrap();
+        w.assign(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &vector(3, &[(0, 1), (1, 2), (2, 3)]),
+            Indices::List(vec![2, 2, 0]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(3, &[(0, 3), (2, 2)]));
+
+        let res = w.assign(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &vector(2, &[]),
+            Indices::All,
+            None,
+        );
+        assert!(res.is_err());
+        let res = w.subassign(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &vector(2, &[]),
+            Indices::List(vec![0, 3]),
+            None,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_assign_value() {
+        init();
+        let mut w = vector(4, &[(0, 1)]);
+        w.assign_value(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            5,
+            Indices::Range { begin: 1, end: 2 },
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(4, &[(0, 1), (1, 5), (2, 5)]));
+
+        w.assign_value(
+            Option::<SimpleVec<i32>>::None,
+            Some(Addition::new()),
+            5,
+            Indices::All,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(4, &[(0, 6), (1, 10), (2, 10), (3, 5)]));
+
+        // w([3, 0])<!mask> = 7
+        w.subassign_value(
+            Some(vector(2, &[(0, 1)])),
+            Option::<Addition<i32>>::None,
+            7,
+            Indices::List(vec![3, 0]),
+            Some(Descriptor::DESC_C),
+        )
+        .unwrap();
+        assert_eq!(w, vector(4, &[(0, 7), (1, 10), (2, 10), (3, 5)]));
+    }
+
+    #[test]
+    fn test_apply() {
+        init();
+        let u = vector(4, &[(0, -1), (2, 3), (3, -4)]);
+
+        let mut w = SimpleVec::new(4).unwrap();
+        w.apply(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            AbsoluteOp,
+            &u,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(4, &[(0, 1), (2, 3), (3, 4)]));
+        assert_eq!(w.nvals().unwrap(), 3);
+
+        // w<!m> = 2 * u
+        let mut w = vector(4, &[(1, 7), (3, 7)]);
+        w.apply_1st(
+            Some(vector(4, &[(3, 1)])),
+            Option::<Addition<i32>>::None,
+            Multiplication::new(),
+            2,
+            &u,
+            Some(Descriptor::DESC_C),
+        )
+        .unwrap();
+        assert_eq!(w, vector(4, &[(0, -2), (2, 6), (3, 7)]));
+
+        // w max= u - 1
+        w.apply_2nd(
+            Option::<SimpleVec<i32>>::None,
+            Some(Maximum::new()),
+            Subtraction::new(),
+            &u,
+            1,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(4, &[(0, -2), (2, 6), (3, 7)]));
+
+        let res = w.apply(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            |x: i32| x + 1,
+            &vector(5, &[]),
+            None,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_reduce() {
+        init();
+        let u = vector(4, &[(0, 1), (2, 3), (3, 4)]);
+
+        let mut val = 0;
+        u.reduce(
+            &mut val,
+            Option::<Addition<i32>>::None,
+            PlusMonoid::new(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(val, 8);
+
+        let mut val = 2;
+        u.reduce(
+            &mut val,
+            Some(Multiplication::new()),
+            TimesMonoid::new(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(val, 24);
+
+        let mut val = 5;
+        vector(4, &[])
+            .reduce(
+                &mut val,
+                Option::<Addition<i32>>::None,
+                PlusMonoid::new(),
+                None,
+            )
+            .unwrap();
+        assert_eq!(val, 0);
+    }
+
+    #[test]
+    fn test_reduce_rows() {
+        init();
+        // [1 0 2]
+        // [0 0 0]
+        // [3 4 0]
+        let a = matrix(3, 3, &[(0, 0, 1), (0, 2, 2), (2, 0, 3), (2, 1, 4)]);
+
+        // out-degree weights, empty rows stay empty
+        let mut w = SimpleVec::new(3).unwrap();
+        w.reduce_binary_op(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            Addition::new(),
+            &a,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(3, &[(0, 3), (2, 7)]));
+
+        // column-wise
+        let mut w = SimpleVec::new(3).unwrap();
+        w.reduce_monoid(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            PlusMonoid::new(),
+            &a,
+            Some(Descriptor::DESC_T0),
+        )
+        .unwrap();
+        assert_eq!(w, vector(3, &[(0, 4), (1, 4), (2, 2)]));
+
+        // w<m> += max(A(i, :))
+        let mut w = vector(3, &[(1, 5), (2, 5)]);
+        w.reduce_monoid(
+            Some(vector(3, &[(0, 1), (1, 1)])),
+            Some(Addition::new()),
+            MaxMonoid::new(),
+            &a,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(3, &[(0, 2), (1, 5), (2, 5)]));
+
+        let res = w.reduce_binary_op(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            Minimum::new(),
+            &matrix(2, 3, &[]),
+            None,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_select() {
+        init();
+        let u = vector(5, &[(0, 1), (1, -2), (3, 3), (4, -4)]);
+
+        let mut w = SimpleVec::new(5).unwrap();
+        w.select(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            ValueLtOp,
+            &u,
+            0,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(5, &[(1, -2), (4, -4)]));
+
+        // w<!m, replace> = u(0:2)
+        let mut w = vector(5, &[(2, 9), (3, 9)]);
+        w.select(
+            Some(vector(5, &[(0, 1)])),
+            Option::<Addition<i32>>::None,
+            RowLeOp,
+            &u,
+            2,
+            Some(Descriptor::DESC_RC),
+        )
+        .unwrap();
+        assert_eq!(w, vector(5, &[(1, -2)]));
+
+        // values compare against a thunk of their own type
+        let mut u = SimpleVec::<f64>::new(3).unwrap();
+        u.set_element(0, 0.5).unwrap();
+        u.set_element(2, 1.5).unwrap();
+        let mut w = SimpleVec::<f64>::new(3).unwrap();
+        w.select(
+            Option::<SimpleVec<f64>>::None,
+            Option::<Addition<f64>>::None,
+            ValueGeOp,
+            &u,
+            1.0,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w.nvals().unwrap(), 1);
+        assert_eq!(*w.extract_element(2).unwrap(), 1.5);
+    }
+
+    #[test]
+    fn test_apply_index_op() {
+        init();
+        let u = vector(5, &[(0, 1), (1, -2), (3, 3), (4, -4)]);
+
+        let mut w = SimpleVec::new(5).unwrap();
+        w.apply_index_op(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            |x: i32, i: IndexType, _: IndexType, t: i32| x * t + i as i32,
+            &u,
+            10,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(5, &[(0, 10), (1, -19), (3, 33), (4, -36)]));
+    }
+
+    #[test]
+    fn test_empty_scalar() {
+        init();
+        let u = vector(3, &[(0, 2), (2, 3)]);
+
+        let mut s = Scalar::new();
+        u.reduce_scalar_binary_op(&mut s, Some(Addition::new()), Maximum::new(), None)
+            .unwrap();
+        assert_eq!(s, Scalar::from(3));
+
+        vector(3, &[])
+            .reduce_scalar(
+                &mut s,
+                Option::<Addition<i32>>::None,
+                TimesMonoid::new(),
+                None,
+            )
+            .unwrap();
+        assert!(s.is_empty());
+
+        assert_eq!(u.extract_scalar(2).unwrap(), Scalar::from(3));
+        assert!(u.extract_scalar(1).unwrap().is_empty());
+
+        let mut w = u.clone();
+        w.subassign_value(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            Scalar::new(),
+            Indices::List(vec![2, 1]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(3, &[(0, 2)]));
+
+        // accumulating an empty scalar changes nothing
+        w.assign_value(
+            Option::<SimpleVec<i32>>::None,
+            Some(Addition::new()),
+            None,
+            Indices::All,
+            None,
+        )
+        .unwrap();
+        assert_eq!(w, vector(3, &[(0, 2)]));
+
+        let res = w.apply_1st(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            Addition::new(),
+            None,
+            &u,
+            None,
+        );
+        assert!(res.is_err());
+    }
+}
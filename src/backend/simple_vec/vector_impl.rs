@@ -1,11 +1,17 @@
+use std::ops::Index;
+
 use crate::{
     algebra::{BinaryOperator, First},
+    complement_mask::VectorComplementMask,
+    context::{self, WaitMode},
+    mask::VecMask,
+    structure_mask::VectorStructureMask,
     types::{IndexType, NoValue},
-    vector::Vector,
+    vector::{Vector, VectorUtils},
     ApiError, ExecutionError, GblasResult,
 };
 
-use super::SimpleVec;
+use super::{Pending, SimpleVec};
 
 impl<T> SimpleVec<T>
 where
@@ -23,7 +29,7 @@ where
             return None;
         }
 
-        let data = &mut self.data;
+        let data = self.data.get_mut();
 
         if data.is_empty() {
             data.push((index, val));
@@ -66,27 +72,29 @@ where
     type Scalar = T;
 
     fn new(size: IndexType) -> GblasResult<Self> {
+        context::check_init()?;
         if size == 0 {
             return Err(ApiError::InvalidValue.into());
         }
         Ok(Self {
-            data: Vec::with_capacity(size),
+            data: Pending::new(Vec::new()),
             size,
         })
     }
 
     fn dup(&self) -> GblasResult<Self> {
+        context::check_init()?;
         Ok(self.clone())
     }
 
     fn resize(&mut self, size: IndexType) -> GblasResult<NoValue> {
+        context::check_init()?;
         if size == 0 {
             return Err(ApiError::InvalidValue.into());
         }
 
-        self.data.reserve(size);
         if size < self.size {
-            self.data.retain(|(i, _)| *i < size);
+            self.data.get_mut().retain(|(i, _)| *i < size);
         }
         self.size = size;
 
@@ -94,7 +102,8 @@ where
     }
 
     fn clear(&mut self) -> GblasResult<NoValue> {
-        self.data.clear();
+        context::check_init()?;
+        self.data = Pending::new(Vec::new());
         Ok(())
     }
 
@@ -102,8 +111,9 @@ where
         self.size
     }
 
-    fn nvals(&self) -> IndexType {
-        self.data.len()
+    fn nvals(&self) -> GblasResult<IndexType> {
+        context::check_init()?;
+        Ok(self.data.get().len())
     }
 
     fn build(
@@ -113,7 +123,8 @@ where
         _: IndexType,
         dup: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
     ) -> GblasResult<Self> {
-        if self.nvals() == 0 {
+        context::check_init()?;
+        if self.nvals()? == 0 {
             return Err(ApiError::OutputNotEmpty.into());
         }
 
@@ -131,16 +142,29 @@ where
     }
 
     fn set_element(&mut self, index: IndexType, val: Self::Scalar) -> GblasResult<NoValue> {
+        context::check_init()?;
+        if context::is_nonblocking() {
+            if index >= self.size {
+                return Err(ApiError::InvalidIndex.into());
+            }
+            self.data.push((index, Some(val)));
+            return Ok(());
+        }
         self.set_element_dup(index, val, Option::<&First<_>>::None)
             .ok_or_else(|| ApiError::InvalidIndex.into())
     }
 
     fn remove_element(&mut self, index: IndexType) -> GblasResult<NoValue> {
+        context::check_init()?;
         if index >= self.size() {
             return Err(ApiError::InvalidIndex.into());
         }
+        if context::is_nonblocking() {
+            self.data.push((index, None));
+            return Ok(());
+        }
 
-        let data = &mut self.data;
+        let data = self.data.get_mut();
 
         if data.is_empty() {
             Ok(())
@@ -156,11 +180,12 @@ where
     }
 
     fn extract_element(&self, index: IndexType) -> GblasResult<&Self::Scalar> {
+        context::check_init()?;
         if index >= self.size() {
             return Err(ApiError::InvalidIndex.into());
         }
 
-        let data = &self.data;
+        let data = self.data.get();
 
         if data.is_empty() {
             Err(ApiError::NoValue.into())
@@ -172,14 +197,125 @@ where
     }
 
     fn extract_tuples(self) -> GblasResult<(Vec<IndexType>, Vec<Self::Scalar>)> {
-        let len = self.data.len();
-        let mut idxs = Vec::with_capacity(len);
-        let mut vals = Vec::with_capacity(len);
-        for (idx, val) in self.data {
+        context::check_init()?;
+        let data = self.data.into_inner();
+        let mut idxs = Vec::with_capacity(data.len());
+        let mut vals = Vec::with_capacity(data.len());
+        for (idx, val) in data {
             idxs.push(idx);
             vals.push(val);
         }
 
         Ok((idxs, vals))
     }
+
+    fn entries(&self) -> Option<&[(IndexType, Self::Scalar)]> {
+        Some(self.data.get())
+    }
+
+    fn wait(&mut self, _mode: WaitMode) -> GblasResult<NoValue> {
+        context::check_init()?;
+        self.data.get_mut();
+        Ok(())
+    }
+}
+
+impl<T> VectorUtils for SimpleVec<T>
+where
+    T: Clone + PartialEq,
+{
+    fn iter(&self) -> impl Iterator<Item = (IndexType, &Self::Scalar)> {
+        self.data.get().iter().map(|(i, val)| (*i, val))
+    }
+}
+
+/// Value mask: an entry counts only if its value is not the zero (default) of `T`.
+impl<T> Index<IndexType> for SimpleVec<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    type Output = bool;
+
+    fn index(&self, index: IndexType) -> &Self::Output {
+        let found = <Self as Vector>::extract_element(self, index);
+        match found {
+            Ok(val) if *val != T::default() => &true,
+            _ => &false,
+        }
+    }
+}
+
+impl<T> VecMask<Self> for SimpleVec<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    fn complement(&self) -> VectorComplementMask<'_, Self> {
+        VectorComplementMask::new(self)
+    }
+
+    fn structure(&self) -> VectorStructureMask<'_, Self> {
+        VectorStructureMask::new(self)
+    }
+
+    fn shape(&self) -> IndexType {
+        self.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::test_utils::init;
+
+    #[test]
+    fn test_vector_masks() {
+        init();
+        let mut vec = SimpleVec::<i32>::new(4).unwrap();
+        vec.set_element(0, 1).unwrap();
+        vec.set_element(1, 0).unwrap();
+
+        let str_mask = vec.structure();
+        let comp_mask = vec.complement();
+        let str_comp_mask = str_mask.complement();
+
+        assert!(vec[0]);
+        assert!(str_mask[0]);
+        assert!(!comp_mask[0]);
+        assert!(!str_comp_mask[0]);
+
+        assert!(!vec[1]);
+        assert!(str_mask[1]);
+        assert!(comp_mask[1]);
+        assert!(!str_comp_mask[1]);
+
+        assert!(!vec[2]);
+        assert!(!str_mask[2]);
+        assert!(comp_mask[2]);
+        assert!(str_comp_mask[2]);
+
+        // out of bounds
+        assert!(!vec[4]);
+        assert!(!str_mask[4]);
+        assert!(!comp_mask[4]);
+
+        // the structure of a complement is complemented too
+        let comp_str_mask = comp_mask.structure();
+        assert!(comp_str_mask.is_complement());
+        assert!(!comp_str_mask[0]);
+        assert!(!comp_str_mask[1]);
+        assert!(comp_str_mask[2]);
+        assert!(!comp_str_mask[4]);
+        assert!(!str_mask.structure().is_complement());
+
+        // complementing twice gives back the original mask
+        let value_mask = comp_mask.complement();
+        let str_mask = str_comp_mask.complement();
+        assert!(!value_mask.is_complement());
+        assert!(!str_mask.is_complement());
+        for i in 0..5 {
+            assert_eq!(value_mask[i], vec[i]);
+            assert_eq!(str_mask[i], vec.structure()[i]);
+        }
+        assert!(comp_mask.complement().complement()[2]);
+    }
 }
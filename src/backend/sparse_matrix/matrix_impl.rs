@@ -3,6 +3,7 @@ use std::{cmp::Ordering, ops::Index};
 use crate::{
     algebra::{BinaryOperator, First},
     complement_mask::MatrixComplementMask,
+    context::{self, WaitMode},
     mask::MatMask,
     matrix::{Matrix, MatrixExtra},
     structure_mask::MatrixStructureMask,
@@ -10,7 +11,7 @@ use crate::{
     ApiError, ExecutionError, GblasResult,
 };
 
-use super::SparseMatrix;
+use super::{Pending, SparseMatrix, Storage};
 
 impl<T> SparseMatrix<T>
 where
@@ -29,11 +30,12 @@ where
             return None;
         }
 
-        let data = &mut self.mat[row];
+        let Storage { mat, nvals } = self.data.get_mut();
+        let data = &mut mat[row];
 
         if data.is_empty() {
             data.push((col, value));
-            self.nvals += 1;
+            *nvals += 1;
         } else {
             let mut value = value;
             let found = data
@@ -55,11 +57,11 @@ where
                 }
                 Some((idx, false)) => {
                     data.insert(idx, (col, value));
-                    self.nvals += 1;
+                    *nvals += 1;
                 }
                 None => {
                     data.push((col, value));
-                    self.nvals += 1;
+                    *nvals += 1;
                 }
             }
         }
@@ -75,50 +77,53 @@ where
     type Scalar = T;
 
     fn new(rows: IndexType, cols: IndexType) -> GblasResult<Self> {
+        context::check_init()?;
         if rows == 0 || cols == 0 {
             return Err(ApiError::InvalidValue.into());
         }
 
         Ok(Self {
-            mat: vec![Vec::new(); rows],
+            data: Pending::new(Storage::new(rows)),
             nrows: rows,
             ncols: cols,
-            nvals: 0,
         })
     }
 
     fn resize(&mut self, rows: IndexType, cols: IndexType) -> GblasResult<NoValue> {
+        context::check_init()?;
         if rows == 0 || cols == 0 {
             return Err(ApiError::InvalidValue.into());
         }
 
-        self.mat.reserve(rows);
+        let Storage { mat, nvals } = self.data.get_mut();
+        mat.reserve(rows);
 
         if rows < self.nrows {
-            self.mat = self
-                .mat
+            *mat = mat
                 .iter_mut()
                 .enumerate()
                 .filter_map(|(idx, row)| {
                     if idx < rows {
                         Some(core::mem::take(row))
                     } else {
-                        self.nvals -= row.len();
+                        *nvals -= row.len();
                         None
                     }
                 })
                 .collect();
+        } else {
+            mat.resize_with(rows, Vec::new);
         }
         self.nrows = rows;
 
         if cols < self.ncols {
-            for row in self.mat.iter_mut() {
+            for row in mat.iter_mut() {
                 if !row.is_empty() {
                     // remove all elements that are out of bounds for new col
                     let sz = row.len();
                     row.retain(|(col, _)| *col < cols);
                     let diff = sz - row.len();
-                    self.nvals -= diff;
+                    *nvals -= diff;
                 }
             }
         }
@@ -128,11 +133,9 @@ where
     }
 
     fn clear(&mut self) -> GblasResult<NoValue> {
-        self.nvals = 0;
-        // TODO: Should we clear the row mat vec? Probably not since the outer vec only hold row indices
-        for row in self.mat.iter_mut() {
-            row.clear();
-        }
+        context::check_init()?;
+        // queued updates are dropped along with the old entries
+        self.data = Pending::new(Storage::new(self.nrows));
         Ok(())
     }
 
@@ -144,8 +147,9 @@ where
         self.ncols
     }
 
-    fn nvals(&self) -> IndexType {
-        self.nvals
+    fn nvals(&self) -> GblasResult<IndexType> {
+        context::check_init()?;
+        Ok(self.data.get().nvals)
     }
 
     fn build(
@@ -156,7 +160,8 @@ where
         _: IndexType,
         dup: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
     ) -> GblasResult<Self> {
-        if self.nvals > 0 {
+        context::check_init()?;
+        if self.nvals()? > 0 {
             return Err(ApiError::OutputNotEmpty.into());
         }
 
@@ -178,15 +183,29 @@ where
         col: IndexType,
         value: Self::Scalar,
     ) -> GblasResult<NoValue> {
+        context::check_init()?;
+        if context::is_nonblocking() {
+            if row >= self.nrows || col >= self.ncols {
+                return Err(ApiError::InvalidIndex.into());
+            }
+            self.data.push((row, col, Some(value)));
+            return Ok(());
+        }
         self.set_element_dup(row, col, value, Option::<&First<_>>::None)
             .ok_or_else(|| ApiError::InvalidIndex.into())
     }
 
     fn remove_element(&mut self, row: IndexType, col: IndexType) -> GblasResult<NoValue> {
+        context::check_init()?;
         if row >= self.nrows || col >= self.ncols {
             return Err(ApiError::InvalidIndex.into());
         }
-        let data = if let Some(data) = self.mat.get_mut(row) {
+        if context::is_nonblocking() {
+            self.data.push((row, col, None));
+            return Ok(());
+        }
+        let Storage { mat, nvals } = self.data.get_mut();
+        let data = if let Some(data) = mat.get_mut(row) {
             data
         } else {
             return Ok(());
@@ -198,17 +217,18 @@ where
             let found = data.iter().position(|(c, _)| *c == col);
             if let Some(idx) = found {
                 data.remove(idx);
-                self.nvals -= 1;
+                *nvals -= 1;
             }
             Ok(())
         }
     }
 
     fn extract_element(&self, row: IndexType, col: IndexType) -> GblasResult<&Self::Scalar> {
+        context::check_init()?;
         if row >= self.nrows || col >= self.ncols {
             return Err(ApiError::InvalidIndex.into());
         }
-        let data = self.mat.get(row).ok_or(ApiError::NoValue)?;
+        let data = self.data.get().mat.get(row).ok_or(ApiError::NoValue)?;
 
         if data.is_empty() {
             Err(ApiError::NoValue.into())
@@ -220,11 +240,13 @@ where
     }
 
     fn extract_tuples(self) -> GblasResult<(Vec<IndexType>, Vec<IndexType>, Vec<Self::Scalar>)> {
-        let mut rows = Vec::with_capacity(self.nvals);
-        let mut cols = Vec::with_capacity(self.nvals);
-        let mut values = Vec::with_capacity(self.nvals);
+        context::check_init()?;
+        let Storage { mat, nvals } = self.data.into_inner();
+        let mut rows = Vec::with_capacity(nvals);
+        let mut cols = Vec::with_capacity(nvals);
+        let mut values = Vec::with_capacity(nvals);
 
-        for (row, data) in self.mat.into_iter().enumerate() {
+        for (row, data) in mat.into_iter().enumerate() {
             for (col, value) in data.into_iter() {
                 rows.push(row);
                 cols.push(col);
@@ -234,6 +256,20 @@ where
 
         Ok((rows, cols, values))
     }
+
+    fn extract_major(&self, k: IndexType) -> GblasResult<Vec<(IndexType, Self::Scalar)>> {
+        context::check_init()?;
+        if k >= self.nrows {
+            return Err(ApiError::InvalidIndex.into());
+        }
+        Ok(self.data.get().mat[k].clone())
+    }
+
+    fn wait(&mut self, _mode: WaitMode) -> GblasResult<NoValue> {
+        context::check_init()?;
+        self.data.get_mut();
+        Ok(())
+    }
 }
 
 impl<T> MatrixExtra for SparseMatrix<T>
@@ -241,56 +277,65 @@ where
     T: Clone + PartialEq,
 {
     fn iter(&self) -> impl Iterator<Item = (IndexType, IndexType, &Self::Scalar)> {
-        self.mat
+        self.data
+            .get()
+            .mat
             .iter()
             .enumerate()
             .flat_map(|(i, row)| row.iter().map(move |(j, v)| (i, *j, v)))
     }
 }
 
+/// Value mask: an entry counts only if its value is not the zero (default) of `T`.
 impl<T> Index<(IndexType, IndexType)> for SparseMatrix<T>
 where
-    T: Clone + PartialEq,
+    T: Clone + PartialEq + Default,
 {
     type Output = bool;
 
     fn index(&self, index: (IndexType, IndexType)) -> &Self::Output {
         let found = <Self as Matrix>::extract_element(self, index.0, index.1);
-        if found.is_ok() {
-            &true
-        } else {
-            &false
+        match found {
+            Ok(val) if *val != T::default() => &true,
+            _ => &false,
         }
     }
 }
 
 impl<T> MatMask<Self> for SparseMatrix<T>
 where
-    T: Clone + PartialEq,
+    T: Clone + PartialEq + Default,
 {
-    fn complement(&self) -> MatrixComplementMask<Self> {
+    fn complement(&self) -> MatrixComplementMask<'_, Self> {
         MatrixComplementMask::new(self)
     }
 
-    fn structure(&self) -> MatrixStructureMask<Self> {
+    fn structure(&self) -> MatrixStructureMask<'_, Self> {
         MatrixStructureMask::new(self)
     }
+
+    fn shape(&self) -> (IndexType, IndexType) {
+        (self.nrows(), self.ncols())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backend::test_utils::init;
 
     #[test]
     fn test_matrix_new() {
+        init();
         let mat = SparseMatrix::<f64>::new(3, 3).unwrap();
         assert_eq!(mat.nrows(), 3);
         assert_eq!(mat.ncols(), 3);
-        assert_eq!(mat.nvals(), 0);
+        assert_eq!(mat.nvals().unwrap(), 0);
     }
 
     #[test]
     fn test_matrix_ops() {
+        init();
         let mut mat = SparseMatrix::<f64>::new(10, 10).unwrap();
         assert!(mat.set_element(0, 0, 1.0).is_ok());
         assert!(mat.set_element(1, 1, 2.0).is_ok());
@@ -308,7 +353,7 @@ mod tests {
         assert!(mat.set_element(8, 2, 9.0).is_ok());
         assert!(mat.set_element(8, 3, 9.0).is_ok());
         assert!(mat.set_element(8, 4, 9.0).is_ok());
-        assert_eq!(mat.nvals(), 15);
+        assert_eq!(mat.nvals().unwrap(), 15);
         let elem = mat.extract_element(0, 0);
         assert!(elem.is_ok());
         assert_eq!(elem.unwrap(), &1.0);
@@ -334,7 +379,7 @@ mod tests {
         let elem = mat.extract_element(8, 8);
         assert!(elem.is_err());
 
-        assert_eq!(mat.nvals(), 7);
+        assert_eq!(mat.nvals().unwrap(), 7);
 
         let res = mat.remove_element(1, 5);
         assert!(res.is_ok());
@@ -352,6 +397,7 @@ mod tests {
 
     #[test]
     fn test_masks() {
+        init();
         let mut mat = SparseMatrix::<f64>::new(10, 10).unwrap();
         mat.set_element(0, 0, 1.0).unwrap();
         mat.set_element(1, 1, 2.0).unwrap();
@@ -371,4 +417,34 @@ mod tests {
         assert_eq!(mat[(0, 0)], str_mask[(0, 0)]);
         assert_eq!(mat[(1, 1)], !comp_mask[(1, 1)]);
     }
+
+    #[test]
+    fn test_value_masks() {
+        init();
+        let mut mat = SparseMatrix::<f64>::new(3, 3).unwrap();
+        mat.set_element(0, 0, 1.0).unwrap();
+        mat.set_element(1, 1, 0.0).unwrap();
+
+        let str_mask = mat.structure();
+        let comp_mask = mat.complement();
+        let str_comp_mask = str_mask.complement();
+
+        // stored 1.0
+        assert!(mat[(0, 0)]);
+        assert!(str_mask[(0, 0)]);
+        assert!(!comp_mask[(0, 0)]);
+        assert!(!str_comp_mask[(0, 0)]);
+
+        // stored 0.0
+        assert!(!mat[(1, 1)]);
+        assert!(str_mask[(1, 1)]);
+        assert!(comp_mask[(1, 1)]);
+        assert!(!str_comp_mask[(1, 1)]);
+
+        // no entry
+        assert!(!mat[(2, 2)]);
+        assert!(!str_mask[(2, 2)]);
+        assert!(comp_mask[(2, 2)]);
+        assert!(str_comp_mask[(2, 2)]);
+    }
 }
@@ -1,11 +1,48 @@
 use crate::types::IndexType;
 
+use super::pending::{merge_updates, Assemble, Pending};
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct SparseMatrix<T> {
-    mat: Vec<Vec<(IndexType, T)>>,
+    data: Pending<Storage<T>>,
     nrows: IndexType,
     ncols: IndexType,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Storage<T> {
+    /// Sorted `(col, value)` entries of every row
+    mat: Vec<Vec<(IndexType, T)>>,
     nvals: IndexType,
 }
 
+impl<T> Storage<T> {
+    fn new(nrows: IndexType) -> Self {
+        Self {
+            mat: (0..nrows).map(|_| Vec::new()).collect(),
+            nvals: 0,
+        }
+    }
+}
+
+impl<T> Assemble for Storage<T> {
+    type Update = (IndexType, IndexType, Option<T>);
+
+    fn assemble(&mut self, mut updates: Vec<Self::Update>) {
+        // stable, so updates of a row keep their order
+        updates.sort_by_key(|val| val.0);
+
+        let mut it = updates.into_iter().peekable();
+        while let Some((row, col, val)) = it.next() {
+            let mut row_updates = vec![(col, val)];
+            while let Some((_, col, val)) = it.next_if(|next| next.0 == row) {
+                row_updates.push((col, val));
+            }
+            let delta = merge_updates(&mut self.mat[row], row_updates);
+            self.nvals = self.nvals.wrapping_add_signed(delta);
+        }
+    }
+}
+
 mod matrix_impl;
+mod ops_impl;
@@ -0,0 +1,1294 @@
+use crate::{
+    backend::{
+        major_ops::MajorStorage,
+        pending::{Defer, Deferred, Pending},
+    },
+    context,
+    types::IndexType,
+};
+
+use super::{SparseMatrix, Storage};
+
+impl<T> MajorStorage for SparseMatrix<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    fn update_major(
+        &mut self,
+        majors: &[IndexType],
+        stored: impl Fn(IndexType) -> bool,
+        mut f: impl FnMut(IndexType, &mut Vec<(IndexType, T)>),
+    ) {
+        let Storage { mat, nvals } = self.data.get_mut();
+        let mut majors = majors.iter().copied().peekable();
+        for (i, row) in mat.iter_mut().enumerate() {
+            let listed = majors.next_if_eq(&i).is_some();
+            while majors.next_if_eq(&i).is_some() {}
+            if listed || (!row.is_empty() && stored(i)) {
+                *nvals -= row.len();
+                f(i, row);
+                *nvals += row.len();
+            }
+        }
+    }
+}
+
+/// Operations are queued in nonblocking mode, after the pending element updates.
+impl<T: 'static> Defer for SparseMatrix<T> {
+    fn defers(&self) -> bool {
+        context::is_nonblocking()
+    }
+
+    fn defer(&mut self, op: Deferred<Self>) {
+        let (nrows, ncols) = (self.nrows, self.ncols);
+        self.data.push_op(Box::new(move |data| {
+            // the operation runs on a matrix around the assembled storage
+            let mut mat = Self {
+                data: Pending::new(core::mem::replace(data, Storage::new(0))),
+                nrows,
+                ncols,
+            };
+            op(&mut mat);
+            *data = mat.data.into_inner();
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algebra::{
+            AbsoluteOp, Addition, ColIndexOp, ColLeOp, MaxMonoid, MaxPlusSemiring, Minimum,
+            Multiplication, PlusMonoid, PlusTimesSemiring, RowIndexOp, Subtraction, TimesMonoid,
+            TrilOp, TriuOp, ValueGtOp,
+        },
+        backend::{
+            test_utils::{check_matches_sparse, init},
+            SimpleVec,
+        },
+        descriptor::Descriptor,
+        indices::Indices,
+        mask::MatMask,
+        matrix::Matrix,
+        operations::MatOps,
+        scalar::Scalar,
+        vector::Vector,
+        ApiError, GblasError,
+    };
+
+    use super::*;
+
+    fn matrix(
+        nrows: IndexType,
+        ncols: IndexType,
+        values: &[(IndexType, IndexType, i32)],
+    ) -> SparseMatrix<i32> {
+        let mut mat = SparseMatrix::new(nrows, ncols).unwrap();
+        for (i, j, v) in values {
+            mat.set_element(*i, *j, *v).unwrap();
+        }
+        mat
+    }
+
+    #[test]
+    fn test_battery() {
+        init();
+        check_matches_sparse::<SparseMatrix<i32>>();
+    }
+
+    #[test]
+    fn test_mxm() {
+        init();
+        // [1 2]   [5 0]   [19 12]
+        // [3 4] * [7 6] = [43 24]
+        let a = matrix(2, 2, &[(0, 0, 1), (0, 1, 2), (1, 0, 3), (1, 1, 4)]);
+        let b = matrix(2, 2, &[(0, 0, 5), (1, 0, 7), (1, 1, 6)]);
+        let mut c = SparseMatrix::new(2, 2).unwrap();
+
+        c.mxm(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &b,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            c,
+            matrix(2, 2, &[(0, 0, 19), (0, 1, 12), (1, 0, 43), (1, 1, 24)])
+        );
+    }
+
+    #[test]
+    fn test_mxm_transpose() {
+        init();
+        let a = matrix(2, 3, &[(0, 0, 1), (0, 2, 2), (1, 1, 3)]);
+        let b = matrix(2, 3, &[(0, 1, 4), (1, 2, 5)]);
+
+        // A' * B: (3x2) * (2x3)
+        let mut c = SparseMatrix::new(3, 3).unwrap();
+        c.mxm(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &b,
+            Some(Descriptor::DESC_T0),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(3, 3, &[(0, 1, 4), (1, 2, 15), (2, 1, 8)]));
+
+        // A * B': (2x3) * (3x2)
+        let mut c = SparseMatrix::new(2, 2).unwrap();
+        c.mxm(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &b,
+            Some(Descriptor::DESC_T1),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(0, 1, 10), (1, 0, 12)]));
+
+        let mut c = SparseMatrix::new(2, 2).unwrap();
+        let res = c.mxm(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &b,
+            None,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_mxm_mask_accum_replace() {
+        init();
+        let a = matrix(2, 2, &[(0, 0, 1), (0, 1, 2), (1, 0, 3), (1, 1, 4)]);
+        let b = matrix(2, 2, &[(0, 0, 1), (1, 1, 1)]);
+        let mask = matrix(2, 2, &[(0, 0, 1), (1, 1, 1)]);
+        let c_init = matrix(2, 2, &[(0, 0, 10), (0, 1, 20)]);
+
+        // Without replace, entries outside of the mask are kept
+        let mut c = c_init.clone();
+        c.mxm(
+            Some(mask.structure()),
+            Some(Addition::new()),
+            PlusTimesSemiring::new(),
+            &a,
+            &b,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(0, 0, 11), (0, 1, 20), (1, 1, 4)]));
+
+        // With replace, entries outside of the mask are removed
+        let mut c = c_init.clone();
+        c.mxm(
+            Some(mask.structure()),
+            Some(Addition::new()),
+            PlusTimesSemiring::new(),
+            &a,
+            &b,
+            Some(Descriptor::DESC_R),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(0, 0, 11), (1, 1, 4)]));
+        assert_eq!(c.nvals().unwrap(), 2);
+
+        // Complemented mask without accumulator overwrites the selected entries
+        let mut c = c_init;
+        c.mxm(
+            Some(mask.complement()),
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &b,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(0, 0, 10), (0, 1, 2), (1, 0, 3)]));
+    }
+
+    #[test]
+    fn test_mxm_structural_complement() {
+        init();
+        let a = matrix(2, 2, &[(0, 0, 1), (0, 1, 2), (1, 0, 3), (1, 1, 4)]);
+        let b = matrix(2, 2, &[(0, 0, 1), (1, 1, 1)]);
+        // value 0 at (1, 1) still counts as stored for the structure
+        let m = matrix(2, 2, &[(0, 0, 1), (1, 1, 0)]);
+        let expected = matrix(2, 2, &[(0, 1, 2), (1, 0, 3)]);
+
+        // C<!struct(M)> = A B, whether the complement comes from the mask or the descriptor
+        let mut c = SparseMatrix::new(2, 2).unwrap();
+        c.mxm(
+            Some(m.complement()),
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &b,
+            Some(Descriptor::DESC_S),
+        )
+        .unwrap();
+        assert_eq!(c, expected);
+
+        let mut c = SparseMatrix::new(2, 2).unwrap();
+        c.mxm(
+            Some(m.structure()),
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &b,
+            Some(Descriptor::DESC_SC),
+        )
+        .unwrap();
+        assert_eq!(c, expected);
+
+        // complementing twice gives back the structure
+        let mut c = SparseMatrix::new(2, 2).unwrap();
+        c.mxm(
+            Some(m.complement()),
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &b,
+            Some(Descriptor::DESC_SC),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(0, 0, 1), (1, 1, 4)]));
+
+        let mut c = SparseMatrix::new(2, 2).unwrap();
+        c.mxm(
+            Some(m.complement().complement()),
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &b,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(0, 0, 1)]));
+    }
+
+    #[test]
+    fn test_mask_dimension_mismatch() {
+        init();
+        let a = matrix(2, 2, &[(0, 0, 1), (1, 1, 2)]);
+        let mask = matrix(3, 3, &[(0, 0, 1)]);
+        let mut c = matrix(2, 2, &[(0, 1, 5)]);
+
+        let res = c.mxm(
+            Some(mask.structure()),
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &a,
+            None,
+        );
+        assert!(matches!(
+            res,
+            Err(GblasError::ApiError(ApiError::DimensionMismatch))
+        ));
+        let res = c.e_wise_add_binary_op(
+            Some(mask.complement()),
+            Option::<Addition<i32>>::None,
+            Addition::new(),
+            &a,
+            &a,
+            None,
+        );
+        assert!(res.is_err());
+        assert_eq!(c, matrix(2, 2, &[(0, 1, 5)]));
+
+        // subassign masks have the shape of the region
+        let region = matrix(1, 2, &[(0, 0, 1)]);
+        let res = c.subassign_value(
+            Some(mask.clone()),
+            Option::<Addition<i32>>::None,
+            3,
+            Indices::List(vec![1]),
+            Indices::All,
+            None,
+        );
+        assert!(res.is_err());
+        c.subassign_value(
+            Some(region),
+            Option::<Addition<i32>>::None,
+            3,
+            Indices::List(vec![1]),
+            Indices::All,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(0, 1, 5), (1, 0, 3)]));
+
+        let res = c.assign_row(
+            Some(SimpleVec::<i32>::new(3).unwrap()),
+            Option::<Addition<i32>>::None,
+            &SimpleVec::new(2).unwrap(),
+            0,
+            Indices::All,
+            None,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_mxm_composed_descriptor() {
+        init();
+        let a = matrix(2, 3, &[(0, 0, 1), (0, 2, 2), (1, 1, 3)]);
+        let b = matrix(3, 2, &[(1, 0, 4), (2, 1, 5)]);
+        let mask = matrix(2, 2, &[(0, 0, 1), (1, 1, 1)]);
+
+        // B' * A' = (A * B)'
+        let mut c = matrix(2, 2, &[(1, 1, 1)]);
+        c.mxm(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &b,
+            &a,
+            Some(Descriptor::DESC_RT0T1),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(0, 1, 12), (1, 0, 10)]));
+
+        // Complemented structural mask with replace
+        let mut c = matrix(2, 2, &[(0, 0, 7), (1, 1, 1)]);
+        c.mxm(
+            Some(mask.clone()),
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &b,
+            &a,
+            Some(Descriptor::DESC_RSCT0T1),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(0, 1, 12), (1, 0, 10)]));
+
+        // A complemented empty mask disables the write entirely
+        let mut c = matrix(2, 2, &[(1, 1, 1)]);
+        c.mxm(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &b,
+            &a,
+            Some(Descriptor::DESC_CT0T1),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(1, 1, 1)]));
+
+        c.mxm(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &b,
+            &a,
+            Some(Descriptor::DESC_RCT0T1),
+        )
+        .unwrap();
+        assert_eq!(c.nvals().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_mxm_value_mask() {
+        init();
+        let a = matrix(2, 2, &[(0, 0, 1), (0, 1, 2), (1, 0, 3), (1, 1, 4)]);
+        let b = matrix(2, 2, &[(0, 0, 1), (1, 1, 1)]);
+        // the stored zero at (1, 1) only counts for the structural mask
+        let mask = matrix(2, 2, &[(0, 0, 1), (1, 1, 0)]);
+
+        let mut c = SparseMatrix::new(2, 2).unwrap();
+        c.mxm(
+            Some(mask.clone()),
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &b,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(0, 0, 1)]));
+
+        let mut c = SparseMatrix::new(2, 2).unwrap();
+        c.mxm(
+            Some(mask.structure()),
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &b,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(0, 0, 1), (1, 1, 4)]));
+
+        let mut c = SparseMatrix::new(2, 2).unwrap();
+        c.mxm(
+            Some(mask.clone()),
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &b,
+            Some(Descriptor::DESC_S),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(0, 0, 1), (1, 1, 4)]));
+
+        let mut c = SparseMatrix::new(2, 2).unwrap();
+        c.mxm(
+            Some(mask.complement()),
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &b,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(0, 1, 2), (1, 0, 3), (1, 1, 4)]));
+
+        let mut c = SparseMatrix::new(2, 2).unwrap();
+        c.mxm(
+            Some(mask.structure().complement()),
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            &b,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(0, 1, 2), (1, 0, 3)]));
+    }
+
+    #[test]
+    fn test_e_wise_add() {
+        init();
+        let a = matrix(2, 3, &[(0, 0, 1), (0, 2, 2), (1, 1, 3)]);
+        let b = matrix(2, 3, &[(0, 0, 4), (1, 0, 5), (1, 1, 6)]);
+        let expected = matrix(2, 3, &[(0, 0, 5), (0, 2, 2), (1, 0, 5), (1, 1, 9)]);
+
+        let mut c = SparseMatrix::new(2, 3).unwrap();
+        c.e_wise_add_binary_op(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            Addition::new(),
+            &a,
+            &b,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, expected);
+
+        let mut c = SparseMatrix::new(2, 3).unwrap();
+        c.e_wise_add_monoid(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            PlusMonoid::new(),
+            &a,
+            &b,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, expected);
+
+        // semiring variant uses the additive monoid (max)
+        let mut c = SparseMatrix::new(2, 3).unwrap();
+        c.e_wise_add_semiring(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            MaxPlusSemiring::new(),
+            &a,
+            &b,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            c,
+            matrix(2, 3, &[(0, 0, 4), (0, 2, 2), (1, 0, 5), (1, 1, 6)])
+        );
+    }
+
+    #[test]
+    fn test_e_wise_mult() {
+        init();
+        let a = matrix(2, 3, &[(0, 0, 1), (0, 2, 2), (1, 1, 3)]);
+        let b = matrix(2, 3, &[(0, 0, 4), (1, 0, 5), (1, 1, 6)]);
+        let expected = matrix(2, 3, &[(0, 0, 4), (1, 1, 18)]);
+
+        let mut c = SparseMatrix::new(2, 3).unwrap();
+        c.e_wise_mult_binary_op(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            Multiplication::new(),
+            &a,
+            &b,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, expected);
+
+        let mut c = SparseMatrix::new(2, 3).unwrap();
+        c.e_wise_mult_monoid(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            TimesMonoid::new(),
+            &a,
+            &b,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, expected);
+
+        // semiring variant uses the multiplicative operator (plus)
+        let mut c = SparseMatrix::new(2, 3).unwrap();
+        c.e_wise_mult_semiring(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            MaxPlusSemiring::new(),
+            &a,
+            &b,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 3, &[(0, 0, 5), (1, 1, 9)]));
+    }
+
+    #[test]
+    fn test_e_wise_mask_accum_transpose() {
+        init();
+        let a = matrix(2, 2, &[(0, 0, 1), (0, 1, 2)]);
+        let b = matrix(2, 2, &[(0, 0, 3), (1, 0, 4)]);
+        let mask = matrix(2, 2, &[(0, 0, 1), (1, 0, 1)]);
+
+        // A' ∪ B' = [1 0; 2 0] ∪ [3 4; 0 0]
+        let mut c = matrix(2, 2, &[(0, 0, 10), (1, 1, 10)]);
+        c.e_wise_add_binary_op(
+            Some(mask.clone()),
+            Some(Minimum::new()),
+            Addition::new(),
+            &a,
+            &b,
+            Some(Descriptor::DESC_T0T1),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(0, 0, 4), (1, 0, 2), (1, 1, 10)]));
+
+        // A' ∩ B' with replace
+        let mut c = matrix(2, 2, &[(0, 0, 10), (1, 1, 10)]);
+        c.e_wise_mult_binary_op(
+            Some(mask),
+            Some(Addition::new()),
+            Multiplication::new(),
+            &a,
+            &b,
+            Some(Descriptor::DESC_RT0T1),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(0, 0, 13)]));
+
+        let mut c = SparseMatrix::new(2, 2).unwrap();
+        let res = c.e_wise_add_binary_op(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            Addition::new(),
+            &a,
+            &matrix(2, 3, &[]),
+            None,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_extract() {
+        init();
+        // [1 0 2]
+        // [0 3 0]
+        // [4 0 5]
+        let a = matrix(
+            3,
+            3,
+            &[(0, 0, 1), (0, 2, 2), (1, 1, 3), (2, 0, 4), (2, 2, 5)],
+        );
+
+        let mut c = SparseMatrix::new(2, 2).unwrap();
+        c.extract(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &a,
+            Indices::List(vec![2, 0]),
+            Indices::List(vec![0, 2]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            c,
+            matrix(2, 2, &[(0, 0, 4), (0, 1, 5), (1, 0, 1), (1, 1, 2)])
+        );
+
+        // A'(1, :)
+        let mut c = SparseMatrix::new(1, 3).unwrap();
+        c.extract(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &a,
+            Indices::List(vec![1]),
+            Indices::All,
+            Some(Descriptor::DESC_T0),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(1, 3, &[(0, 1, 3)]));
+
+        let mut c = matrix(2, 2, &[(0, 0, 10), (1, 1, 10)]);
+        c.extract(
+            Some(matrix(2, 2, &[(0, 0, 1), (1, 0, 1)])),
+            Some(Addition::new()),
+            &a,
+            Indices::List(vec![2, 0]),
+            Indices::List(vec![0, 2]),
+            Some(Descriptor::DESC_R),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(0, 0, 14), (1, 0, 1)]));
+
+        let res = c.extract(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &a,
+            Indices::All,
+            Indices::List(vec![0, 1]),
+            None,
+        );
+        assert!(res.is_err());
+        let res = c.extract(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &a,
+            Indices::List(vec![0, 3]),
+            Indices::List(vec![0, 1]),
+            None,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_assign_subassign() {
+        init();
+        let c0 = matrix(3, 3, &[(0, 0, 1), (1, 1, 1), (2, 2, 1)]);
+        let a = matrix(2, 2, &[(0, 0, 5), (1, 1, 6)]);
+        let rows = Indices::List(vec![0, 2]);
+        let cols = Indices::List(vec![2, 0]);
+
+        let mut c = c0.clone();
+        c.assign(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &a,
+            rows.clone(),
+            cols.clone(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(3, 3, &[(0, 2, 5), (1, 1, 1), (2, 0, 6)]));
+
+        // the assign mask covers the whole of C
+        let mask = matrix(3, 3, &[(0, 0, 1), (0, 2, 1), (1, 1, 1)]);
+        let mut c = c0.clone();
+        c.assign(
+            Some(mask.clone()),
+            Option::<Addition<i32>>::None,
+            &a,
+            rows.clone(),
+            cols.clone(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(3, 3, &[(0, 2, 5), (1, 1, 1), (2, 2, 1)]));
+
+        let mut c = c0.clone();
+        c.assign(
+            Some(mask),
+            Option::<Addition<i32>>::None,
+            &a,
+            rows.clone(),
+            cols.clone(),
+            Some(Descriptor::DESC_R),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(3, 3, &[(0, 2, 5), (1, 1, 1)]));
+
+        // the subassign mask is |I| x |J| and C outside C(I, J) is never touched
+        let mask = matrix(2, 2, &[(0, 0, 1)]);
+        let mut c = c0.clone();
+        c.subassign(
+            Some(mask.clone()),
+            Option::<Addition<i32>>::None,
+            &a,
+            rows.clone(),
+            cols.clone(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            c,
+            matrix(3, 3, &[(0, 0, 1), (0, 2, 5), (1, 1, 1), (2, 2, 1)])
+        );
+
+        let mut c = c0.clone();
+        c.subassign(
+            Some(mask),
+            Option::<Addition<i32>>::None,
+            &a,
+            rows.clone(),
+            cols,
+            Some(Descriptor::DESC_R),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(3, 3, &[(0, 2, 5), (1, 1, 1)]));
+
+        let mut c = c0;
+        let res = c.assign(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &a,
+            rows,
+            Indices::All,
+            None,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_assign_value_row_col() {
+        init();
+        let mut c = SparseMatrix::new(2, 3).unwrap();
+        c.assign_value(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            7,
+            Indices::All,
+            Indices::List(vec![1]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 3, &[(0, 1, 7), (1, 1, 7)]));
+
+        c.subassign_value(
+            Option::<SparseMatrix<i32>>::None,
+            Some(Addition::new()),
+            1,
+            Indices::List(vec![0]),
+            Indices::Range { begin: 0, end: 1 },
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 3, &[(0, 0, 1), (0, 1, 8), (1, 1, 7)]));
+
+        let mut c = matrix(2, 3, &[(0, 0, 1)]);
+        let mut u = SimpleVec::new(2).unwrap();
+        u.set_element(0, 4).unwrap();
+        u.set_element(1, 5).unwrap();
+
+        c.assign_row(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &u,
+            1,
+            Indices::List(vec![2, 0]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 3, &[(0, 0, 1), (1, 0, 5), (1, 2, 4)]));
+
+        c.assign_col(
+            Option::<SimpleVec<i32>>::None,
+            Some(Addition::new()),
+            &u,
+            Indices::All,
+            0,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 3, &[(0, 0, 5), (1, 0, 10), (1, 2, 4)]));
+
+        let res = c.assign_row(
+            Option::<SimpleVec<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &u,
+            2,
+            Indices::List(vec![0, 1]),
+            None,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_apply() {
+        init();
+        let a = matrix(2, 3, &[(0, 0, -1), (0, 2, 2), (1, 1, -3)]);
+
+        let mut c = SparseMatrix::new(2, 3).unwrap();
+        c.apply(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            AbsoluteOp,
+            &a,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 3, &[(0, 0, 1), (0, 2, 2), (1, 1, 3)]));
+        assert_eq!(c.nvals().unwrap(), 3);
+
+        // C = A' * 2
+        let mut c = SparseMatrix::new(3, 2).unwrap();
+        c.apply_2nd(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            Multiplication::new(),
+            &a,
+            2,
+            Some(Descriptor::DESC_T0),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(3, 2, &[(0, 0, -2), (1, 1, -6), (2, 0, 4)]));
+
+        // C<M, replace> += 10 - A
+        let mut c = matrix(2, 3, &[(0, 0, 1), (1, 2, 1)]);
+        c.apply_1st(
+            Some(matrix(2, 3, &[(0, 0, 1), (0, 2, 1)])),
+            Some(Addition::new()),
+            Subtraction::new(),
+            10,
+            &a,
+            Some(Descriptor::DESC_R),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 3, &[(0, 0, 12), (0, 2, 8)]));
+
+        let mut c = SparseMatrix::new(2, 3).unwrap();
+        c.apply(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            |x: i32| x * x,
+            &a,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 3, &[(0, 0, 1), (0, 2, 4), (1, 1, 9)]));
+
+        let res = c.apply(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            AbsoluteOp,
+            &a,
+            Some(Descriptor::DESC_T0),
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_reduce() {
+        init();
+        let a = matrix(2, 3, &[(0, 0, 1), (0, 2, 2), (1, 1, 3)]);
+
+        let mut val = 0;
+        a.reduce(
+            &mut val,
+            Option::<Addition<i32>>::None,
+            PlusMonoid::new(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(val, 6);
+
+        let mut val = 4;
+        a.reduce(&mut val, Some(Addition::new()), PlusMonoid::new(), None)
+            .unwrap();
+        assert_eq!(val, 10);
+
+        let mut val = 0;
+        a.reduce(
+            &mut val,
+            Option::<Addition<i32>>::None,
+            MaxMonoid::new(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(val, 3);
+
+        let mut val = 0;
+        SparseMatrix::<i32>::new(2, 2)
+            .unwrap()
+            .reduce(
+                &mut val,
+                Option::<Addition<i32>>::None,
+                TimesMonoid::new(),
+                None,
+            )
+            .unwrap();
+        assert_eq!(val, 1);
+    }
+
+    #[test]
+    fn test_transpose() {
+        init();
+        // [1 0 2]
+        // [0 3 0]
+        let a = matrix(2, 3, &[(0, 0, 1), (0, 2, 2), (1, 1, 3)]);
+
+        let mut c = SparseMatrix::new(3, 2).unwrap();
+        c.transpose(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &a,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(3, 2, &[(0, 0, 1), (1, 1, 3), (2, 0, 2)]));
+        assert_eq!(c.nvals().unwrap(), 3);
+
+        // transposing A' gives back A
+        let mut c = SparseMatrix::new(2, 3).unwrap();
+        c.transpose(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &a,
+            Some(Descriptor::DESC_T0),
+        )
+        .unwrap();
+        assert_eq!(c, a);
+
+        // C<M, replace> += A'
+        let mut c = matrix(3, 2, &[(0, 0, 10), (0, 1, 10), (2, 1, 10)]);
+        c.transpose(
+            Some(matrix(3, 2, &[(0, 0, 1), (2, 0, 1)])),
+            Some(Addition::new()),
+            &a,
+            Some(Descriptor::DESC_R),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(3, 2, &[(0, 0, 11), (2, 0, 2)]));
+
+        let mut c = SparseMatrix::new(2, 3).unwrap();
+        let res = c.transpose(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &a,
+            None,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_kronecker() {
+        init();
+        // [1 0]    [0 3 0]
+        // [0 2] ⊗ [4 0 0]
+        let a = matrix(2, 2, &[(0, 0, 1), (1, 1, 2)]);
+        let b = matrix(2, 3, &[(0, 1, 3), (1, 0, 4)]);
+
+        let mut c = SparseMatrix::new(4, 6).unwrap();
+        c.kronecker_binary_op(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            Multiplication::new(),
+            &a,
+            &b,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            c,
+            matrix(4, 6, &[(0, 1, 3), (1, 0, 4), (2, 4, 6), (3, 3, 8)])
+        );
+
+        // C<M> += kron(A, B)
+        let mut c = matrix(4, 6, &[(0, 1, 10), (3, 5, 1)]);
+        c.kronecker_semiring(
+            Some(matrix(4, 6, &[(0, 1, 1), (2, 4, 1), (3, 5, 1)])),
+            Some(Addition::new()),
+            PlusTimesSemiring::new(),
+            &a,
+            &b,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(4, 6, &[(0, 1, 13), (2, 4, 6), (3, 5, 1)]));
+
+        // kron(A, B') with the monoid operator
+        let mut c = SparseMatrix::new(6, 4).unwrap();
+        c.kronecker_monoid(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            PlusMonoid::new(),
+            &a,
+            &b,
+            Some(Descriptor::DESC_T1),
+        )
+        .unwrap();
+        assert_eq!(
+            c,
+            matrix(6, 4, &[(0, 1, 5), (1, 0, 4), (3, 3, 6), (4, 2, 5)])
+        );
+
+        let mut c = SparseMatrix::new(4, 5).unwrap();
+        let res = c.kronecker_binary_op(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            Multiplication::new(),
+            &a,
+            &b,
+            None,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_outer() {
+        init();
+        let mut u = SimpleVec::new(3).unwrap();
+        u.set_element(0, 1).unwrap();
+        u.set_element(2, 2).unwrap();
+        let mut v = SimpleVec::new(2).unwrap();
+        v.set_element(1, 3).unwrap();
+
+        let mut c = SparseMatrix::new(3, 2).unwrap();
+        c.outer_binary_op(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            Multiplication::new(),
+            &u,
+            &v,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(3, 2, &[(0, 1, 3), (2, 1, 6)]));
+
+        // rank-1 update C<!M> += u ⊗ v'
+        let mut c = matrix(3, 2, &[(0, 0, 1), (2, 1, 1)]);
+        c.outer_semiring(
+            Some(matrix(3, 2, &[(0, 1, 1)])),
+            Some(Addition::new()),
+            PlusTimesSemiring::new(),
+            &u,
+            &v,
+            Some(Descriptor::DESC_C),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(3, 2, &[(0, 0, 1), (2, 1, 7)]));
+
+        let mut c = SparseMatrix::new(2, 3).unwrap();
+        let res = c.outer_binary_op(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            Multiplication::new(),
+            &u,
+            &v,
+            None,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_select() {
+        init();
+        // [1 2 0]
+        // [3 0 4]
+        // [0 5 6]
+        let a = matrix(
+            3,
+            3,
+            &[
+                (0, 0, 1),
+                (0, 1, 2),
+                (1, 0, 3),
+                (1, 2, 4),
+                (2, 1, 5),
+                (2, 2, 6),
+            ],
+        );
+
+        // strictly lower triangle, as used for triangle counting
+        let mut c = SparseMatrix::new(3, 3).unwrap();
+        c.select(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            TrilOp,
+            &a,
+            -1,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(3, 3, &[(1, 0, 3), (2, 1, 5)]));
+
+        let mut c = SparseMatrix::new(3, 3).unwrap();
+        c.select(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            TriuOp,
+            &a,
+            0,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            c,
+            matrix(3, 3, &[(0, 0, 1), (0, 1, 2), (1, 2, 4), (2, 2, 6)])
+        );
+
+        let mut c = SparseMatrix::new(3, 3).unwrap();
+        c.select(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            ColLeOp,
+            &a,
+            0,
+            Some(Descriptor::DESC_T0),
+        )
+        .unwrap();
+        assert_eq!(c, matrix(3, 3, &[(0, 0, 1), (1, 0, 2)]));
+
+        // C<M> += A > 3
+        let mut c = matrix(3, 3, &[(1, 2, 10)]);
+        c.select(
+            Some(matrix(3, 3, &[(1, 2, 1), (2, 1, 1)])),
+            Some(Addition::new()),
+            ValueGtOp,
+            &a,
+            3,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(3, 3, &[(1, 2, 14), (2, 1, 5)]));
+
+        let mut c = SparseMatrix::new(3, 3).unwrap();
+        c.select(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            |x: i32, i: IndexType, j: IndexType, _: ()| i != j && x % 2 == 0,
+            &a,
+            (),
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(3, 3, &[(0, 1, 2), (1, 2, 4)]));
+    }
+
+    #[test]
+    fn test_apply_index_op() {
+        init();
+        let mut a = SparseMatrix::<i64>::new(3, 2).unwrap();
+        a.set_element(0, 1, 7).unwrap();
+        a.set_element(2, 0, 7).unwrap();
+
+        let mut c = SparseMatrix::<i64>::new(3, 2).unwrap();
+        c.apply_index_op(
+            Option::<SparseMatrix<i64>>::None,
+            Option::<Addition<i64>>::None,
+            RowIndexOp,
+            &a,
+            1,
+            None,
+        )
+        .unwrap();
+        assert_eq!(*c.extract_element(0, 1).unwrap(), 1);
+        assert_eq!(*c.extract_element(2, 0).unwrap(), 3);
+        assert_eq!(c.nvals().unwrap(), 2);
+
+        let mut c = SparseMatrix::<i64>::new(2, 3).unwrap();
+        c.apply_index_op(
+            Option::<SparseMatrix<i64>>::None,
+            Option::<Addition<i64>>::None,
+            ColIndexOp,
+            &a,
+            0,
+            Some(Descriptor::DESC_T0),
+        )
+        .unwrap();
+        assert_eq!(*c.extract_element(1, 0).unwrap(), 0);
+        assert_eq!(*c.extract_element(0, 2).unwrap(), 2);
+
+        // the output takes the type of the matrix through the thunk
+        let a = matrix(3, 2, &[(0, 1, 7), (2, 0, 7)]);
+        let mut c = SparseMatrix::<i32>::new(3, 2).unwrap();
+        c.apply_index_op(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            RowIndexOp,
+            &a,
+            -1,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(3, 2, &[(0, 1, -1), (2, 0, 1)]));
+    }
+
+    #[test]
+    fn test_empty_scalar() {
+        init();
+        let a = matrix(2, 2, &[(0, 0, 1), (1, 0, 2), (1, 1, 3)]);
+
+        let mut s = Scalar::from(10);
+        a.reduce_scalar(&mut s, Some(Addition::new()), PlusMonoid::new(), None)
+            .unwrap();
+        assert_eq!(s, Scalar::from(16));
+
+        // an empty reduction leaves an accumulated scalar untouched, and clears it otherwise
+        let empty = SparseMatrix::<i32>::new(2, 2).unwrap();
+        empty
+            .reduce_scalar_binary_op(&mut s, Some(Addition::new()), Addition::new(), None)
+            .unwrap();
+        assert_eq!(s, Scalar::from(16));
+        empty
+            .reduce_scalar(
+                &mut s,
+                Option::<Addition<i32>>::None,
+                PlusMonoid::new(),
+                None,
+            )
+            .unwrap();
+        assert!(s.is_empty());
+
+        assert_eq!(a.extract_scalar(1, 0).unwrap(), Scalar::from(2));
+        assert!(a.extract_scalar(0, 1).unwrap().is_empty());
+        assert!(a.extract_scalar(2, 0).is_err());
+
+        // assigning an empty scalar deletes C(I, J)
+        let mut c = a.clone();
+        c.assign_value(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            Scalar::new(),
+            Indices::List(vec![1]),
+            Indices::All,
+            None,
+        )
+        .unwrap();
+        assert_eq!(c, matrix(2, 2, &[(0, 0, 1)]));
+
+        let res = c.apply_2nd(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            Multiplication::new(),
+            &a,
+            Scalar::new(),
+            None,
+        );
+        assert!(matches!(
+            res,
+            Err(GblasError::ApiError(ApiError::EmptyObject))
+        ));
+        let res = c.select(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            TrilOp,
+            &a,
+            Scalar::<i64>::new(),
+            None,
+        );
+        assert!(res.is_err());
+    }
+}
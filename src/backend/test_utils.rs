@@ -0,0 +1,445 @@
+//! Fixtures shared by the tests of the backends.
+//!
+//! Every backend must give the same results as the reference ones, [`SparseMatrix`] and
+//! [`SimpleVec`], so [`check_matches_sparse`] and [`check_matches_simple`] run the same
+//! operations on both and compare. The tests of each
+//! backend only add the edge cases of its own storage.
+
+use std::sync::Once;
+
+use crate::{
+    algebra::{
+        Addition, AdditiveInverseOp, MinFirstSemiring, PlusMonoid, PlusTimesSemiring, Subtraction,
+        TrilOp,
+    },
+    backend::{SimpleVec, SparseMatrix},
+    context::{Context, Mode},
+    descriptor::Descriptor,
+    indices::Indices,
+    mask::{MatMask, VecMask},
+    matrix::{Matrix, MatrixExtra},
+    operations::{MatOps, VecOps},
+    types::IndexType,
+    vector::{Vector, VectorUtils},
+};
+
+/// Initializes the library in blocking mode, once for all the tests of the process.
+pub(crate) fn init() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| Context::init(Mode::Blocking).unwrap());
+}
+
+pub(crate) fn matrix<M: Matrix<Scalar = i32>>(
+    nrows: IndexType,
+    ncols: IndexType,
+    values: &[(IndexType, IndexType, i32)],
+) -> M {
+    let mut mat = M::new(nrows, ncols).unwrap();
+    for (i, j, v) in values {
+        mat.set_element(*i, *j, *v).unwrap();
+    }
+    mat
+}
+
+pub(crate) fn vector<V: Vector<Scalar = i32>>(size: IndexType, values: &[(IndexType, i32)]) -> V {
+    let mut vec = V::new(size).unwrap();
+    for (i, v) in values {
+        vec.set_element(*i, *v).unwrap();
+    }
+    vec
+}
+
+/// Entries of `mat` sorted by row then column, whatever order they are stored in.
+pub(crate) fn entries(mat: &impl MatrixExtra<Scalar = i32>) -> Vec<(IndexType, IndexType, i32)> {
+    let mut res: Vec<_> = mat.iter().map(|(i, j, v)| (i, j, *v)).collect();
+    res.sort();
+    res
+}
+
+pub(crate) fn vec_entries(vec: &impl VectorUtils<Scalar = i32>) -> Vec<(IndexType, i32)> {
+    vec.iter().map(|(i, v)| (i, *v)).collect()
+}
+
+/// _4 x 4_ inputs, `A` with an empty row and an empty column, `B` full, and a mask holding a zero.
+const A: [(IndexType, IndexType, i32); 6] = [
+    (0, 0, 1),
+    (0, 3, 2),
+    (2, 1, 3),
+    (2, 3, -1),
+    (3, 0, 4),
+    (3, 3, 5),
+];
+const B: [(IndexType, IndexType, i32); 16] = [
+    (0, 0, 2),
+    (0, 1, 2),
+    (0, 2, -1),
+    (0, 3, 1),
+    (1, 0, 1),
+    (1, 1, 1),
+    (1, 2, 3),
+    (1, 3, 4),
+    (2, 0, 7),
+    (2, 1, 0),
+    (2, 2, 1),
+    (2, 3, 2),
+    (3, 0, -2),
+    (3, 1, 5),
+    (3, 2, 6),
+    (3, 3, 1),
+];
+const C: [(IndexType, IndexType, i32); 3] = [(1, 0, 7), (2, 2, 8), (3, 3, 9)];
+const MASK: [(IndexType, IndexType, i32); 6] = [
+    (0, 0, 1),
+    (0, 2, 1),
+    (1, 1, 1),
+    (2, 0, 0),
+    (2, 1, 1),
+    (3, 3, 1),
+];
+
+const DESCS: [Descriptor; 8] = [
+    Descriptor::new(),
+    Descriptor::DESC_T0,
+    Descriptor::DESC_T1,
+    Descriptor::DESC_T0T1,
+    Descriptor::DESC_CT0,
+    Descriptor::DESC_ST1,
+    Descriptor::DESC_RT0T1,
+    Descriptor::DESC_RSC,
+];
+
+/// Runs the matrix operations with `M` as output, inputs and masks, and checks they match
+/// [`SparseMatrix`].
+pub(crate) fn check_matches_sparse<M>()
+where
+    M: MatOps + MatrixExtra<Scalar = i32> + MatMask<M> + Clone,
+{
+    let (a, b, mask): (M, M, M) = (matrix(4, 4, &A), matrix(4, 4, &B), matrix(4, 4, &MASK));
+    let (a_s, b_s, mask_s): (SparseMatrix<i32>, SparseMatrix<i32>, SparseMatrix<i32>) =
+        (matrix(4, 4, &A), matrix(4, 4, &B), matrix(4, 4, &MASK));
+
+    // runs `$op` on an `M` and on a `SparseMatrix` output, both starting from `$init`
+    macro_rules! check {
+        ($nrows:expr, $ncols:expr, $init:expr, |$c:ident, $a:ident, $b:ident, $m:ident, $none:ident| $op:expr) => {{
+            let res = {
+                let $none: Option<M> = None;
+                let mut $c: M = matrix($nrows, $ncols, $init);
+                let ($a, $b, $m) = (&a, &b, &mask);
+                $op.unwrap();
+                entries(&$c)
+            };
+            let expected = {
+                let $none: Option<SparseMatrix<i32>> = None;
+                let mut $c: SparseMatrix<i32> = matrix($nrows, $ncols, $init);
+                let ($a, $b, $m) = (&a_s, &b_s, &mask_s);
+                $op.unwrap();
+                entries(&$c)
+            };
+            assert_eq!(res, expected, "{}", stringify!($op));
+        }};
+    }
+
+    for desc in DESCS {
+        check!(4, 4, &C, |c, a, b, m, _none| c.mxm(
+            Some(m.clone()),
+            Some(Addition::new()),
+            MinFirstSemiring::new(),
+            a,
+            b,
+            Some(desc),
+        ));
+        check!(4, 4, &C, |c, a, b, m, _none| c.mxm(
+            Some(m.structure()),
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            a,
+            b,
+            Some(desc),
+        ));
+        check!(4, 4, &C, |c, a, b, m, _none| c.e_wise_add_binary_op(
+            Some(m.complement()),
+            Some(Addition::new()),
+            Subtraction::new(),
+            a,
+            b,
+            Some(desc),
+        ));
+        check!(4, 4, &C, |c, a, b, m, _none| c.e_wise_mult_binary_op(
+            Some(m.clone()),
+            Option::<Addition<i32>>::None,
+            Subtraction::new(),
+            a,
+            b,
+            Some(desc),
+        ));
+        check!(4, 4, &C, |c, a, _b, m, _none| c.transpose(
+            Some(m.clone()),
+            Some(Addition::new()),
+            a,
+            Some(desc),
+        ));
+        check!(4, 4, &C, |c, a, _b, m, _none| c.apply(
+            Some(m.clone()),
+            Option::<Addition<i32>>::None,
+            AdditiveInverseOp,
+            a,
+            Some(desc),
+        ));
+        check!(4, 4, &C, |c, a, _b, m, _none| c.select(
+            Some(m.structure()),
+            Some(Addition::new()),
+            TrilOp,
+            a,
+            0,
+            Some(desc),
+        ));
+        check!(2, 3, &[(0, 0, 1), (1, 2, 2)], |c, a, _b, _m, none| c
+            .extract(
+                none,
+                Some(Addition::new()),
+                a,
+                Indices::List(vec![3, 0]),
+                Indices::List(vec![3, 1, 3]),
+                Some(desc),
+            ));
+        check!(4, 4, &C, |c, _a, _b, m, none| c
+            .assign(
+                Some(m.clone()),
+                Option::<Addition<i32>>::None,
+                &matrix::<SparseMatrix<i32>>(2, 2, &[(0, 1, 5), (1, 1, 6)]),
+                Indices::List(vec![2, 0]),
+                Indices::Range { begin: 1, end: 2 },
+                Some(desc),
+            )
+            .and(c.subassign(
+                none,
+                Some(Addition::new()),
+                &matrix::<SparseMatrix<i32>>(2, 2, &[(0, 0, 1), (1, 0, 2), (1, 1, 3)]),
+                Indices::List(vec![3, 1]),
+                Indices::List(vec![0, 3]),
+                Some(desc),
+            )));
+        check!(4, 4, &C, |c, _a, _b, _m, _none| c
+            .assign_col(
+                Some(vector::<SimpleVec<i32>>(4, &[(0, 1), (2, 1), (3, 0)])),
+                Some(Addition::new()),
+                &vector::<SimpleVec<i32>>(3, &[(0, 4), (2, 5)]),
+                Indices::List(vec![3, 0, 1]),
+                2,
+                Some(desc),
+            )
+            .and(c.assign_row(
+                Option::<SimpleVec<i32>>::None,
+                Option::<Addition<i32>>::None,
+                &vector::<SimpleVec<i32>>(2, &[(1, 6)]),
+                3,
+                Indices::Range { begin: 2, end: 3 },
+                Some(desc),
+            )));
+        check!(8, 8, &[(5, 7, 1)], |c, a, b, _m, none| c
+            .kronecker_binary_op(
+                none,
+                Some(Addition::new()),
+                Subtraction::new(),
+                &matrix::<SparseMatrix<i32>>(2, 2, &[(0, 1, 1), (1, 1, 2)]),
+                if desc.transpose_inp0 { a } else { b },
+                Some(desc),
+            ));
+    }
+
+    // hand-computed, so the reference is checked too
+    let mut c: M = matrix(4, 4, &C);
+    c.mxm(
+        Some(mask.clone()),
+        Some(Addition::new()),
+        PlusTimesSemiring::new(),
+        &a,
+        &b,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        entries(&c),
+        [
+            (0, 0, -2),
+            (0, 2, 11),
+            (1, 0, 7),
+            (2, 1, -2),
+            (2, 2, 8),
+            (3, 3, 18)
+        ]
+    );
+
+    let mut c: M = matrix(2, 3, &[(0, 0, 1), (1, 2, 2)]);
+    c.extract(
+        Option::<M>::None,
+        Some(Addition::new()),
+        &a,
+        Indices::List(vec![3, 0]),
+        Indices::List(vec![3, 1, 3]),
+        Some(Descriptor::DESC_T0),
+    )
+    .unwrap();
+    assert_eq!(entries(&c), [(0, 0, 6), (0, 2, 5), (1, 0, 4), (1, 2, 6)]);
+
+    let mut c: M = matrix(4, 4, &[(1, 0, 7), (2, 2, 8), (3, 1, 6), (3, 3, 9)]);
+    c.subassign(
+        Some(matrix::<M>(2, 3, &[(0, 2, 1), (1, 0, 1), (1, 2, 1)])),
+        Option::<Addition<i32>>::None,
+        &matrix::<SparseMatrix<i32>>(2, 3, &[(0, 0, 1), (0, 2, 4), (1, 1, 2)]),
+        Indices::List(vec![3, 1]),
+        Indices::Range { begin: 1, end: 3 },
+        Some(Descriptor::new().with_replace()),
+    )
+    .unwrap();
+    assert_eq!(entries(&c), [(1, 0, 7), (2, 2, 8), (3, 3, 4)]);
+
+    let (mut sum, mut sum_s) = (0, 0);
+    a.reduce(
+        &mut sum,
+        Option::<Addition<i32>>::None,
+        PlusMonoid::new(),
+        None,
+    )
+    .unwrap();
+    a_s.reduce(
+        &mut sum_s,
+        Option::<Addition<i32>>::None,
+        PlusMonoid::new(),
+        None,
+    )
+    .unwrap();
+    assert_eq!(sum, sum_s);
+}
+
+/// Runs the vector operations with `V` as output, inputs and masks, and checks they match
+/// [`SimpleVec`].
+pub(crate) fn check_matches_simple<V>()
+where
+    V: VecOps + VectorUtils<Scalar = i32> + VecMask<V> + Clone,
+{
+    let values = [(0, 3), (2, 1), (3, -4)];
+    let other = [(0, 2), (1, 2), (2, 5), (3, 1)];
+    let mask_values = [(0, 1), (1, 0), (2, 1)];
+    let (u, v, mask): (V, V, V) = (
+        vector(4, &values),
+        vector(4, &other),
+        vector(4, &mask_values),
+    );
+    let (u_s, v_s, mask_s): (SimpleVec<i32>, SimpleVec<i32>, SimpleVec<i32>) = (
+        vector(4, &values),
+        vector(4, &other),
+        vector(4, &mask_values),
+    );
+    let a: SparseMatrix<i32> = matrix(4, 4, &A);
+
+    // runs `$op` on a `V` and on a `SimpleVec` output, both starting from `$init`
+    macro_rules! check {
+        ($size:expr, $init:expr, |$w:ident, $u:ident, $v:ident, $m:ident, $none:ident| $op:expr) => {{
+            let res = {
+                let $none: Option<V> = None;
+                let mut $w: V = vector($size, $init);
+                let ($u, $v, $m) = (&u, &v, &mask);
+                $op.unwrap();
+                vec_entries(&$w)
+            };
+            let expected = {
+                let $none: Option<SimpleVec<i32>> = None;
+                let mut $w: SimpleVec<i32> = vector($size, $init);
+                let ($u, $v, $m) = (&u_s, &v_s, &mask_s);
+                $op.unwrap();
+                vec_entries(&$w)
+            };
+            assert_eq!(res, expected, "{}", stringify!($op));
+        }};
+    }
+
+    for desc in DESCS {
+        check!(4, &[(1, 1), (3, 1)], |w, u, _v, m, _none| w.vxm(
+            Some(m.clone()),
+            Some(Addition::new()),
+            MinFirstSemiring::new(),
+            u,
+            &a,
+            Some(desc),
+        ));
+        check!(4, &[(1, 1), (3, 1)], |w, u, _v, m, _none| w.mxv(
+            Some(m.structure()),
+            Option::<Addition<i32>>::None,
+            PlusTimesSemiring::new(),
+            &a,
+            u,
+            Some(desc),
+        ));
+        check!(4, &[(0, 1)], |w, u, v, m, _none| w.e_wise_add_binary_op(
+            Some(m.complement()),
+            Some(Addition::new()),
+            Subtraction::new(),
+            u,
+            v,
+            Some(desc),
+        ));
+        check!(4, &[(0, 1)], |w, u, v, m, _none| w.e_wise_mult_binary_op(
+            Some(m.clone()),
+            Option::<Addition<i32>>::None,
+            Subtraction::new(),
+            u,
+            v,
+            Some(desc),
+        ));
+        check!(3, &[(2, 1)], |w, u, _v, _m, none| w.extract(
+            none,
+            Some(Addition::new()),
+            u,
+            Indices::List(vec![3, 0, 3]),
+            Some(desc),
+        ));
+        check!(3, &[(2, 1)], |w, _u, _v, _m, none| w.extract_col(
+            none,
+            Option::<Addition<i32>>::None,
+            &a,
+            Indices::List(vec![3, 2, 0]),
+            3,
+            Some(desc),
+        ));
+        check!(4, &[(0, 1), (3, 2)], |w, _u, _v, m, none| w
+            .assign(
+                Some(m.clone()),
+                Option::<Addition<i32>>::None,
+                &vector::<SimpleVec<i32>>(2, &[(1, 9)]),
+                Indices::List(vec![1, 3]),
+                Some(desc),
+            )
+            .and(w.subassign(
+                none,
+                Some(Addition::new()),
+                &vector::<SimpleVec<i32>>(2, &[(0, 5), (1, 6)]),
+                Indices::Range { begin: 1, end: 2 },
+                Some(desc),
+            )));
+        check!(4, &[(1, 1)], |w, u, _v, m, _none| w.apply(
+            Some(m.clone()),
+            Some(Addition::new()),
+            AdditiveInverseOp,
+            u,
+            Some(desc),
+        ));
+    }
+
+    let (mut sum, mut sum_s) = (0, 0);
+    u.reduce(
+        &mut sum,
+        Option::<Addition<i32>>::None,
+        PlusMonoid::new(),
+        None,
+    )
+    .unwrap();
+    u_s.reduce(
+        &mut sum_s,
+        Option::<Addition<i32>>::None,
+        PlusMonoid::new(),
+        None,
+    )
+    .unwrap();
+    assert_eq!(sum, sum_s);
+}
@@ -1,11 +1,153 @@
 use std::cmp::Ordering;
 
 use crate::{
-    algebra::{BinaryOperator, Semiring},
+    algebra::{BinaryOperator, First, Semiring},
+    backend::pending::Defer,
+    descriptor::Descriptor,
+    indices::Indices,
+    mask::{MatMask, VecMask},
+    matrix::{Compressed, Matrix, Orientation},
+    scalar::Scalar,
     types::{IndexType, NoValue},
+    vector::Vector,
     ApiError, GblasResult,
 };
 
+/// Compressed rows of a matrix: the entries of row `rows[k]` are `cols[ptr[k]..ptr[k + 1]]`,
+/// sorted, with their `values`.
+///
+/// Only the non-empty rows are kept, so the size depends on the number of entries and not on the
+/// dimensions, which may be huge for hypersparse matrices. Read through [`Rows::view`], like the
+/// storage of the compressed backends.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Rows<T> {
+    rows: Vec<IndexType>,
+    ptr: Vec<IndexType>,
+    cols: Vec<IndexType>,
+    values: Vec<T>,
+}
+
+impl<T> Rows<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            rows: Vec::new(),
+            ptr: vec![0],
+            cols: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Groups `(row, col, value)` entries sorted by row then column.
+    pub(crate) fn from_sorted(
+        entries: impl IntoIterator<Item = (IndexType, IndexType, T)>,
+    ) -> Self {
+        let mut res = Self::new();
+        for (i, j, val) in entries {
+            if res.rows.last() != Some(&i) {
+                debug_assert!(res.rows.last().is_none_or(|last| *last < i));
+                res.rows.push(i);
+                res.ptr.push(res.cols.len());
+            }
+            res.cols.push(j);
+            res.values.push(val);
+            *res.ptr.last_mut().unwrap() += 1;
+        }
+        res
+    }
+
+    /// Appends row `i`, which must come after the rows already pushed. Empty rows are skipped.
+    pub(crate) fn push(&mut self, i: IndexType, row: impl IntoIterator<Item = (IndexType, T)>) {
+        debug_assert!(self.rows.last().is_none_or(|last| *last < i));
+        for (j, val) in row {
+            self.cols.push(j);
+            self.values.push(val);
+        }
+        if self.cols.len() > *self.ptr.last().unwrap() {
+            self.rows.push(i);
+            self.ptr.push(self.cols.len());
+        }
+    }
+
+    pub(crate) fn view(&self) -> Compressed<'_, T> {
+        Compressed::new(Some(&self.rows), &self.ptr, &self.cols, &self.values)
+    }
+}
+
+impl<T, R> FromIterator<(IndexType, R)> for Rows<T>
+where
+    R: IntoIterator<Item = (IndexType, T)>,
+{
+    fn from_iter<I: IntoIterator<Item = (IndexType, R)>>(iter: I) -> Self {
+        let mut res = Self::new();
+        for (i, row) in iter {
+            res.push(i, row);
+        }
+        res
+    }
+}
+
+/// Rows of an input, borrowed from its storage when it can be read in place.
+pub(crate) enum InputRows<'a, T> {
+    Borrowed(Compressed<'a, T>),
+    Owned(Rows<T>),
+}
+
+impl<T> InputRows<'_, T> {
+    pub(crate) fn view(&self) -> Compressed<'_, T> {
+        match self {
+            Self::Borrowed(data) => *data,
+            Self::Owned(rows) => rows.view(),
+        }
+    }
+}
+
+impl<T: Clone> InputRows<'_, T> {
+    /// Rows that no longer borrow the input, copied if they were.
+    pub(crate) fn into_owned(self) -> Rows<T> {
+        match self {
+            Self::Borrowed(data) => data
+                .iter()
+                .map(|(i, row)| (i, row.iter().map(|(j, val)| (j, val.clone()))))
+                .collect(),
+            Self::Owned(rows) => rows,
+        }
+    }
+}
+
+/// Sorted `(index, value)` entries of an input vector, borrowed from its storage when it can be
+/// read in place.
+pub(crate) enum InputEntries<'a, T> {
+    Borrowed(&'a [(IndexType, T)]),
+    Owned(Vec<(IndexType, T)>),
+}
+
+impl<T> InputEntries<'_, T> {
+    pub(crate) fn view(&self) -> &[(IndexType, T)] {
+        match self {
+            Self::Borrowed(entries) => entries,
+            Self::Owned(entries) => entries,
+        }
+    }
+}
+
+impl<T: Clone> InputEntries<'_, T> {
+    /// Entries that no longer borrow the input, copied if they were.
+    pub(crate) fn into_owned(self) -> Vec<(IndexType, T)> {
+        match self {
+            Self::Borrowed(entries) => entries.to_vec(),
+            Self::Owned(entries) => entries,
+        }
+    }
+}
+
+/// `(index, value)` entries of a sorted vector, as read by the kernels below.
+#[inline]
+pub(crate) fn iter_entries<T>(
+    v: &[(IndexType, T)],
+) -> impl Iterator<Item = (IndexType, &T)> + Clone {
+    v.iter().map(|(i, val)| (*i, val))
+}
+
 pub(crate) fn check_vals(n1: IndexType, n2: IndexType) -> GblasResult<NoValue> {
     if n1 != n2 {
         return Err(ApiError::DimensionMismatch.into());
@@ -13,74 +155,1096 @@ pub(crate) fn check_vals(n1: IndexType, n2: IndexType) -> GblasResult<NoValue> {
     Ok(())
 }
 
+/// Checks that `mask`, if given, is _nrows x ncols_.
+pub(crate) fn check_mat_mask<M>(
+    mask: Option<&impl MatMask<M>>,
+    nrows: IndexType,
+    ncols: IndexType,
+) -> GblasResult<NoValue> {
+    match mask {
+        Some(mask) if mask.shape() != (nrows, ncols) => Err(ApiError::DimensionMismatch.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Checks that `mask`, if given, has size `size`.
+pub(crate) fn check_vec_mask<V>(
+    mask: Option<&impl VecMask<V>>,
+    size: IndexType,
+) -> GblasResult<NoValue> {
+    match mask {
+        Some(mask) => check_vals(mask.shape(), size),
+        None => Ok(()),
+    }
+}
+
 /// _c += a_ik * b\[..\]_
 ///
 /// **Obs:**
 ///
 /// * `c` ***must be*** sorted by index (i.e., `c[i].0 < c[i+1].0`). This is checked in debug mode.
 #[inline]
-pub(crate) fn accum_mult_vec<T: Clone>(
+pub(crate) fn accum_mult_vec<'a, T: Clone + 'a>(
     c: &mut Vec<(IndexType, T)>,
-    b: &[(IndexType, T)],
+    b: impl Iterator<Item = (IndexType, &'a T)>,
     a: &T,
     op: &impl Semiring<T, Output = T>,
 ) {
     debug_assert!(c.is_sorted_by_key(|val| val.0));
-    for (j, b_val) in b.iter() {
+    for (j, b_val) in b {
         let val = op.mult(a.clone(), b_val.clone());
 
-        let found = c.binary_search_by_key(j, |val| val.0);
+        let found = c.binary_search_by_key(&j, |val| val.0);
         match found {
             Ok(idx) => {
                 let c_val = &mut c[idx].1;
                 *c_val = op.add(c_val.clone(), val);
             }
             Err(idx) => {
-                c.insert(idx, (*j, val));
+                c.insert(idx, (j, val));
             }
         }
     }
 }
 
-pub(crate) fn ewise_or_accum_vec<T: Clone>(
+pub(crate) fn ewise_or_accum_vec<'a, 'b, T: Clone + 'a + 'b>(
     res: &mut Vec<(IndexType, T)>,
-    v1: &[(IndexType, T)],
-    v2: &[(IndexType, T)],
+    v1: impl Iterator<Item = (IndexType, &'a T)>,
+    v2: impl Iterator<Item = (IndexType, &'b T)>,
     bin_op: &impl BinaryOperator<T, Output = T>,
 ) {
     res.clear();
 
-    let mut v1_it = v1.iter().peekable();
-    let mut v2_it = v2.iter().peekable();
+    let mut v1_it = v1.peekable();
+    let mut v2_it = v2.peekable();
 
-    while v1_it.peek().is_some() || v2_it.peek().is_some() {
+    loop {
         let value = match (v1_it.peek(), v2_it.peek()) {
-            (Some((i1, v1)), Some((i2, v2))) => match i2.cmp(i1) {
+            (Some(&(i1, v1)), Some(&(i2, v2))) => match i2.cmp(&i1) {
                 Ordering::Equal => {
                     v1_it.next();
                     v2_it.next();
-                    (*i1, bin_op.op(v1.clone(), v2.clone()))
+                    (i1, bin_op.op(v1.clone(), v2.clone()))
                 }
                 Ordering::Greater => {
                     v1_it.next();
-                    (*i1, v1.clone())
+                    (i1, v1.clone())
                 }
                 Ordering::Less => {
                     v2_it.next();
-                    (*i2, v2.clone())
+                    (i2, v2.clone())
                 }
             },
-            (Some((i1, v1)), None) => {
+            (Some(&(i1, v1)), None) => {
                 v1_it.next();
-                (*i1, v1.clone())
+                (i1, v1.clone())
             }
-            (None, Some((i2, v2))) => {
+            (None, Some(&(i2, v2))) => {
                 v2_it.next();
-                (*i2, v2.clone())
+                (i2, v2.clone())
             }
-            (None, None) => unreachable!(),
+            (None, None) => break,
         };
 
         res.push(value);
     }
 }
+
+/// _res = v1 ∩ v2_
+///
+/// Only the indices present in both `v1` and `v2` are kept, combined with `bin_op`.
+pub(crate) fn ewise_and_vec<'a, 'b, T: Clone + 'a + 'b>(
+    res: &mut Vec<(IndexType, T)>,
+    v1: impl Iterator<Item = (IndexType, &'a T)>,
+    v2: impl Iterator<Item = (IndexType, &'b T)>,
+    bin_op: &impl BinaryOperator<T, Output = T>,
+) {
+    res.clear();
+
+    let mut v1_it = v1.peekable();
+    let mut v2_it = v2.peekable();
+
+    while let (Some(&(i1, v1)), Some(&(i2, v2))) = (v1_it.peek(), v2_it.peek()) {
+        match i2.cmp(&i1) {
+            Ordering::Equal => {
+                res.push((i1, bin_op.op(v1.clone(), v2.clone())));
+                v1_it.next();
+                v2_it.next();
+            }
+            Ordering::Greater => {
+                v1_it.next();
+            }
+            Ordering::Less => {
+                v2_it.next();
+            }
+        }
+    }
+}
+
+/// Dimensions of `a` as `(nrows, ncols)`, swapped when `transpose` is set.
+#[inline]
+pub(crate) fn dims<M: Matrix>(a: &M, transpose: bool) -> (IndexType, IndexType) {
+    if transpose {
+        (a.ncols(), a.nrows())
+    } else {
+        (a.nrows(), a.ncols())
+    }
+}
+
+/// Whether the rows of _op(A)_ are the columns stored by `a`, i.e. _op(A)_ is read by column
+/// without a transpose.
+#[inline]
+pub(crate) fn is_by_col<M: Matrix>(a: &M, transpose: bool) -> bool {
+    (a.orientation() == Orientation::ByCol) != transpose
+}
+
+/// Whether a counting pass over a dimension of size `dim` pays off for `nnz` entries.
+///
+/// The pass is _O(nnz + dim)_, so hypersparse matrices, whose dimension may be far larger than
+/// their number of entries (e.g. _2^40_), sort their entries instead in _O(nnz log nnz)_.
+fn is_bounded(dim: IndexType, nnz: usize) -> bool {
+    dim <= nnz.saturating_mul(8).max(1 << 16)
+}
+
+/// Groups `(major, minor, value)` entries into rows, `major` being below `dim`.
+///
+/// Entries sorted by minor index, e.g. when transposing, are bucketed by major index in one
+/// stable counting pass, so the rows come out sorted in _O(nnz + dim)_. Other entries, or a
+/// `dim` that isn't bounded (see [`is_bounded`]), are sorted instead.
+fn bucket_rows<T>(mut entries: Vec<(IndexType, IndexType, T)>, dim: IndexType) -> Rows<T> {
+    if entries.is_sorted_by_key(|val| (val.0, val.1)) {
+        return Rows::from_sorted(entries);
+    }
+    if !is_bounded(dim, entries.len()) || !entries.is_sorted_by_key(|val| val.1) {
+        entries.sort_by_key(|val| (val.0, val.1));
+        return Rows::from_sorted(entries);
+    }
+
+    let mut ptr = vec![0; dim + 1];
+    for (i, _, _) in entries.iter() {
+        ptr[i + 1] += 1;
+    }
+    for i in 0..dim {
+        ptr[i + 1] += ptr[i];
+    }
+    let mut next = ptr.clone();
+    let mut cols = vec![0; entries.len()];
+    let mut values: Vec<Option<T>> = entries.iter().map(|_| None).collect();
+    for (i, j, val) in entries {
+        cols[next[i]] = j;
+        values[next[i]] = Some(val);
+        next[i] += 1;
+    }
+
+    let rows: Vec<IndexType> = (0..dim).filter(|i| ptr[*i] < ptr[i + 1]).collect();
+    Rows {
+        ptr: std::iter::once(0)
+            .chain(rows.iter().map(|i| ptr[i + 1]))
+            .collect(),
+        rows,
+        cols,
+        values: values.into_iter().flatten().collect(),
+    }
+}
+
+/// Transposes `rows`, whose minor indices are below `dim`.
+///
+/// Counts the entries of each minor index, then scatters the rows in order, so the transposed
+/// rows come out sorted in _O(nnz + dim)_. A `dim` that isn't bounded (see [`is_bounded`]) goes
+/// through [`bucket_rows`], which sorts instead.
+pub(crate) fn transpose_rows<T: Clone>(rows: Compressed<'_, T>, dim: IndexType) -> Rows<T> {
+    let nnz = rows.minors.len();
+    if !is_bounded(dim, nnz) {
+        let entries = rows
+            .iter()
+            .flat_map(|(i, row)| row.iter().map(move |(j, val)| (j, i, val.clone())))
+            .collect();
+        return bucket_rows(entries, dim);
+    }
+
+    let mut ptr = vec![0; dim + 1];
+    for j in rows.minors {
+        ptr[j + 1] += 1;
+    }
+    for j in 0..dim {
+        ptr[j + 1] += ptr[j];
+    }
+    // position in `rows` of each transposed entry
+    let mut next = ptr.clone();
+    let mut src = vec![0; nnz];
+    let mut cols = vec![0; nnz];
+    for k in 0..rows.ptr.len() - 1 {
+        let i = rows.majors.map_or(k, |majors| majors[k]);
+        for p in rows.ptr[k]..rows.ptr[k + 1] {
+            let j = rows.minors[p];
+            src[next[j]] = p;
+            cols[next[j]] = i;
+            next[j] += 1;
+        }
+    }
+
+    let majors: Vec<IndexType> = (0..dim).filter(|j| ptr[*j] < ptr[j + 1]).collect();
+    Rows {
+        ptr: std::iter::once(0)
+            .chain(majors.iter().map(|j| ptr[j + 1]))
+            .collect(),
+        rows: majors,
+        cols,
+        values: src.into_iter().map(|p| rows.values[p].clone()).collect(),
+    }
+}
+
+/// Rows of `a`, or of _A'_ when `transpose` is set.
+///
+/// Matrices exposing their [`Matrix::compressed`] storage are read in place when it holds the
+/// requested rows, and transposed from it otherwise. The others are copied through
+/// [`Matrix::extract_tuples`].
+pub(crate) fn to_rows<M: Matrix>(a: &M, transpose: bool) -> GblasResult<InputRows<'_, M::Scalar>>
+where
+    M::Scalar: Clone,
+{
+    let (nrows, _) = dims(a, transpose);
+    if let Some(data) = a.compressed() {
+        return Ok(if is_by_col(a, transpose) {
+            InputRows::Owned(transpose_rows(data, nrows))
+        } else {
+            InputRows::Borrowed(data)
+        });
+    }
+
+    let (rows, cols, values) = a.dup()?.extract_tuples()?;
+    let (rows, cols) = if transpose {
+        (cols, rows)
+    } else {
+        (rows, cols)
+    };
+    let entries = rows
+        .into_iter()
+        .zip(cols)
+        .zip(values)
+        .map(|((i, j), val)| (i, j, val))
+        .collect();
+
+    Ok(InputRows::Owned(bucket_rows(entries, nrows)))
+}
+
+/// Collects the entries of `u` sorted by index.
+pub(crate) fn to_entries<V: Vector>(u: &V) -> GblasResult<InputEntries<'_, V::Scalar>> {
+    if let Some(entries) = u.entries() {
+        return Ok(InputEntries::Borrowed(entries));
+    }
+
+    let (indices, values) = u.dup()?.extract_tuples()?;
+
+    let mut res: Vec<(IndexType, V::Scalar)> = indices.into_iter().zip(values).collect();
+    if !res.is_sorted_by_key(|val| val.0) {
+        res.sort_by_key(|val| val.0);
+    }
+
+    Ok(InputEntries::Owned(res))
+}
+
+/// Semiring with the operands of `mult` swapped, used to compute _(A B)' = B' A'_.
+pub(crate) struct Flipped<'a, S>(pub(crate) &'a S);
+
+impl<T, S> Semiring<T> for Flipped<'_, S>
+where
+    S: Semiring<T, Output = T>,
+{
+    type Output = T;
+
+    fn add(&self, lhs: T, rhs: T) -> T {
+        self.0.add(lhs, rhs)
+    }
+
+    fn mult(&self, lhs: T, rhs: T) -> T {
+        self.0.mult(rhs, lhs)
+    }
+
+    fn zero(&self) -> T {
+        self.0.zero()
+    }
+}
+
+/// _a ⊕.⊗ b_
+///
+/// Returns `None` when `a` and `b` share no index, i.e. the result has no stored value.
+///
+/// **Obs:**
+///
+/// * `a` and `b` ***must be*** sorted by index. This is checked in debug mode.
+pub(crate) fn dot_vec<'a, 'b, T: Clone + 'a + 'b>(
+    a: impl Iterator<Item = (IndexType, &'a T)> + Clone,
+    b: impl Iterator<Item = (IndexType, &'b T)> + Clone,
+    op: &impl Semiring<T, Output = T>,
+) -> Option<T> {
+    debug_assert!(a.clone().is_sorted_by_key(|val| val.0));
+    debug_assert!(b.clone().is_sorted_by_key(|val| val.0));
+
+    let mut res: Option<T> = None;
+    let mut a_it = a.peekable();
+    let mut b_it = b.peekable();
+
+    while let (Some(&(i1, v1)), Some(&(i2, v2))) = (a_it.peek(), b_it.peek()) {
+        match i1.cmp(&i2) {
+            Ordering::Equal => {
+                let val = op.mult(v1.clone(), v2.clone());
+                res = Some(match res {
+                    Some(acc) => op.add(acc, val),
+                    None => val,
+                });
+                a_it.next();
+                b_it.next();
+            }
+            Ordering::Less => {
+                a_it.next();
+            }
+            Ordering::Greater => {
+                b_it.next();
+            }
+        }
+    }
+
+    res
+}
+
+/// _c<mask> = c ⊙ t_
+///
+/// Merges `t` into `c`, combining with `accum` where both hold an entry. Indices rejected by `mask`
+/// keep the old value of `c`, unless `replace` is set, in which case they are cleared.
+///
+/// **Obs:**
+///
+/// * `c` and `t` ***must be*** sorted by index. This is checked in debug mode.
+pub(crate) fn masked_write_vec<'a, T: Clone + 'a>(
+    c: &mut Vec<(IndexType, T)>,
+    t: impl Iterator<Item = (IndexType, &'a T)> + Clone,
+    mask: impl Fn(IndexType) -> bool,
+    accum: Option<&impl BinaryOperator<T, Output = T>>,
+    replace: bool,
+) {
+    debug_assert!(c.is_sorted_by_key(|val| val.0));
+    debug_assert!(t.clone().is_sorted_by_key(|val| val.0));
+
+    if c.is_empty() {
+        // nothing to accumulate into, keep or replace
+        c.extend(
+            t.filter(|(idx, _)| mask(*idx))
+                .map(|(idx, val)| (idx, val.clone())),
+        );
+        return;
+    }
+
+    let z = match accum {
+        Some(accum) => {
+            let mut z = Vec::with_capacity(c.len());
+            ewise_or_accum_vec(&mut z, iter_entries(c), t, accum);
+            z
+        }
+        None => t.map(|(i, val)| (i, val.clone())).collect(),
+    };
+
+    let old = core::mem::take(c);
+    let mut c_it = old.into_iter().peekable();
+    let mut z_it = z.into_iter().peekable();
+
+    loop {
+        let (idx, c_val, z_val) = match (c_it.peek(), z_it.peek()) {
+            (Some((i1, _)), Some((i2, _))) => match i1.cmp(i2) {
+                Ordering::Equal => (*i1, c_it.next(), z_it.next()),
+                Ordering::Less => (*i1, c_it.next(), None),
+                Ordering::Greater => (*i2, None, z_it.next()),
+            },
+            (Some((i1, _)), None) => (*i1, c_it.next(), None),
+            (None, Some((i2, _))) => (*i2, None, z_it.next()),
+            (None, None) => break,
+        };
+
+        if mask(idx) {
+            if let Some(val) = z_val {
+                c.push(val);
+            }
+        } else if !replace {
+            if let Some(val) = c_val {
+                c.push(val);
+            }
+        }
+    }
+}
+
+/// Predicate telling whether position _(i, j)_ may be written, following the mask fields of `desc`.
+pub(crate) fn mat_mask_fn<'a, M: Matrix + 'a>(
+    mask: Option<&'a impl MatMask<M>>,
+    desc: &Descriptor,
+) -> impl Fn(IndexType, IndexType) -> bool + 'a {
+    let structure = mask.map(|mask| mask.structure());
+    let (use_structure, complement) = (desc.mask_structure, desc.mask_complement);
+
+    move |i, j| match (mask, &structure) {
+        (Some(_), Some(structure)) if use_structure => structure[(i, j)] != complement,
+        (Some(mask), _) => mask[(i, j)] != complement,
+        _ => !complement,
+    }
+}
+
+/// Predicate telling whether position _i_ may be written, following the mask fields of `desc`.
+pub(crate) fn vec_mask_fn<'a, V: Vector + 'a>(
+    mask: Option<&'a impl VecMask<V>>,
+    desc: &Descriptor,
+) -> impl Fn(IndexType) -> bool + 'a {
+    let structure = mask.map(|mask| mask.structure());
+    let (use_structure, complement) = (desc.mask_structure, desc.mask_complement);
+
+    move |i| match (mask, &structure) {
+        (Some(_), Some(structure)) if use_structure => structure[i] != complement,
+        (Some(mask), _) => mask[i] != complement,
+        _ => !complement,
+    }
+}
+
+/// Copy of what a mask allows, taken when an operation is queued in nonblocking mode so the mask
+/// object can change or be dropped before the operation runs.
+pub(crate) struct MaskCopy<P> {
+    /// Sorted stored positions of the mask that don't follow `outside`
+    flipped: Vec<P>,
+    /// Whether the positions without a stored entry may be written
+    outside: bool,
+}
+
+impl<P: Ord> MaskCopy<P> {
+    pub(crate) fn allows(&self, pos: P) -> bool {
+        self.flipped.binary_search(&pos).is_ok() != self.outside
+    }
+}
+
+impl MaskCopy<(IndexType, IndexType)> {
+    /// Copies [`mat_mask_fn`] for `mask` and `desc`.
+    pub(crate) fn matrix<M: Matrix>(
+        mask: Option<&impl MatMask<M>>,
+        desc: &Descriptor,
+    ) -> GblasResult<Self> {
+        let allows = mat_mask_fn(mask, desc);
+        let Some(mask) = mask else {
+            return Ok(Self {
+                flipped: Vec::new(),
+                outside: !desc.mask_complement,
+            });
+        };
+        // every view gives the complement flag of its structure where nothing is stored
+        let structure = mask.structure();
+        let outside = structure.complement != desc.mask_complement;
+
+        let (rows, cols, _) = structure.mat.dup()?.extract_tuples()?;
+        let mut flipped: Vec<_> = rows
+            .into_iter()
+            .zip(cols)
+            .filter(|(i, j)| allows(*i, *j) != outside)
+            .collect();
+        if !flipped.is_sorted() {
+            flipped.sort_unstable();
+        }
+        Ok(Self { flipped, outside })
+    }
+}
+
+impl MaskCopy<IndexType> {
+    /// Copies [`vec_mask_fn`] for `mask` and `desc`.
+    pub(crate) fn vector<V: Vector>(
+        mask: Option<&impl VecMask<V>>,
+        desc: &Descriptor,
+    ) -> GblasResult<Self> {
+        let allows = vec_mask_fn(mask, desc);
+        let Some(mask) = mask else {
+            return Ok(Self {
+                flipped: Vec::new(),
+                outside: !desc.mask_complement,
+            });
+        };
+        let structure = mask.structure();
+        let outside = structure.complement != desc.mask_complement;
+
+        let (indices, _) = structure.vector.dup()?.extract_tuples()?;
+        let mut flipped: Vec<_> = indices
+            .into_iter()
+            .filter(|i| allows(*i) != outside)
+            .collect();
+        if !flipped.is_sorted() {
+            flipped.sort_unstable();
+        }
+        Ok(Self { flipped, outside })
+    }
+}
+
+/// Runs `f` on `c` with the inputs and the mask predicate (see [`mat_mask_fn`]), or queues it on
+/// `c` with copies of them when `c` defers its operations.
+///
+/// `rows` are the input matrices and `entries` the input vectors, `f` gets views of both.
+#[allow(clippy::type_complexity)]
+pub(crate) fn run_mat_op<C, M, T, const N: usize, const K: usize>(
+    c: &mut C,
+    mask: Option<&impl MatMask<M>>,
+    desc: &Descriptor,
+    rows: [InputRows<'_, T>; N],
+    entries: [InputEntries<'_, T>; K],
+    f: impl for<'a> FnOnce(
+            &mut C,
+            [Compressed<'a, T>; N],
+            [&'a [(IndexType, T)]; K],
+            &dyn Fn(IndexType, IndexType) -> bool,
+        ) + Send
+        + 'static,
+) -> GblasResult<NoValue>
+where
+    C: Defer,
+    M: Matrix,
+    T: Clone + Send + 'static,
+{
+    if c.defers() {
+        let mask = MaskCopy::matrix(mask, desc)?;
+        let rows = rows.map(InputRows::into_owned);
+        let entries = entries.map(InputEntries::into_owned);
+        c.defer(Box::new(move |c| {
+            let mask = |i, j| mask.allows((i, j));
+            f(
+                c,
+                rows.each_ref().map(Rows::view),
+                entries.each_ref().map(Vec::as_slice),
+                &mask,
+            )
+        }));
+    } else {
+        f(
+            c,
+            rows.each_ref().map(InputRows::view),
+            entries.each_ref().map(InputEntries::view),
+            &mat_mask_fn(mask, desc),
+        );
+    }
+    Ok(())
+}
+
+/// Same as [`run_mat_op`] with a vector mask (see [`vec_mask_fn`]).
+#[allow(clippy::type_complexity)]
+pub(crate) fn run_vec_op<C, V, T, const N: usize, const K: usize>(
+    c: &mut C,
+    mask: Option<&impl VecMask<V>>,
+    desc: &Descriptor,
+    rows: [InputRows<'_, T>; N],
+    entries: [InputEntries<'_, T>; K],
+    f: impl for<'a> FnOnce(
+            &mut C,
+            [Compressed<'a, T>; N],
+            [&'a [(IndexType, T)]; K],
+            &dyn Fn(IndexType) -> bool,
+        ) + Send
+        + 'static,
+) -> GblasResult<NoValue>
+where
+    C: Defer,
+    V: Vector,
+    T: Clone + Send + 'static,
+{
+    if c.defers() {
+        let mask = MaskCopy::vector(mask, desc)?;
+        let rows = rows.map(InputRows::into_owned);
+        let entries = entries.map(InputEntries::into_owned);
+        c.defer(Box::new(move |c| {
+            let mask = |i| mask.allows(i);
+            f(
+                c,
+                rows.each_ref().map(Rows::view),
+                entries.each_ref().map(Vec::as_slice),
+                &mask,
+            )
+        }));
+    } else {
+        f(
+            c,
+            rows.each_ref().map(InputRows::view),
+            entries.each_ref().map(InputEntries::view),
+            &vec_mask_fn(mask, desc),
+        );
+    }
+    Ok(())
+}
+
+/// [`Indices`] selection stepping by a constant, `begin + k * inc` for _k < len_ (`begin - k * inc`
+/// when `backwards`). Positions and indices are mapped both ways with arithmetic, so nothing is
+/// listed even for a huge selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Progression {
+    begin: IndexType,
+    inc: IndexType,
+    len: IndexType,
+    backwards: bool,
+}
+
+impl Progression {
+    /// `None` for [`Indices::List`], which has to be listed.
+    fn new(indices: &Indices, dim: IndexType) -> GblasResult<Option<Self>> {
+        indices.check(dim)?;
+        let len = indices.len(dim)?;
+        let (begin, inc, backwards) = match *indices {
+            Indices::List(_) => return Ok(None),
+            Indices::All => (0, 1, false),
+            Indices::Range { begin, .. } => (begin, 1, false),
+            Indices::Stride { begin, inc, .. } => (begin, inc, false),
+            Indices::Backwards { begin, inc, .. } => (begin, inc, true),
+        };
+        Ok(Some(Self {
+            begin,
+            inc,
+            len,
+            backwards,
+        }))
+    }
+
+    /// _I\[k\]_, for _k < len_.
+    fn index(&self, k: IndexType) -> IndexType {
+        if self.backwards {
+            self.begin - k * self.inc
+        } else {
+            self.begin + k * self.inc
+        }
+    }
+
+    /// Position _k_ selecting `idx`, if any.
+    fn position(&self, idx: IndexType) -> Option<IndexType> {
+        let offset = if self.backwards {
+            self.begin.checked_sub(idx)?
+        } else {
+            idx.checked_sub(self.begin)?
+        };
+        (offset % self.inc == 0 && offset / self.inc < self.len).then_some(offset / self.inc)
+    }
+}
+
+/// Maps the positions of an [`Indices`] selection (_k_) to the indices they select (_I\[k\]_).
+///
+/// When an index is selected more than once, only its last occurrence is kept (last one wins).
+pub(crate) enum IndexMap {
+    /// Every selection but a list, [`Indices::All`] being the identity
+    Progression(Progression),
+    List {
+        /// `(I[k], k)` pairs sorted by `I[k]`, one per distinct index
+        by_dest: Vec<(IndexType, IndexType)>,
+        /// `I[k]` for each position `k`, `None` when shadowed by a later duplicate
+        by_src: Vec<Option<IndexType>>,
+    },
+}
+
+impl IndexMap {
+    pub(crate) fn new(indices: &Indices, dim: IndexType) -> GblasResult<Self> {
+        if let Some(progression) = Progression::new(indices, dim)? {
+            return Ok(Self::Progression(progression));
+        }
+        let list = indices.resolve(dim)?;
+
+        let mut by_dest: Vec<(IndexType, IndexType)> =
+            list.iter().enumerate().map(|(k, idx)| (*idx, k)).collect();
+        if !by_dest.is_sorted() {
+            by_dest.sort_unstable();
+        }
+        // keep the last `k` of each run of equal indices
+        let mut by_src = vec![None; list.len()];
+        let mut dedup: Vec<(IndexType, IndexType)> = Vec::with_capacity(by_dest.len());
+        for (idx, k) in by_dest {
+            match dedup.last_mut() {
+                Some(last) if last.0 == idx => last.1 = k,
+                _ => dedup.push((idx, k)),
+            }
+        }
+        for (idx, k) in dedup.iter() {
+            by_src[*k] = Some(*idx);
+        }
+
+        Ok(Self::List {
+            by_dest: dedup,
+            by_src,
+        })
+    }
+
+    /// Number of positions, i.e. _|I|_ including duplicates.
+    pub(crate) fn len(&self) -> IndexType {
+        match self {
+            Self::Progression(progression) => progression.len,
+            Self::List { by_src, .. } => by_src.len(),
+        }
+    }
+
+    /// Position selecting `dest`, if any.
+    pub(crate) fn src(&self, dest: IndexType) -> Option<IndexType> {
+        match self {
+            Self::Progression(progression) => progression.position(dest),
+            Self::List { by_dest, .. } => by_dest
+                .binary_search_by_key(&dest, |val| val.0)
+                .ok()
+                .map(|idx| by_dest[idx].1),
+        }
+    }
+
+    /// Index selected by position `src`, `None` if shadowed by a duplicate.
+    pub(crate) fn dest(&self, src: IndexType) -> Option<IndexType> {
+        match self {
+            Self::Progression(progression) => {
+                (src < progression.len).then(|| progression.index(src))
+            }
+            Self::List { by_src, .. } => by_src.get(src).copied().flatten(),
+        }
+    }
+}
+
+/// Positions _k_ of an [`Indices`] selection, looked up by the index _I\[k\]_ they select, as
+/// read by [`extract_vec`]. Duplicates are kept.
+pub(crate) enum Positions {
+    /// Every selection but a list, [`Indices::All`] being the identity
+    Progression(Progression),
+    /// `(I[k], k)` pairs sorted by `I[k]`
+    List(Vec<(IndexType, IndexType)>),
+}
+
+impl Positions {
+    pub(crate) fn new(indices: &Indices, dim: IndexType) -> GblasResult<Self> {
+        if let Some(progression) = Progression::new(indices, dim)? {
+            return Ok(Self::Progression(progression));
+        }
+        let mut positions: Vec<(IndexType, IndexType)> = indices
+            .resolve(dim)?
+            .into_iter()
+            .enumerate()
+            .map(|(k, idx)| (idx, k))
+            .collect();
+        if !positions.is_sorted() {
+            positions.sort_unstable();
+        }
+        Ok(Self::List(positions))
+    }
+
+    /// Entries of `u` (sorted by index) at the selected indices, indexed by their positions.
+    ///
+    /// Only the entries of `u` are visited for a [`Progression`], however large it is.
+    pub(crate) fn select<V: Clone>(
+        &self,
+        u: impl Iterator<Item = (IndexType, V)>,
+    ) -> Vec<(IndexType, V)> {
+        let mut res: Vec<(IndexType, V)> = match self {
+            Self::Progression(progression) => u
+                .filter_map(|(i, val)| progression.position(i).map(|k| (k, val)))
+                .collect(),
+            Self::List(positions) => {
+                debug_assert!(positions.is_sorted_by_key(|val| val.0));
+                let mut res = Vec::new();
+                let mut u_it = u.peekable();
+                let mut p_it = positions.iter().peekable();
+
+                while let (Some((i1, val)), Some((i2, k))) = (u_it.peek(), p_it.peek()) {
+                    match i1.cmp(i2) {
+                        Ordering::Equal => {
+                            res.push((*k, val.clone()));
+                            // the same index may be selected again
+                            p_it.next();
+                        }
+                        Ordering::Less => {
+                            u_it.next();
+                        }
+                        Ordering::Greater => {
+                            p_it.next();
+                        }
+                    }
+                }
+                res
+            }
+        };
+
+        if !res.is_sorted_by_key(|val| val.0) {
+            res.sort_by_key(|val| val.0);
+        }
+        res
+    }
+}
+
+/// _t = u(I)_
+///
+/// The result is indexed by the positions _k_ of `positions`.
+pub(crate) fn extract_vec<'a, T: Clone + 'a>(
+    u: impl Iterator<Item = (IndexType, &'a T)> + Clone,
+    positions: &Positions,
+) -> Vec<(IndexType, T)> {
+    debug_assert!(u.clone().is_sorted_by_key(|val| val.0));
+    positions
+        .select(u)
+        .into_iter()
+        .map(|(k, val)| (k, val.clone()))
+        .collect()
+}
+
+/// _w<mask>(I) = w(I) ⊙ u_ (assign) or _w(I)<mask> = w(I) ⊙ u_ (subassign)
+///
+/// `u` is indexed by the positions of `map`. With `sub` set, `mask` is evaluated on those positions
+/// and only _w(I)_ is written, otherwise `mask` is evaluated on the indices of `w` and replace
+/// applies to the whole of `w`.
+pub(crate) fn assign_vec<'a, T: Clone + 'a>(
+    w: &mut Vec<(IndexType, T)>,
+    u: impl Iterator<Item = (IndexType, &'a T)>,
+    map: &IndexMap,
+    mask: impl Fn(IndexType) -> bool,
+    accum: Option<&impl BinaryOperator<T, Output = T>>,
+    replace: bool,
+    sub: bool,
+) {
+    debug_assert!(w.is_sorted_by_key(|val| val.0));
+
+    // move `u` to the indices of `w`, dropping shadowed duplicates
+    let mut u_dest: Vec<(IndexType, T)> = u
+        .filter_map(|(k, val)| map.dest(k).map(|idx| (idx, val.clone())))
+        .collect();
+    if !u_dest.is_sorted_by_key(|val| val.0) {
+        u_dest.sort_by_key(|val| val.0);
+    }
+
+    let (mut region, outside): (Vec<_>, Vec<_>) = core::mem::take(w)
+        .into_iter()
+        .partition(|(idx, _)| map.src(*idx).is_some());
+
+    if sub {
+        let sub_mask = |idx| map.src(idx).is_some_and(&mask);
+        masked_write_vec(&mut region, iter_entries(&u_dest), sub_mask, accum, replace);
+
+        *w = merge_disjoint_vec(outside, region);
+    } else {
+        let z_region = match accum {
+            Some(accum) => {
+                let mut z = Vec::with_capacity(region.len() + u_dest.len());
+                ewise_or_accum_vec(&mut z, iter_entries(&region), iter_entries(&u_dest), accum);
+                z
+            }
+            None => u_dest,
+        };
+        let z = merge_disjoint_vec(outside.clone(), z_region);
+
+        *w = merge_disjoint_vec(outside, region);
+        masked_write_vec(
+            w,
+            iter_entries(&z),
+            mask,
+            Option::<&First<T>>::None,
+            replace,
+        );
+    }
+}
+
+/// Rows of a _nrows x ncols_ matrix with `value` stored at every position (scalar broadcast).
+///
+/// An empty scalar broadcasts to an empty matrix.
+pub(crate) fn full_rows<T: Clone>(nrows: IndexType, ncols: IndexType, value: Scalar<T>) -> Rows<T> {
+    match value.into_value() {
+        Some(value) => (0..nrows)
+            .map(|i| (i, (0..ncols).map(|j| (j, value.clone()))))
+            .collect(),
+        None => Rows::new(),
+    }
+}
+
+/// Entries of a vector of `size` with `value` stored at every index (scalar broadcast).
+///
+/// An empty scalar broadcasts to an empty vector.
+pub(crate) fn full_entries<T: Clone>(size: IndexType, value: Scalar<T>) -> Vec<(IndexType, T)> {
+    match value.into_value() {
+        Some(value) => (0..size).map(|i| (i, value.clone())).collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Value of a scalar that must not be empty.
+pub(crate) fn scalar_value<T>(value: Scalar<T>) -> GblasResult<T> {
+    value
+        .into_value()
+        .ok_or_else(|| ApiError::EmptyObject.into())
+}
+
+/// _s = accum(s, t)_, an empty _t_ leaves _s_ untouched when accumulating.
+pub(crate) fn accum_scalar<T>(
+    s: &mut Scalar<T>,
+    t: Option<T>,
+    accum: Option<&impl BinaryOperator<T, Output = T>>,
+) {
+    *s = match (core::mem::take(s).into_value(), t, accum) {
+        (Some(s_val), Some(t_val), Some(accum)) => Scalar::from(accum.op(s_val, t_val)),
+        (s_val, None, Some(_)) => Scalar::from(s_val),
+        (_, t, _) => Scalar::from(t),
+    };
+}
+
+/// Merges two sorted vectors that don't share any index.
+fn merge_disjoint_vec<T>(
+    mut v1: Vec<(IndexType, T)>,
+    v2: Vec<(IndexType, T)>,
+) -> Vec<(IndexType, T)> {
+    v1.extend(v2);
+    v1.sort_by_key(|val| val.0);
+    v1
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::{test_utils::init, CscMatrix, CsrMatrix, SparseMatrix};
+
+    use super::*;
+
+    #[test]
+    fn test_transpose_rows() {
+        // [1 0 2]
+        // [0 3 0]
+        // [4 0 5]
+        let rows = Rows::from_sorted([(0, 0, 1), (0, 2, 2), (1, 1, 3), (2, 0, 4), (2, 2, 5)]);
+        let expected = Rows::from_sorted([(0, 0, 1), (0, 2, 4), (1, 1, 3), (2, 0, 2), (2, 2, 5)]);
+        assert_eq!(transpose_rows(rows.view(), 3), expected);
+        assert_eq!(transpose_rows(expected.view(), 3), rows);
+
+        // entries that aren't sorted by minor index are sorted within their bucket
+        let rows = bucket_rows(vec![(1, 2, 'a'), (0, 1, 'b'), (1, 0, 'c')], 2);
+        assert_eq!(
+            rows,
+            Rows::from_sorted([(0, 1, 'b'), (1, 0, 'c'), (1, 2, 'a')])
+        );
+
+        // a hypersparse dimension is sorted instead of bucketed
+        let n = 1 << 40;
+        let rows = Rows::from_sorted([(0, n - 1, 1), (n - 1, 0, 2), (n - 1, n - 1, 3)]);
+        assert_eq!(
+            transpose_rows(rows.view(), n),
+            Rows::from_sorted([(0, n - 1, 2), (n - 1, 0, 1), (n - 1, n - 1, 3)])
+        );
+    }
+
+    #[test]
+    fn test_index_map() {
+        let map = IndexMap::new(&Indices::List(vec![4, 1, 4]), 5).unwrap();
+        assert_eq!(map.len(), 3);
+        assert_eq!(
+            (map.src(4), map.src(1), map.src(0)),
+            (Some(2), Some(1), None)
+        );
+        assert_eq!((map.dest(0), map.dest(2)), (None, Some(4)));
+        assert!(IndexMap::new(&Indices::List(vec![5]), 5).is_err());
+
+        // `All` over a 2^40 dimension is the identity and lists nothing
+        let n = 1 << 40;
+        let map = IndexMap::new(&Indices::All, n).unwrap();
+        assert!(matches!(map, IndexMap::Progression(_)));
+        assert_eq!(
+            (map.len(), map.src(n - 1), map.dest(n)),
+            (n, Some(n - 1), None)
+        );
+
+        // nor do ranges and strides, mapped both ways with arithmetic
+        let map = IndexMap::new(
+            &Indices::Range {
+                begin: 2,
+                end: n - 1,
+            },
+            n,
+        )
+        .unwrap();
+        assert_eq!(
+            (map.len(), map.src(1), map.src(n - 1), map.dest(n - 3)),
+            (n - 2, None, Some(n - 3), Some(n - 1))
+        );
+        let map = IndexMap::new(
+            &Indices::Stride {
+                begin: 1,
+                end: n - 1,
+                inc: 3,
+            },
+            n,
+        )
+        .unwrap();
+        assert_eq!(
+            (map.src(7), map.src(8), map.dest(2)),
+            (Some(2), None, Some(7))
+        );
+        let map = IndexMap::new(
+            &Indices::Backwards {
+                begin: n - 1,
+                end: 0,
+                inc: 2,
+            },
+            n,
+        )
+        .unwrap();
+        assert_eq!(
+            (map.len(), map.src(n - 5), map.src(n), map.dest(1)),
+            (n / 2, Some(2), None, Some(n - 3))
+        );
+        assert!(IndexMap::new(&Indices::Range { begin: 0, end: n }, n).is_err());
+
+        let u = [(3, 'a'), (n - 1, 'b')];
+        let positions = Positions::new(&Indices::All, n).unwrap();
+        assert_eq!(extract_vec(iter_entries(&u), &positions), u.to_vec());
+        let positions = Positions::new(&Indices::List(vec![n - 1, 0, n - 1]), n).unwrap();
+        assert_eq!(
+            extract_vec(iter_entries(&u), &positions),
+            vec![(0, 'b'), (2, 'b')]
+        );
+        let positions = Positions::new(
+            &Indices::Backwards {
+                begin: n - 1,
+                end: 1,
+                inc: 1,
+            },
+            n,
+        );
+        assert_eq!(
+            extract_vec(iter_entries(&u), &positions.unwrap()),
+            vec![(0, 'b'), (n - 4, 'a')]
+        );
+    }
+
+    #[test]
+    fn test_to_rows() {
+        init();
+        let values = [(0, 2, 1), (2, 0, 2), (2, 1, 3)];
+        let rows = Rows::from_sorted(values);
+        let cols = Rows::from_sorted([(0, 2, 2), (1, 2, 3), (2, 0, 1)]);
+
+        let mut csr = CsrMatrix::new(3, 3).unwrap();
+        let mut csc = CscMatrix::new(3, 3).unwrap();
+        let mut sparse = SparseMatrix::new(3, 3).unwrap();
+        for (i, j, val) in values {
+            csr.set_element(i, j, val).unwrap();
+            csc.set_element(i, j, val).unwrap();
+            sparse.set_element(i, j, val).unwrap();
+        }
+
+        // compressed storage is read in place when it holds the requested rows
+        let data = to_rows(&csr, false).unwrap();
+        assert!(matches!(data, InputRows::Borrowed(_)));
+        assert!(data.view().get(1).is_empty());
+        assert_eq!(data.view().indices().collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(Rows::from_iter(data.view().iter().map(cloned)), rows);
+        let data = to_rows(&csc, true).unwrap();
+        assert!(matches!(data, InputRows::Borrowed(_)));
+        assert_eq!(Rows::from_iter(data.view().iter().map(cloned)), cols);
+
+        for data in [to_rows(&csr, true), to_rows(&csc, false)] {
+            let data = data.unwrap();
+            assert!(matches!(data, InputRows::Owned(_)));
+        }
+        assert_eq!(
+            Rows::from_iter(to_rows(&csr, true).unwrap().view().iter().map(cloned)),
+            cols
+        );
+        assert_eq!(
+            Rows::from_iter(to_rows(&csc, false).unwrap().view().iter().map(cloned)),
+            rows
+        );
+        assert!(matches!(to_rows(&sparse, false).unwrap(), InputRows::Owned(r) if r == rows));
+        assert!(matches!(to_rows(&sparse, true).unwrap(), InputRows::Owned(r) if r == cols));
+    }
+
+    fn cloned<T: Clone>(
+        (i, row): (IndexType, crate::matrix::Entries<'_, T>),
+    ) -> (IndexType, Vec<(IndexType, T)>) {
+        (i, row.iter().map(|(j, val)| (j, val.clone())).collect())
+    }
+}
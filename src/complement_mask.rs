@@ -2,13 +2,42 @@ use std::ops::Index;
 
 use crate::{matrix::Matrix, types::IndexType, vector::Vector};
 
+/// Complement of a mask.
+///
+/// A value complement selects every position where the mask value is `false` (or missing),
+/// while a structural complement selects every position without a stored entry. Complementing
+/// it again gives back a view of the original mask, see [`MatrixComplementMask::is_complement`].
 pub struct MatrixComplementMask<'a, M> {
     pub(crate) mat: &'a M,
+    pub(crate) structure: bool,
+    pub(crate) complement: bool,
 }
 
 impl<'a, M> MatrixComplementMask<'a, M> {
     pub fn new(mat: &'a M) -> Self {
-        Self { mat }
+        Self {
+            mat,
+            structure: false,
+            complement: true,
+        }
+    }
+
+    pub fn new_structure(mat: &'a M) -> Self {
+        Self {
+            mat,
+            structure: true,
+            complement: true,
+        }
+    }
+
+    pub fn is_structure(&self) -> bool {
+        self.structure
+    }
+
+    /// `false` once complemented twice, the view then selects the same positions as the
+    /// original mask.
+    pub fn is_complement(&self) -> bool {
+        self.complement
     }
 }
 
@@ -36,25 +65,58 @@ where
             return &false;
         }
 
-        let is_val = *self.mat.index(index);
-
-        if is_val {
-            &false
+        let is_val = if self.structure {
+            self.mat.extract_element(index.0, index.1).is_ok()
         } else {
+            *self.mat.index(index)
+        };
+
+        if is_val != self.complement {
             &true
+        } else {
+            &false
         }
     }
 }
 
 // --------------------------------------------------------------------------------
 
+/// Complement of a mask.
+///
+/// A value complement selects every position where the mask value is `false` (or missing),
+/// while a structural complement selects every position without a stored entry. Complementing
+/// it again gives back a view of the original mask, see [`VectorComplementMask::is_complement`].
 pub struct VectorComplementMask<'a, V> {
     pub(crate) vector: &'a V,
+    pub(crate) structure: bool,
+    pub(crate) complement: bool,
 }
 
 impl<'a, V> VectorComplementMask<'a, V> {
     pub fn new(vector: &'a V) -> Self {
-        Self { vector }
+        Self {
+            vector,
+            structure: false,
+            complement: true,
+        }
+    }
+
+    pub fn new_structure(vector: &'a V) -> Self {
+        Self {
+            vector,
+            structure: true,
+            complement: true,
+        }
+    }
+
+    pub fn is_structure(&self) -> bool {
+        self.structure
+    }
+
+    /// `false` once complemented twice, the view then selects the same positions as the
+    /// original mask.
+    pub fn is_complement(&self) -> bool {
+        self.complement
     }
 }
 
@@ -78,12 +140,16 @@ where
             return &false;
         }
 
-        let is_val = *self.vector.index(index);
-
-        if is_val {
-            &false
+        let is_val = if self.structure {
+            self.vector.extract_element(index).is_ok()
         } else {
+            *self.vector.index(index)
+        };
+
+        if is_val != self.complement {
             &true
+        } else {
+            &false
         }
     }
 }
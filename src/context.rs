@@ -0,0 +1,176 @@
+use std::sync::{
+    atomic::{AtomicU8, Ordering},
+    RwLock, RwLockReadGuard, RwLockWriteGuard,
+};
+
+use crate::{descriptor::Descriptor, types::NoValue, ApiError, GblasResult};
+
+/// Execution mode (`GrB_Mode`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// Every method is complete when it returns.
+    #[default]
+    Blocking,
+    /// Element updates (`set_element`, `remove_element`) and operations writing into a sparse
+    /// object are queued on it, in call order, and run the next time it is read: on `wait`,
+    /// `nvals`, `extract_element`, `extract_tuples` or when it is used by an operation. Results
+    /// are identical to [`Mode::Blocking`].
+    ///
+    /// A queued operation owns its operators and a copy of its mask and inputs, so the objects it
+    /// was called with can change before it runs. The bitmap and full backends run operations
+    /// right away.
+    NonBlocking,
+}
+
+/// Wait mode (`GrB_WaitMode`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitMode {
+    /// `GrB_COMPLETE`: the object can be used by another thread.
+    Complete,
+    /// `GrB_MATERIALIZE`: the object is complete and has no pending work left.
+    Materialize,
+}
+
+// Status of the library, checked by every method, so it is kept out of the lock.
+const UNINITIALIZED: u8 = 0;
+const BLOCKING: u8 = 1;
+const NONBLOCKING: u8 = 2;
+const FINALIZED: u8 = 3;
+
+static STATUS: AtomicU8 = AtomicU8::new(UNINITIALIZED);
+
+#[derive(Debug)]
+struct Options {
+    nthreads: usize,
+    descriptor: Descriptor,
+}
+
+static OPTIONS: RwLock<Options> = RwLock::new(Options {
+    nthreads: 1,
+    descriptor: Descriptor::new(),
+});
+
+fn read() -> RwLockReadGuard<'static, Options> {
+    OPTIONS.read().unwrap_or_else(|err| err.into_inner())
+}
+
+fn write() -> RwLockWriteGuard<'static, Options> {
+    OPTIONS.write().unwrap_or_else(|err| err.into_inner())
+}
+
+fn status(mode: Mode) -> u8 {
+    match mode {
+        Mode::Blocking => BLOCKING,
+        Mode::NonBlocking => NONBLOCKING,
+    }
+}
+
+/// The current mode, `ApiError::UninitializedObject` outside of init/finalize.
+fn current_mode() -> GblasResult<Mode> {
+    match STATUS.load(Ordering::Acquire) {
+        BLOCKING => Ok(Mode::Blocking),
+        NONBLOCKING => Ok(Mode::NonBlocking),
+        _ => Err(ApiError::UninitializedObject.into()),
+    }
+}
+
+/// Global state of the library (`GrB_init`, `GrB_finalize` and the global options).
+///
+/// Objects can only be created, read, updated and used in operations between [`Context::init`]
+/// and [`Context::finalize`], otherwise `ApiError::UninitializedObject` is returned. Only the
+/// dimensions (`nrows`, `ncols`, `size`) stay readable.
+pub struct Context;
+
+impl Context {
+    /// Initializes the library in the given mode.
+    ///
+    /// It can only be called once, a second call or a call after [`Context::finalize`] returns
+    /// `ApiError::InvalidValue`.
+    pub fn init(mode: Mode) -> GblasResult<NoValue> {
+        // held until the options are set, so they are never read before
+        let mut options = write();
+        STATUS
+            .compare_exchange(
+                UNINITIALIZED,
+                status(mode),
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            )
+            .map_err(|_| ApiError::InvalidValue)?;
+        options.nthreads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        Ok(())
+    }
+
+    /// Finalizes the library, it cannot be initialized again afterwards.
+    pub fn finalize() -> GblasResult<NoValue> {
+        let current = status(current_mode()?);
+        STATUS
+            .compare_exchange(current, FINALIZED, Ordering::AcqRel, Ordering::Acquire)
+            .map_err(|_| ApiError::UninitializedObject)?;
+        Ok(())
+    }
+
+    pub fn is_initialized() -> bool {
+        current_mode().is_ok()
+    }
+
+    pub fn mode() -> GblasResult<Mode> {
+        current_mode()
+    }
+
+    /// Switches the execution mode.
+    ///
+    /// Objects with queued updates keep them until they are read.
+    pub fn set_mode(mode: Mode) -> GblasResult<NoValue> {
+        STATUS
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+                matches!(current, BLOCKING | NONBLOCKING).then_some(status(mode))
+            })
+            .map_err(|_| ApiError::UninitializedObject)?;
+        Ok(())
+    }
+
+    /// Number of threads the backends may use, defaults to the available parallelism.
+    ///
+    /// It's only a hint, the current backends run on the calling thread.
+    pub fn nthreads() -> GblasResult<usize> {
+        current_mode()?;
+        Ok(read().nthreads)
+    }
+
+    /// Sets the number of threads the backends may use, see [`Context::nthreads`].
+    pub fn set_nthreads(nthreads: usize) -> GblasResult<NoValue> {
+        if nthreads == 0 {
+            return Err(ApiError::InvalidValue.into());
+        }
+        current_mode()?;
+        write().nthreads = nthreads;
+        Ok(())
+    }
+
+    /// Descriptor used by operations called with `None`.
+    pub fn descriptor() -> GblasResult<Descriptor> {
+        current_mode()?;
+        Ok(read().descriptor)
+    }
+
+    pub fn set_descriptor(desc: Descriptor) -> GblasResult<NoValue> {
+        current_mode()?;
+        write().descriptor = desc;
+        Ok(())
+    }
+}
+
+/// Returns `ApiError::UninitializedObject` outside of init/finalize.
+pub(crate) fn check_init() -> GblasResult<NoValue> {
+    current_mode().map(|_| ())
+}
+
+pub(crate) fn is_nonblocking() -> bool {
+    STATUS.load(Ordering::Relaxed) == NONBLOCKING
+}
+
+/// The descriptor given to an operation, or the global default one.
+pub(crate) fn descriptor_or_default(desc: Option<Descriptor>) -> Descriptor {
+    desc.unwrap_or_else(|| read().descriptor)
+}
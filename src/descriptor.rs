@@ -1,8 +1,173 @@
-pub enum Descriptor {
-    /// Transpose Input 1
-    Inp0,
-    /// Transpose Input 1
-    Inp1,
-    /// Clear Output before processing
-    Replace,
+/// GraphBLAS descriptor.
+///
+/// Each field maps to one of the `GrB_Desc_Field`s, so settings can be combined freely
+/// (e.g. transposing both inputs while also clearing the output).
+/// `Descriptor::default()` is equivalent to `GrB_NULL`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Descriptor {
+    /// `GrB_OUTP = GrB_REPLACE`: Clear Output before processing
+    pub replace: bool,
+    /// `GrB_MASK = GrB_COMP`: Use the complement of the Mask
+    pub mask_complement: bool,
+    /// `GrB_MASK = GrB_STRUCTURE`: Use only the structure of the Mask, ignoring its values
+    pub mask_structure: bool,
+    /// `GrB_INP0 = GrB_TRAN`: Transpose Input 0
+    pub transpose_inp0: bool,
+    /// `GrB_INP1 = GrB_TRAN`: Transpose Input 1
+    pub transpose_inp1: bool,
+}
+
+impl Descriptor {
+    pub const fn new() -> Self {
+        Self {
+            replace: false,
+            mask_complement: false,
+            mask_structure: false,
+            transpose_inp0: false,
+            transpose_inp1: false,
+        }
+    }
+
+    pub const fn with_replace(mut self) -> Self {
+        self.replace = true;
+        self
+    }
+
+    pub const fn with_mask_complement(mut self) -> Self {
+        self.mask_complement = true;
+        self
+    }
+
+    pub const fn with_mask_structure(mut self) -> Self {
+        self.mask_structure = true;
+        self
+    }
+
+    pub const fn with_transpose_inp0(mut self) -> Self {
+        self.transpose_inp0 = true;
+        self
+    }
+
+    pub const fn with_transpose_inp1(mut self) -> Self {
+        self.transpose_inp1 = true;
+        self
+    }
+}
+
+// Predefined descriptors (`GrB_DESC_*`)
+// R: Replace, S: Structure, C: Complement, T0: Transpose Input 0, T1: Transpose Input 1
+impl Descriptor {
+    pub const DESC_T1: Self = Self::new().with_transpose_inp1();
+    pub const DESC_T0: Self = Self::new().with_transpose_inp0();
+    pub const DESC_T0T1: Self = Self::new().with_transpose_inp0().with_transpose_inp1();
+    pub const DESC_C: Self = Self::new().with_mask_complement();
+    pub const DESC_CT1: Self = Self::new().with_mask_complement().with_transpose_inp1();
+    pub const DESC_CT0: Self = Self::new().with_mask_complement().with_transpose_inp0();
+    pub const DESC_CT0T1: Self = Self::new()
+        .with_mask_complement()
+        .with_transpose_inp0()
+        .with_transpose_inp1();
+    pub const DESC_S: Self = Self::new().with_mask_structure();
+    pub const DESC_ST1: Self = Self::new().with_mask_structure().with_transpose_inp1();
+    pub const DESC_ST0: Self = Self::new().with_mask_structure().with_transpose_inp0();
+    pub const DESC_ST0T1: Self = Self::new()
+        .with_mask_structure()
+        .with_transpose_inp0()
+        .with_transpose_inp1();
+    pub const DESC_SC: Self = Self::new().with_mask_structure().with_mask_complement();
+    pub const DESC_SCT1: Self = Self::new()
+        .with_mask_structure()
+        .with_mask_complement()
+        .with_transpose_inp1();
+    pub const DESC_SCT0: Self = Self::new()
+        .with_mask_structure()
+        .with_mask_complement()
+        .with_transpose_inp0();
+    pub const DESC_SCT0T1: Self = Self::new()
+        .with_mask_structure()
+        .with_mask_complement()
+        .with_transpose_inp0()
+        .with_transpose_inp1();
+    pub const DESC_R: Self = Self::new().with_replace();
+    pub const DESC_RT1: Self = Self::new().with_replace().with_transpose_inp1();
+    pub const DESC_RT0: Self = Self::new().with_replace().with_transpose_inp0();
+    pub const DESC_RT0T1: Self = Self::new()
+        .with_replace()
+        .with_transpose_inp0()
+        .with_transpose_inp1();
+    pub const DESC_RC: Self = Self::new().with_replace().with_mask_complement();
+    pub const DESC_RCT1: Self = Self::new()
+        .with_replace()
+        .with_mask_complement()
+        .with_transpose_inp1();
+    pub const DESC_RCT0: Self = Self::new()
+        .with_replace()
+        .with_mask_complement()
+        .with_transpose_inp0();
+    pub const DESC_RCT0T1: Self = Self::new()
+        .with_replace()
+        .with_mask_complement()
+        .with_transpose_inp0()
+        .with_transpose_inp1();
+    pub const DESC_RS: Self = Self::new().with_replace().with_mask_structure();
+    pub const DESC_RST1: Self = Self::new()
+        .with_replace()
+        .with_mask_structure()
+        .with_transpose_inp1();
+    pub const DESC_RST0: Self = Self::new()
+        .with_replace()
+        .with_mask_structure()
+        .with_transpose_inp0();
+    pub const DESC_RST0T1: Self = Self::new()
+        .with_replace()
+        .with_mask_structure()
+        .with_transpose_inp0()
+        .with_transpose_inp1();
+    pub const DESC_RSC: Self = Self::new()
+        .with_replace()
+        .with_mask_structure()
+        .with_mask_complement();
+    pub const DESC_RSCT1: Self = Self::new()
+        .with_replace()
+        .with_mask_structure()
+        .with_mask_complement()
+        .with_transpose_inp1();
+    pub const DESC_RSCT0: Self = Self::new()
+        .with_replace()
+        .with_mask_structure()
+        .with_mask_complement()
+        .with_transpose_inp0();
+    pub const DESC_RSCT0T1: Self = Self::new()
+        .with_replace()
+        .with_mask_structure()
+        .with_mask_complement()
+        .with_transpose_inp0()
+        .with_transpose_inp1();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_descriptor() {
+        assert_eq!(Descriptor::default(), Descriptor::new());
+
+        let desc = Descriptor::DESC_RSCT0T1;
+        assert!(desc.replace);
+        assert!(desc.mask_structure);
+        assert!(desc.mask_complement);
+        assert!(desc.transpose_inp0);
+        assert!(desc.transpose_inp1);
+
+        let desc = Descriptor::DESC_T1;
+        assert!(desc.transpose_inp1);
+        assert!(!desc.transpose_inp0);
+        assert!(!desc.replace);
+
+        assert_eq!(
+            Descriptor::new().with_replace().with_transpose_inp0(),
+            Descriptor::DESC_RT0
+        );
+    }
 }
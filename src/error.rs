@@ -26,6 +26,8 @@ pub enum ApiError {
     OutputNotEmpty,
     #[error("NoValue: A location in a matrix or vector is being accessed that has no stored value at the specified location.")]
     NoValue,
+    #[error("EmptyObject: An empty scalar is passed to a method that needs its value.")]
+    EmptyObject,
 }
 
 #[derive(Debug, Error)]
@@ -0,0 +1,290 @@
+use crate::{
+    types::{IndexType, NoValue},
+    ApiError, ExecutionError, GblasResult,
+};
+
+/// Index selection used by the `extract` and `assign` family of operations.
+///
+/// Ranges follow the GxB convention and are inclusive on both ends.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Indices {
+    /// Explicit list of indices.
+    List(Vec<IndexType>),
+    /// Every index of the dimension (`GrB_ALL`).
+    All,
+    /// `begin..=end` (`GxB_RANGE`).
+    Range { begin: IndexType, end: IndexType },
+    /// `begin..=end` stepping by `inc` (`GxB_STRIDE`).
+    Stride {
+        begin: IndexType,
+        end: IndexType,
+        inc: IndexType,
+    },
+    /// `begin` down to `end` stepping by `inc` (`GxB_BACKWARDS`).
+    Backwards {
+        begin: IndexType,
+        end: IndexType,
+        inc: IndexType,
+    },
+}
+
+impl Indices {
+    /// Number of indices selected for a dimension of size `dim`.
+    ///
+    /// Returns `ApiError::InvalidIndex` when the count doesn't fit in an [`IndexType`], e.g. for
+    /// `Range { begin: 0, end: IndexType::MAX }`, and `ApiError::InvalidValue` for a stride of 0.
+    pub fn len(&self, dim: IndexType) -> GblasResult<IndexType> {
+        if let Indices::Stride { inc: 0, .. } | Indices::Backwards { inc: 0, .. } = self {
+            return Err(ApiError::InvalidValue.into());
+        }
+        let len = match self {
+            Indices::List(list) => Some(list.len()),
+            Indices::All => Some(dim),
+            Indices::Range { begin, end } => {
+                if end < begin {
+                    Some(0)
+                } else {
+                    (end - begin).checked_add(1)
+                }
+            }
+            Indices::Stride { begin, end, inc } => {
+                if end < begin {
+                    Some(0)
+                } else {
+                    ((end - begin) / inc).checked_add(1)
+                }
+            }
+            Indices::Backwards { begin, end, inc } => {
+                if begin < end {
+                    Some(0)
+                } else {
+                    ((begin - end) / inc).checked_add(1)
+                }
+            }
+        };
+        len.ok_or_else(|| ApiError::InvalidIndex.into())
+    }
+
+    pub fn is_empty(&self, dim: IndexType) -> GblasResult<bool> {
+        Ok(self.len(dim)? == 0)
+    }
+
+    /// Iterates the selected indices in order for a dimension of size `dim`.
+    ///
+    /// **Obs:**
+    ///
+    /// * The indices are ***not*** validated, see [`Indices::check`]. A selection whose length
+    ///   overflows, or whose stride is 0, yields nothing.
+    pub fn iter(&self, dim: IndexType) -> impl Iterator<Item = IndexType> + '_ {
+        let len = self.len(dim).unwrap_or_default();
+        (0..len).map(move |k| match self {
+            Indices::List(list) => list[k],
+            Indices::All => k,
+            Indices::Range { begin, .. } => begin + k,
+            Indices::Stride { begin, inc, .. } => begin + k * inc,
+            Indices::Backwards { begin, inc, .. } => begin - k * inc,
+        })
+    }
+
+    /// Checks the indices against a dimension of size `dim`.
+    pub fn check(&self, dim: IndexType) -> GblasResult<NoValue> {
+        let len = self.len(dim)?;
+        // the largest selected index, found without going through the selection
+        let max = match self {
+            Indices::List(list) => list.iter().max().copied(),
+            Indices::All => None,
+            _ if len == 0 => None,
+            Indices::Range { end, .. } => Some(*end),
+            Indices::Stride { begin, inc, .. } => Some(begin + (len - 1) * inc),
+            Indices::Backwards { begin, .. } => Some(*begin),
+        };
+        match max {
+            Some(max) if max >= dim => Err(ExecutionError::IndexOutOfBounds.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks the indices against a dimension of size `dim` and collects them.
+    pub fn resolve(&self, dim: IndexType) -> GblasResult<Vec<IndexType>> {
+        self.check(dim)?;
+        Ok(self.iter(dim).collect())
+    }
+}
+
+impl From<Vec<IndexType>> for Indices {
+    fn from(value: Vec<IndexType>) -> Self {
+        Indices::List(value)
+    }
+}
+
+impl From<&[IndexType]> for Indices {
+    fn from(value: &[IndexType]) -> Self {
+        Indices::List(value.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GblasError;
+
+    #[test]
+    fn test_indices_iter() {
+        let list = Indices::from(vec![3, 1, 1]);
+        assert_eq!(list.len(5).unwrap(), 3);
+        assert_eq!(list.iter(5).collect::<Vec<_>>(), vec![3, 1, 1]);
+
+        let all = Indices::All;
+        assert_eq!(all.iter(4).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+
+        let range = Indices::Range { begin: 2, end: 4 };
+        assert_eq!(range.iter(10).collect::<Vec<_>>(), vec![2, 3, 4]);
+
+        let range = Indices::Range { begin: 4, end: 2 };
+        assert!(range.is_empty(10).unwrap());
+
+        let stride = Indices::Stride {
+            begin: 1,
+            end: 8,
+            inc: 3,
+        };
+        assert_eq!(stride.iter(10).collect::<Vec<_>>(), vec![1, 4, 7]);
+
+        let backwards = Indices::Backwards {
+            begin: 8,
+            end: 1,
+            inc: 3,
+        };
+        assert_eq!(backwards.iter(10).collect::<Vec<_>>(), vec![8, 5, 2]);
+
+        let backwards = Indices::Backwards {
+            begin: 1,
+            end: 8,
+            inc: 3,
+        };
+        assert!(backwards.is_empty(10).unwrap());
+    }
+
+    #[test]
+    fn test_indices_overflow() {
+        let range = Indices::Range {
+            begin: 0,
+            end: IndexType::MAX,
+        };
+        assert!(matches!(
+            range.len(5),
+            Err(GblasError::ApiError(ApiError::InvalidIndex))
+        ));
+        assert!(matches!(
+            range.check(5),
+            Err(GblasError::ApiError(ApiError::InvalidIndex))
+        ));
+        assert_eq!(range.iter(5).count(), 0);
+
+        let stride = Indices::Stride {
+            begin: 0,
+            end: IndexType::MAX,
+            inc: 1,
+        };
+        assert!(stride.check(5).is_err());
+        let backwards = Indices::Backwards {
+            begin: IndexType::MAX,
+            end: 0,
+            inc: 1,
+        };
+        assert!(backwards.resolve(5).is_err());
+
+        // a single step over the whole range fits
+        let stride = Indices::Stride {
+            begin: 0,
+            end: IndexType::MAX,
+            inc: IndexType::MAX,
+        };
+        assert_eq!(stride.len(5).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_indices_check() {
+        assert!(Indices::from(vec![0, 4]).check(5).is_ok());
+        assert!(Indices::from(vec![0, 5]).check(5).is_err());
+        assert!(Indices::All.check(5).is_ok());
+        assert!(Indices::Range { begin: 0, end: 4 }.check(5).is_ok());
+        assert!(Indices::Range { begin: 0, end: 5 }.check(5).is_err());
+        assert!(Indices::Range { begin: 7, end: 5 }.check(5).is_ok());
+        assert!(Indices::Stride {
+            begin: 0,
+            end: 5,
+            inc: 2
+        }
+        .check(5)
+        .is_ok());
+        assert!(Indices::Stride {
+            begin: 0,
+            end: 6,
+            inc: 2
+        }
+        .check(5)
+        .is_err());
+        let zero_inc = Indices::Stride {
+            begin: 0,
+            end: 4,
+            inc: 0,
+        };
+        assert!(matches!(
+            zero_inc.check(5),
+            Err(GblasError::ApiError(ApiError::InvalidValue))
+        ));
+        assert!(matches!(
+            zero_inc.len(5),
+            Err(GblasError::ApiError(ApiError::InvalidValue))
+        ));
+        assert_eq!(zero_inc.iter(5).count(), 0);
+        assert!(Indices::Backwards {
+            begin: 5,
+            end: 0,
+            inc: 1
+        }
+        .check(5)
+        .is_err());
+        assert_eq!(
+            Indices::Backwards {
+                begin: 4,
+                end: 0,
+                inc: 2
+            }
+            .resolve(5)
+            .unwrap(),
+            vec![4, 2, 0]
+        );
+
+        // checked without walking the selection
+        let dim = 1 << 40;
+        assert!(Indices::Range {
+            begin: 0,
+            end: dim - 1
+        }
+        .check(dim)
+        .is_ok());
+        assert!(Indices::Stride {
+            begin: 1,
+            end: dim,
+            inc: 2
+        }
+        .check(dim)
+        .is_ok());
+        assert!(Indices::Stride {
+            begin: 0,
+            end: dim,
+            inc: 2
+        }
+        .check(dim)
+        .is_err());
+        assert!(Indices::Backwards {
+            begin: dim,
+            end: 0,
+            inc: 3
+        }
+        .check(dim)
+        .is_err());
+    }
+}
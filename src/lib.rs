@@ -1,15 +1,18 @@
-mod algebra;
-mod backend;
-mod complement_mask;
-mod descriptor;
+pub mod algebra;
+pub mod backend;
+pub mod complement_mask;
+pub mod context;
+pub mod descriptor;
 mod error;
-mod indices;
-mod mask;
-mod matrix;
-mod operations;
-mod structure_mask;
-mod types;
-mod vector;
+pub mod indices;
+pub mod mask;
+pub mod matrix;
+pub mod operations;
+pub mod prelude;
+pub mod scalar;
+pub mod structure_mask;
+pub mod types;
+pub mod vector;
 
 pub use error::{ApiError, ExecutionError, GblasError};
 
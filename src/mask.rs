@@ -8,21 +8,38 @@ use crate::{
     vector::Vector,
 };
 
+/// Vector mask.
+///
+/// Indexing the object itself gives the value mask (a stored entry counts only if its value is
+/// `true`), [`VecMask::structure`] gives the structural mask (any stored entry counts).
 pub trait VecMask<V>: Index<IndexType, Output = bool> {
-    fn complement(&self) -> VectorComplementMask<V>;
-    fn structure(&self) -> VectorStructureMask<V>;
+    fn complement(&self) -> VectorComplementMask<'_, V>;
+    fn structure(&self) -> VectorStructureMask<'_, V>;
+    /// Size of the mask, which must match the output it's applied to.
+    fn shape(&self) -> IndexType;
 }
 
 impl<V> VecMask<V> for VectorStructureMask<'_, V>
 where
-    V: Vector + Index<IndexType, Output = bool>,
+    V: Vector,
 {
-    fn complement(&self) -> VectorComplementMask<V> {
-        VectorComplementMask::new(self.vector)
+    fn complement(&self) -> VectorComplementMask<'_, V> {
+        VectorComplementMask {
+            vector: self.vector,
+            structure: true,
+            complement: !self.complement,
+        }
+    }
+
+    fn structure(&self) -> VectorStructureMask<'_, V> {
+        VectorStructureMask {
+            vector: self.vector,
+            complement: self.complement,
+        }
     }
 
-    fn structure(&self) -> VectorStructureMask<V> {
-        VectorStructureMask::new(self.vector)
+    fn shape(&self) -> IndexType {
+        self.size()
     }
 }
 
@@ -30,32 +47,62 @@ impl<V> VecMask<V> for VectorComplementMask<'_, V>
 where
     V: Vector + Index<IndexType, Output = bool>,
 {
-    fn complement(&self) -> VectorComplementMask<V> {
-        VectorComplementMask::new(self.vector)
+    /// Complementing twice gives back the original mask.
+    fn complement(&self) -> VectorComplementMask<'_, V> {
+        VectorComplementMask {
+            vector: self.vector,
+            structure: self.structure,
+            complement: !self.complement,
+        }
     }
 
-    fn structure(&self) -> VectorStructureMask<V> {
-        VectorStructureMask::new(self.vector)
+    /// The structure of a complement is the complement of the structure.
+    fn structure(&self) -> VectorStructureMask<'_, V> {
+        VectorStructureMask {
+            vector: self.vector,
+            complement: self.complement,
+        }
+    }
+
+    fn shape(&self) -> IndexType {
+        self.size()
     }
 }
 
 // --------------------------------------------------------------------------------
 
+/// Matrix mask.
+///
+/// Indexing the object itself gives the value mask (a stored entry counts only if its value is
+/// `true`), [`MatMask::structure`] gives the structural mask (any stored entry counts).
 pub trait MatMask<M>: Index<(IndexType, IndexType), Output = bool> {
-    fn complement(&self) -> MatrixComplementMask<M>;
-    fn structure(&self) -> MatrixStructureMask<M>;
+    fn complement(&self) -> MatrixComplementMask<'_, M>;
+    fn structure(&self) -> MatrixStructureMask<'_, M>;
+    /// Dimensions of the mask as `(nrows, ncols)`, which must match the output it's applied to.
+    fn shape(&self) -> (IndexType, IndexType);
 }
 
 impl<M> MatMask<M> for MatrixStructureMask<'_, M>
 where
-    M: Matrix + Index<(IndexType, IndexType), Output = bool>,
+    M: Matrix,
 {
-    fn complement(&self) -> MatrixComplementMask<M> {
-        MatrixComplementMask::new(self.mat)
+    fn complement(&self) -> MatrixComplementMask<'_, M> {
+        MatrixComplementMask {
+            mat: self.mat,
+            structure: true,
+            complement: !self.complement,
+        }
     }
 
-    fn structure(&self) -> MatrixStructureMask<M> {
-        MatrixStructureMask::new(self.mat)
+    fn structure(&self) -> MatrixStructureMask<'_, M> {
+        MatrixStructureMask {
+            mat: self.mat,
+            complement: self.complement,
+        }
+    }
+
+    fn shape(&self) -> (IndexType, IndexType) {
+        (self.nrows(), self.ncols())
     }
 }
 
@@ -63,12 +110,24 @@ impl<M> MatMask<M> for MatrixComplementMask<'_, M>
 where
     M: Matrix + Index<(IndexType, IndexType), Output = bool>,
 {
-    fn complement(&self) -> MatrixComplementMask<M> {
-        MatrixComplementMask::new(self.mat)
+    /// Complementing twice gives back the original mask.
+    fn complement(&self) -> MatrixComplementMask<'_, M> {
+        MatrixComplementMask {
+            mat: self.mat,
+            structure: self.structure,
+            complement: !self.complement,
+        }
     }
 
-    fn structure(&self) -> MatrixStructureMask<M> {
-        MatrixStructureMask::new(self.mat)
+    /// The structure of a complement is the complement of the structure.
+    fn structure(&self) -> MatrixStructureMask<'_, M> {
+        MatrixStructureMask {
+            mat: self.mat,
+            complement: self.complement,
+        }
     }
-}
 
+    fn shape(&self) -> (IndexType, IndexType) {
+        (self.nrows(), self.ncols())
+    }
+}
@@ -1,22 +1,143 @@
 use crate::{
     algebra::BinaryOperator,
+    context::{self, WaitMode},
+    scalar::Scalar,
     types::{IndexType, NoValue},
-    GblasResult,
+    ApiError, GblasError, GblasResult,
 };
 
+/// Storage orientation of a matrix (`GxB_FORMAT`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Orientation {
+    /// Entries are grouped by row.
+    #[default]
+    ByRow,
+    /// Entries are grouped by column.
+    ByCol,
+}
+
+/// Borrowed compressed storage of a matrix, see [`Matrix::compressed`].
+///
+/// The entries of the `k`-th stored major vector (row, or column when stored
+/// [`Orientation::ByCol`]) are `minors[ptr[k]..ptr[k + 1]]`, sorted, with their `values`. The
+/// index of that vector is `majors[k]` when only the non-empty ones are listed (hypersparse), `k`
+/// otherwise.
+#[derive(Debug)]
+pub struct Compressed<'a, T> {
+    pub(crate) majors: Option<&'a [IndexType]>,
+    pub(crate) ptr: &'a [IndexType],
+    pub(crate) minors: &'a [IndexType],
+    pub(crate) values: &'a [T],
+}
+
+/// Sorted `(index, value)` entries of one major vector of a [`Compressed`] matrix.
+#[derive(Debug)]
+pub(crate) struct Entries<'a, T> {
+    indices: &'a [IndexType],
+    values: &'a [T],
+}
+
+impl<T> Clone for Compressed<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Compressed<'_, T> {}
+
+impl<T> Clone for Entries<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Entries<'_, T> {}
+
+impl<'a, T> Compressed<'a, T> {
+    pub(crate) fn new(
+        majors: Option<&'a [IndexType]>,
+        ptr: &'a [IndexType],
+        minors: &'a [IndexType],
+        values: &'a [T],
+    ) -> Self {
+        debug_assert_eq!(
+            ptr.len(),
+            majors.map_or(ptr.len(), |majors| majors.len() + 1)
+        );
+        debug_assert_eq!(minors.len(), values.len());
+        Self {
+            majors,
+            ptr,
+            minors,
+            values,
+        }
+    }
+
+    fn entries(&self, k: usize) -> Entries<'a, T> {
+        let range = self.ptr[k]..self.ptr[k + 1];
+        Entries {
+            indices: &self.minors[range.clone()],
+            values: &self.values[range],
+        }
+    }
+
+    /// Entries of major vector `i`, empty if it has none.
+    pub(crate) fn get(&self, i: IndexType) -> Entries<'a, T> {
+        let k = match self.majors {
+            Some(majors) => majors.binary_search(&i).ok(),
+            None => (i + 1 < self.ptr.len()).then_some(i),
+        };
+        match k {
+            Some(k) => self.entries(k),
+            None => Entries {
+                indices: &[],
+                values: &[],
+            },
+        }
+    }
+
+    /// Non-empty major vectors with their index, in order.
+    pub(crate) fn iter(self) -> impl Iterator<Item = (IndexType, Entries<'a, T>)> {
+        (0..self.ptr.len() - 1)
+            .map(move |k| (self.majors.map_or(k, |majors| majors[k]), self.entries(k)))
+            .filter(|(_, vec)| !vec.is_empty())
+    }
+
+    /// Indices of the non-empty major vectors, in order.
+    pub(crate) fn indices(self) -> impl Iterator<Item = IndexType> + 'a {
+        self.iter().map(|(i, _)| i)
+    }
+}
+
+impl<'a, T> Entries<'a, T> {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (IndexType, &'a T)> + Clone {
+        self.indices.iter().copied().zip(self.values)
+    }
+
+    /// Value at minor index `j`, found by binary search.
+    pub(crate) fn find(&self, j: IndexType) -> Option<&'a T> {
+        self.indices.binary_search(&j).ok().map(|k| &self.values[k])
+    }
+}
+
 /// Frontend Matrix.
 pub trait Matrix: Clone + PartialEq + Sized {
     type Scalar;
 
     fn new(rows: IndexType, cols: IndexType) -> GblasResult<Self>;
     fn dup(&self) -> GblasResult<Self> {
+        context::check_init()?;
         Ok(self.clone())
     }
     fn resize(&mut self, rows: IndexType, cols: IndexType) -> GblasResult<NoValue>;
     fn clear(&mut self) -> GblasResult<NoValue>;
     fn nrows(&self) -> IndexType;
     fn ncols(&self) -> IndexType;
-    fn nvals(&self) -> IndexType;
+    fn nvals(&self) -> GblasResult<IndexType>;
     fn build(
         self,
         rows: impl Iterator<Item = IndexType>,
@@ -33,7 +154,87 @@ pub trait Matrix: Clone + PartialEq + Sized {
     ) -> GblasResult<NoValue>;
     fn remove_element(&mut self, row: IndexType, col: IndexType) -> GblasResult<NoValue>;
     fn extract_element(&self, row: IndexType, col: IndexType) -> GblasResult<&Self::Scalar>;
+
+    /// Same as [`Matrix::extract_element`], but a missing entry gives an empty [`Scalar`] instead
+    /// of `ApiError::NoValue`.
+    fn extract_scalar(&self, row: IndexType, col: IndexType) -> GblasResult<Scalar<Self::Scalar>>
+    where
+        Self::Scalar: Clone,
+    {
+        match self.extract_element(row, col) {
+            Ok(val) => Ok(Scalar::from(val.clone())),
+            Err(GblasError::ApiError(ApiError::NoValue)) => Ok(Scalar::new()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Tuples are returned in storage order, see [`Matrix::orientation`].
+    #[allow(clippy::type_complexity)]
     fn extract_tuples(self) -> GblasResult<(Vec<IndexType>, Vec<IndexType>, Vec<Self::Scalar>)>;
+
+    /// Copies the entries into a matrix of another backend, e.g. to move a matrix that became
+    /// dense to [`BitmapMatrix`](crate::backend::BitmapMatrix) or
+    /// [`FullMatrix`](crate::backend::FullMatrix).
+    fn convert<M: Matrix<Scalar = Self::Scalar>>(self) -> GblasResult<M> {
+        let mut res = M::new(self.nrows(), self.ncols())?;
+        let (rows, cols, values) = self.extract_tuples()?;
+        for ((row, col), val) in rows.into_iter().zip(cols).zip(values) {
+            res.set_element(row, col, val)?;
+        }
+        Ok(res)
+    }
+
+    /// Orientation the entries are stored in.
+    ///
+    /// Operations read their inputs in this orientation when they can, so e.g. a transposed
+    /// by-row input is read as a by-column one instead of being transposed.
+    fn orientation(&self) -> Orientation {
+        Orientation::ByRow
+    }
+
+    /// Sorted `(index, value)` entries of row `k`, or of column `k` when stored
+    /// [`Orientation::ByCol`].
+    ///
+    /// The default goes through [`Matrix::extract_element`] for every index of the other
+    /// dimension.
+    fn extract_major(&self, k: IndexType) -> GblasResult<Vec<(IndexType, Self::Scalar)>>
+    where
+        Self::Scalar: Clone,
+    {
+        context::check_init()?;
+        let by_col = self.orientation() == Orientation::ByCol;
+        let (major, minor) = if by_col {
+            (self.ncols(), self.nrows())
+        } else {
+            (self.nrows(), self.ncols())
+        };
+        if k >= major {
+            return Err(ApiError::InvalidIndex.into());
+        }
+
+        Ok((0..minor)
+            .filter_map(|m| {
+                let (i, j) = if by_col { (m, k) } else { (k, m) };
+                self.extract_element(i, j).ok().map(|val| (m, val.clone()))
+            })
+            .collect())
+    }
+
+    /// Borrowed compressed arrays of the entries, in the orientation they are stored in.
+    ///
+    /// Operations read these in place instead of copying the matrix. `None` by default, which
+    /// makes them go through [`Matrix::extract_tuples`] on a copy.
+    fn compressed(&self) -> Option<Compressed<'_, Self::Scalar>> {
+        None
+    }
+
+    /// Runs the updates and operations queued on the object in nonblocking mode (`GrB_wait`).
+    ///
+    /// Objects are always complete in blocking mode, so the default only checks that the
+    /// library is initialized.
+    fn wait(&mut self, _mode: WaitMode) -> GblasResult<NoValue> {
+        context::check_init()
+    }
 }
 
 pub trait MatrixExtra: Matrix {
@@ -1,20 +1,29 @@
 use crate::{
-    algebra::{BinaryOperator, Monoid, Semiring, UnaryOperator},
+    algebra::{
+        AssociativeCommutative, BinaryOperator, IndexUnaryOperator, Monoid, Semiring, UnaryOperator,
+    },
     descriptor::Descriptor,
     indices::Indices,
     mask::{MatMask, VecMask},
     matrix::Matrix,
+    scalar::Scalar,
     types::{IndexType, NoValue},
     vector::Vector,
     GblasResult,
 };
 
+/// Operations of a matrix, writing into it unless they reduce it.
+///
+/// Operators and accumulators are `Send + 'static` so that nonblocking mode
+/// ([`Mode::NonBlocking`](crate::context::Mode::NonBlocking)) can queue an operation on its output
+/// and run it later, closures have to capture by `move`. Masks and inputs are copied when the
+/// operation is queued, so they can change or be dropped afterwards.
 pub trait MatOps: Matrix {
     fn mxm(
         &mut self,
         mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl Semiring<Self::Scalar, Output = Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Semiring<Self::Scalar, Output = Self::Scalar> + Send + 'static,
         a: &impl Matrix<Scalar = Self::Scalar>,
         b: &impl Matrix<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
@@ -23,8 +32,8 @@ pub trait MatOps: Matrix {
     fn e_wise_mult_binary_op(
         &mut self,
         mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
         a: &impl Matrix<Scalar = Self::Scalar>,
         b: &impl Matrix<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
@@ -33,8 +42,8 @@ pub trait MatOps: Matrix {
     fn e_wise_mult_monoid(
         &mut self,
         mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl Monoid<Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Monoid<Self::Scalar> + Send + 'static,
         a: &impl Matrix<Scalar = Self::Scalar>,
         b: &impl Matrix<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
@@ -43,8 +52,8 @@ pub trait MatOps: Matrix {
     fn e_wise_mult_semiring(
         &mut self,
         mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl Semiring<Self::Scalar, Output = Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Semiring<Self::Scalar, Output = Self::Scalar> + Send + 'static,
         a: &impl Matrix<Scalar = Self::Scalar>,
         b: &impl Matrix<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
@@ -53,8 +62,8 @@ pub trait MatOps: Matrix {
     fn e_wise_add_binary_op(
         &mut self,
         mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
         a: &impl Matrix<Scalar = Self::Scalar>,
         b: &impl Matrix<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
@@ -63,8 +72,8 @@ pub trait MatOps: Matrix {
     fn e_wise_add_monoid(
         &mut self,
         mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl Monoid<Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Monoid<Self::Scalar> + Send + 'static,
         a: &impl Matrix<Scalar = Self::Scalar>,
         b: &impl Matrix<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
@@ -73,8 +82,8 @@ pub trait MatOps: Matrix {
     fn e_wise_add_semiring(
         &mut self,
         mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl Semiring<Self::Scalar, Output = Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Semiring<Self::Scalar, Output = Self::Scalar> + Send + 'static,
         a: &impl Matrix<Scalar = Self::Scalar>,
         b: &impl Matrix<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
@@ -83,7 +92,7 @@ pub trait MatOps: Matrix {
     fn extract(
         &mut self,
         mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
         a: &impl Matrix<Scalar = Self::Scalar>,
         row_indices: Indices,
         col_indices: Indices,
@@ -93,39 +102,74 @@ pub trait MatOps: Matrix {
     fn assign(
         &mut self,
         mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
         a: &impl Matrix<Scalar = Self::Scalar>,
         row_indices: Indices,
         col_indices: Indices,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
 
-    fn assign_col(
+    /// _C<m>(I, j) = C(I, j) ⊙ u_
+    ///
+    /// The mask has the size of a column of `C` and only column `j` is affected.
+    fn assign_col<V>(
         &mut self,
-        mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        a: &impl Matrix<Scalar = Self::Scalar>,
+        mask: Option<impl VecMask<V>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        u: &V,
         row_indices: Indices,
         col_index: IndexType,
         desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue>
+    where
+        V: Vector<Scalar = Self::Scalar>;
+
+    /// _C<m'>(i, J) = C(i, J) ⊙ u'_
+    ///
+    /// The mask has the size of a row of `C` and only row `i` is affected.
+    fn assign_row<V>(
+        &mut self,
+        mask: Option<impl VecMask<V>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        u: &V,
+        row_index: IndexType,
+        col_indices: Indices,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue>
+    where
+        V: Vector<Scalar = Self::Scalar>;
+
+    // TODO: rename to `assign_udt`? (https://graphblas.org/docs/GraphBLAS_API_C_v1.3.0.pdf#table.5.5)
+    /// An empty `value` behaves like assigning an empty object, deleting the assigned entries.
+    fn assign_value(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        value: impl Into<Scalar<Self::Scalar>>,
+        row_indices: Indices,
+        col_indices: Indices,
+        desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
 
-    fn assign_row(
+    /// _C(I, J)<M> = C(I, J) ⊙ A_ (`GxB_subassign`)
+    ///
+    /// Unlike [`MatOps::assign`], the mask has the size of the submatrix and entries of `C`
+    /// outside of `C(I, J)` are never modified, even with replace.
+    fn subassign(
         &mut self,
         mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
         a: &impl Matrix<Scalar = Self::Scalar>,
-        row_index: IndexType,
+        row_indices: Indices,
         col_indices: Indices,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
 
-    // TODO: rename to `assign_udt`? (https://graphblas.org/docs/GraphBLAS_API_C_v1.3.0.pdf#table.5.5)
-    fn assign_value(
+    fn subassign_value(
         &mut self,
         mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        value: Self::Scalar,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        value: impl Into<Scalar<Self::Scalar>>,
         row_indices: Indices,
         col_indices: Indices,
         desc: Option<Descriptor>,
@@ -134,32 +178,62 @@ pub trait MatOps: Matrix {
     fn apply(
         &mut self,
         mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl UnaryOperator<Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl UnaryOperator<Self::Scalar> + Send + 'static,
         a: &impl Matrix<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
 
+    /// _C<M, accum> = op(value, A)_, `A` is still transposed with [`Descriptor::transpose_inp0`].
     fn apply_1st(
         &mut self,
         mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
-        value: Self::Scalar,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        value: impl Into<Scalar<Self::Scalar>>,
         a: &impl Matrix<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
 
+    /// _C<M, accum> = op(A, value)_
+    ///
+    /// Both variants return `ApiError::EmptyObject` when `value` is empty.
     fn apply_2nd(
         &mut self,
         mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
         a: &impl Matrix<Scalar = Self::Scalar>,
-        value: Self::Scalar,
+        value: impl Into<Scalar<Self::Scalar>>,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
 
+    /// _C<M, accum>(i, j) = op(A(i, j), i, j, thunk)_ (`GrB_apply` with a `GrB_IndexUnaryOp`).
+    fn apply_index_op<S: Clone + Send + 'static>(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl IndexUnaryOperator<Self::Scalar, S, Output = Self::Scalar> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        thunk: impl Into<Scalar<S>>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue>;
+
+    /// _C<M, accum> = select(A, op, thunk)_ (`GrB_select`)
+    ///
+    /// Keeps the entries of _A_ for which `op` returns true. Returns `ApiError::EmptyObject` when
+    /// `thunk` is empty, like [`MatOps::apply_index_op`].
+    fn select<S: Clone + Send + 'static>(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl IndexUnaryOperator<Self::Scalar, S, Output = bool> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
+        thunk: impl Into<Scalar<S>>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue>;
+
+    /// _val = accum(val, ⊕ A(i, j))_, the identity of `op` when _A_ is empty.
     fn reduce(
         &self,
         val: &mut Self::Scalar,
@@ -168,10 +242,54 @@ pub trait MatOps: Matrix {
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
 
+    /// _s = accum(s, ⊕ A(i, j))_ into a [`Scalar`]. Unlike [`MatOps::reduce`], an empty _A_ gives
+    /// an empty result, which leaves `s` untouched when accumulating and clears it otherwise.
+    fn reduce_scalar(
+        &self,
+        s: &mut Scalar<Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
+        op: impl Monoid<Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue>;
+
+    /// Same as [`MatOps::reduce_scalar`] with an associative and commutative operator.
+    fn reduce_scalar_binary_op(
+        &self,
+        s: &mut Scalar<Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + AssociativeCommutative,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue>;
+
+    /// _C<M, accum> = u ⊗ v'_, the outer product _C(i, j) = op(u(i), v(j))_.
+    ///
+    /// `C` must be _size(u) x size(v)_.
+    fn outer_binary_op(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        v: &impl Vector<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue>;
+
+    /// Same as [`MatOps::outer_binary_op`] with the multiplicative operator of a semiring.
+    fn outer_semiring(
+        &mut self,
+        mask: Option<impl MatMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Semiring<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        v: &impl Vector<Scalar = Self::Scalar>,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue>;
+
+    /// _C<M, accum> = A'_, or _C<M, accum> = A_ with [`Descriptor::transpose_inp0`].
     fn transpose(
         &mut self,
         mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
         a: &impl Matrix<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
@@ -179,8 +297,8 @@ pub trait MatOps: Matrix {
     fn kronecker_binary_op(
         &mut self,
         mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
         a: &impl Matrix<Scalar = Self::Scalar>,
         b: &impl Matrix<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
@@ -189,8 +307,8 @@ pub trait MatOps: Matrix {
     fn kronecker_monoid(
         &mut self,
         mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl Monoid<Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Monoid<Self::Scalar> + Send + 'static,
         a: &impl Matrix<Scalar = Self::Scalar>,
         b: &impl Matrix<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
@@ -199,8 +317,8 @@ pub trait MatOps: Matrix {
     fn kronecker_semiring(
         &mut self,
         mask: Option<impl MatMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl Semiring<Self::Scalar, Output = Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Semiring<Self::Scalar, Output = Self::Scalar> + Send + 'static,
         a: &impl Matrix<Scalar = Self::Scalar>,
         b: &impl Matrix<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
@@ -209,22 +327,23 @@ pub trait MatOps: Matrix {
 
 // ---------------------------------------------------------------------------
 
+/// Operations of a vector, see [`MatOps`] for the bounds on the operators.
 pub trait VecOps: Vector {
     fn vxm(
         &mut self,
         mask: Option<impl VecMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl Semiring<Self::Scalar, Output = Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Semiring<Self::Scalar, Output = Self::Scalar> + Send + 'static,
         u: &impl Vector<Scalar = Self::Scalar>,
-        a: &impl Vector<Scalar = Self::Scalar>,
+        a: &impl Matrix<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
 
     fn mxv(
         &mut self,
         mask: Option<impl VecMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl Semiring<Self::Scalar, Output = Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Semiring<Self::Scalar, Output = Self::Scalar> + Send + 'static,
         a: &impl Matrix<Scalar = Self::Scalar>,
         u: &impl Vector<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
@@ -233,8 +352,8 @@ pub trait VecOps: Vector {
     fn e_wise_mult_binary_op(
         &mut self,
         mask: Option<impl VecMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
         u: &impl Vector<Scalar = Self::Scalar>,
         v: &impl Vector<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
@@ -243,8 +362,8 @@ pub trait VecOps: Vector {
     fn e_wise_mult_monoid(
         &mut self,
         mask: Option<impl VecMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl Monoid<Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Monoid<Self::Scalar> + Send + 'static,
         u: &impl Vector<Scalar = Self::Scalar>,
         v: &impl Vector<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
@@ -253,8 +372,8 @@ pub trait VecOps: Vector {
     fn e_wise_mult_semiring(
         &mut self,
         mask: Option<impl VecMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl Semiring<Self::Scalar, Output = Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Semiring<Self::Scalar, Output = Self::Scalar> + Send + 'static,
         u: &impl Vector<Scalar = Self::Scalar>,
         v: &impl Vector<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
@@ -263,8 +382,8 @@ pub trait VecOps: Vector {
     fn e_wise_add_binary_op(
         &mut self,
         mask: Option<impl VecMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
         u: &impl Vector<Scalar = Self::Scalar>,
         v: &impl Vector<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
@@ -273,8 +392,8 @@ pub trait VecOps: Vector {
     fn e_wise_add_monoid(
         &mut self,
         mask: Option<impl VecMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl Monoid<Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Monoid<Self::Scalar> + Send + 'static,
         u: &impl Vector<Scalar = Self::Scalar>,
         v: &impl Vector<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
@@ -283,8 +402,8 @@ pub trait VecOps: Vector {
     fn e_wise_add_semiring(
         &mut self,
         mask: Option<impl VecMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl Semiring<Self::Scalar, Output = Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Semiring<Self::Scalar, Output = Self::Scalar> + Send + 'static,
         u: &impl Vector<Scalar = Self::Scalar>,
         v: &impl Vector<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
@@ -293,7 +412,7 @@ pub trait VecOps: Vector {
     fn extract(
         &mut self,
         mask: Option<impl VecMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
         u: &impl Vector<Scalar = Self::Scalar>,
         indices: Indices,
         desc: Option<Descriptor>,
@@ -302,7 +421,7 @@ pub trait VecOps: Vector {
     fn extract_col(
         &mut self,
         mask: Option<impl VecMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
         a: &impl Matrix<Scalar = Self::Scalar>,
         row_indices: Indices,
         col_index: IndexType,
@@ -312,18 +431,41 @@ pub trait VecOps: Vector {
     fn assign(
         &mut self,
         mask: Option<impl VecMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
         u: &impl Vector<Scalar = Self::Scalar>,
         indices: Indices,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
 
     // TODO: rename to `assign_udt`? (https://graphblas.org/docs/GraphBLAS_API_C_v1.3.0.pdf#table.5.5)
+    /// An empty `value` behaves like assigning an empty object, deleting the assigned entries.
     fn assign_value(
         &mut self,
         mask: Option<impl VecMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        value: Self::Scalar,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        value: impl Into<Scalar<Self::Scalar>>,
+        indices: Indices,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue>;
+
+    /// _w(I)<m> = w(I) ⊙ u_ (`GxB_subassign`)
+    ///
+    /// Unlike [`VecOps::assign`], the mask has the size of `u` and entries of `w` outside of
+    /// `w(I)` are never modified, even with replace.
+    fn subassign(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        indices: Indices,
+        desc: Option<Descriptor>,
+    ) -> GblasResult<NoValue>;
+
+    fn subassign_value(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        value: impl Into<Scalar<Self::Scalar>>,
         indices: Indices,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
@@ -331,8 +473,8 @@ pub trait VecOps: Vector {
     fn apply(
         &mut self,
         mask: Option<impl VecMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl UnaryOperator<Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl UnaryOperator<Self::Scalar> + Send + 'static,
         u: &impl Vector<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
@@ -340,9 +482,9 @@ pub trait VecOps: Vector {
     fn apply_1st(
         &mut self,
         mask: Option<impl VecMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
-        value: Self::Scalar,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
+        value: impl Into<Scalar<Self::Scalar>>,
         u: &impl Vector<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
@@ -350,74 +492,87 @@ pub trait VecOps: Vector {
     fn apply_2nd(
         &mut self,
         mask: Option<impl VecMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static,
         u: &impl Vector<Scalar = Self::Scalar>,
-        value: Self::Scalar,
+        value: impl Into<Scalar<Self::Scalar>>,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
 
-    fn reduce(
-        &self,
-        val: &mut Self::Scalar,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl Monoid<Self::Scalar>,
+    /// _w<m, accum>(i) = op(u(i), i, 0, thunk)_ (`GrB_apply` with a `GrB_IndexUnaryOp`).
+    fn apply_index_op<S: Clone + Send + 'static>(
+        &mut self,
+        mask: Option<impl VecMask<Self>>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl IndexUnaryOperator<Self::Scalar, S, Output = Self::Scalar> + Send + 'static,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        thunk: impl Into<Scalar<S>>,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
 
-    fn reduce_binary_op(
+    /// _w<m, accum> = select(u, op, thunk)_ (`GrB_select`)
+    ///
+    /// Keeps the entries of _u_ for which `op` returns true. Returns `ApiError::EmptyObject` when
+    /// `thunk` is empty, like [`VecOps::apply_index_op`].
+    fn select<S: Clone + Send + 'static>(
         &mut self,
         mask: Option<impl VecMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
-        a: &impl Matrix<Scalar = Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl IndexUnaryOperator<Self::Scalar, S, Output = bool> + Send + 'static,
+        u: &impl Vector<Scalar = Self::Scalar>,
+        thunk: impl Into<Scalar<S>>,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
 
-    fn reduce_monoid(
-        &mut self,
-        mask: Option<impl VecMask<Self>>,
+    /// _val = accum(val, ⊕ u(i))_, the identity of `op` when _u_ is empty.
+    fn reduce(
+        &self,
+        val: &mut Self::Scalar,
         accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
         op: impl Monoid<Self::Scalar>,
-        a: &impl Matrix<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
 
-    fn transpose(
-        &mut self,
-        mask: Option<impl VecMask<Self>>,
+    /// _s = accum(s, ⊕ u(i))_ into a [`Scalar`]. Unlike [`VecOps::reduce`], an empty _u_ gives
+    /// an empty result, which leaves `s` untouched when accumulating and clears it otherwise.
+    fn reduce_scalar(
+        &self,
+        s: &mut Scalar<Self::Scalar>,
         accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        a: &impl Vector<Scalar = Self::Scalar>,
+        op: impl Monoid<Self::Scalar>,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
 
-    fn kronecker_binary_op(
-        &mut self,
-        mask: Option<impl VecMask<Self>>,
+    /// Same as [`VecOps::reduce_scalar`] with an associative and commutative operator.
+    fn reduce_scalar_binary_op(
+        &self,
+        s: &mut Scalar<Self::Scalar>,
         accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>,
-        a: &impl Vector<Scalar = Self::Scalar>,
-        b: &impl Vector<Scalar = Self::Scalar>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + AssociativeCommutative,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
 
-    fn kronecker_monoid(
+    /// _w<m, accum>(i) = ⊕ A(i, :)_, or a column-wise reduction with
+    /// [`Descriptor::transpose_inp0`]. Rows without entries leave _w(i)_ empty.
+    fn reduce_binary_op(
         &mut self,
         mask: Option<impl VecMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl Monoid<Self::Scalar>,
-        a: &impl Vector<Scalar = Self::Scalar>,
-        b: &impl Vector<Scalar = Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl BinaryOperator<Self::Scalar, Output = Self::Scalar>
+            + AssociativeCommutative
+            + Send
+            + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
 
-    fn kronecker_semiring(
+    /// Same as [`VecOps::reduce_binary_op`] with the operator of a monoid.
+    fn reduce_monoid(
         &mut self,
         mask: Option<impl VecMask<Self>>,
-        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar>>,
-        op: impl Semiring<Self::Scalar, Output = Self::Scalar>,
-        a: &impl Vector<Scalar = Self::Scalar>,
-        b: &impl Vector<Scalar = Self::Scalar>,
+        accum: Option<impl BinaryOperator<Self::Scalar, Output = Self::Scalar> + Send + 'static>,
+        op: impl Monoid<Self::Scalar> + Send + 'static,
+        a: &impl Matrix<Scalar = Self::Scalar>,
         desc: Option<Descriptor>,
     ) -> GblasResult<NoValue>;
 }
@@ -0,0 +1,25 @@
+//! Commonly used traits and types.
+//!
+//! ```
+//! use gblas::prelude::*;
+//! ```
+
+pub use crate::{
+    algebra::*,
+    backend::{
+        BitmapMatrix, BitmapVec, CscMatrix, CsrMatrix, DcsrMatrix, FullMatrix, FullVec, SimpleVec,
+        SparseMatrix,
+    },
+    complement_mask::{MatrixComplementMask, VectorComplementMask},
+    context::{Context, Mode, WaitMode},
+    descriptor::Descriptor,
+    indices::Indices,
+    mask::{MatMask, VecMask},
+    matrix::{Matrix, MatrixExtra, Orientation},
+    operations::{MatOps, VecOps},
+    scalar::Scalar,
+    structure_mask::{MatrixStructureMask, VectorStructureMask},
+    types::{IndexType, NoValue},
+    vector::{Vector, VectorUtils},
+    ApiError, ExecutionError, GblasError, GblasResult,
+};
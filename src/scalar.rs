@@ -0,0 +1,99 @@
+use crate::{
+    types::{IndexType, NoValue},
+    ApiError, GblasResult,
+};
+
+/// Scalar that can hold a value or be empty (`GrB_Scalar`).
+///
+/// Bare values convert into a full scalar, so `5` can be passed wherever an
+/// `impl Into<Scalar<T>>` is expected.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Scalar<T> {
+    value: Option<T>,
+}
+
+impl<T> Scalar<T> {
+    /// Creates an empty scalar.
+    pub fn new() -> Self {
+        Self { value: None }
+    }
+
+    pub fn dup(&self) -> GblasResult<Self>
+    where
+        T: Clone,
+    {
+        Ok(self.clone())
+    }
+
+    pub fn clear(&mut self) -> GblasResult<NoValue> {
+        self.value = None;
+        Ok(())
+    }
+
+    /// Number of stored values, either 0 or 1.
+    pub fn nvals(&self) -> IndexType {
+        usize::from(self.value.is_some())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_none()
+    }
+
+    pub fn set_element(&mut self, value: T) -> GblasResult<NoValue> {
+        self.value = Some(value);
+        Ok(())
+    }
+
+    /// Returns `ApiError::NoValue` when the scalar is empty.
+    pub fn extract_element(&self) -> GblasResult<&T> {
+        self.value.as_ref().ok_or_else(|| ApiError::NoValue.into())
+    }
+
+    pub fn value(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+
+    pub fn into_value(self) -> Option<T> {
+        self.value
+    }
+}
+
+impl<T> Default for Scalar<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<T> for Scalar<T> {
+    fn from(value: T) -> Self {
+        Self { value: Some(value) }
+    }
+}
+
+impl<T> From<Option<T>> for Scalar<T> {
+    fn from(value: Option<T>) -> Self {
+        Self { value }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scalar() {
+        let mut s = Scalar::new();
+        assert!(s.is_empty());
+        assert_eq!(s.nvals(), 0);
+        assert!(s.extract_element().is_err());
+
+        s.set_element(3).unwrap();
+        assert_eq!(s.nvals(), 1);
+        assert_eq!(*s.extract_element().unwrap(), 3);
+        assert_eq!(s, Scalar::from(3));
+
+        s.clear().unwrap();
+        assert_eq!(s, Scalar::from(None));
+        assert_eq!(Scalar::from(Some(2)).into_value(), Some(2));
+    }
+}
@@ -2,13 +2,31 @@ use std::ops::Index;
 
 use crate::{matrix::Matrix, types::IndexType, vector::Vector};
 
+/// Structural mask: selects every position with a stored entry, or every position without one
+/// when complemented.
 pub struct MatrixStructureMask<'a, M> {
     pub(crate) mat: &'a M,
+    pub(crate) complement: bool,
 }
 
 impl<'a, M> MatrixStructureMask<'a, M> {
     pub fn new(mat: &'a M) -> Self {
-        Self { mat }
+        Self {
+            mat,
+            complement: false,
+        }
+    }
+
+    /// Structure of the complement, i.e. the positions without a stored entry.
+    pub fn new_complement(mat: &'a M) -> Self {
+        Self {
+            mat,
+            complement: true,
+        }
+    }
+
+    pub fn is_complement(&self) -> bool {
+        self.complement
     }
 }
 
@@ -27,7 +45,7 @@ where
 
 impl<M> Index<(IndexType, IndexType)> for MatrixStructureMask<'_, M>
 where
-    M: Matrix + Index<(IndexType, IndexType), Output = bool>,
+    M: Matrix,
 {
     type Output = bool;
 
@@ -36,19 +54,41 @@ where
             return &false;
         }
 
-        self.mat.index(index)
+        if self.mat.extract_element(index.0, index.1).is_ok() != self.complement {
+            &true
+        } else {
+            &false
+        }
     }
 }
 
 // --------------------------------------------------------------------------------
 
+/// Structural mask: selects every index with a stored entry, or every index without one when
+/// complemented.
 pub struct VectorStructureMask<'a, V> {
     pub(crate) vector: &'a V,
+    pub(crate) complement: bool,
 }
 
 impl<'a, V> VectorStructureMask<'a, V> {
     pub fn new(vector: &'a V) -> Self {
-        Self { vector }
+        Self {
+            vector,
+            complement: false,
+        }
+    }
+
+    /// Structure of the complement, i.e. the indices without a stored entry.
+    pub fn new_complement(vector: &'a V) -> Self {
+        Self {
+            vector,
+            complement: true,
+        }
+    }
+
+    pub fn is_complement(&self) -> bool {
+        self.complement
     }
 }
 
@@ -63,7 +103,7 @@ where
 
 impl<V> Index<IndexType> for VectorStructureMask<'_, V>
 where
-    V: Vector + Index<IndexType, Output = bool>,
+    V: Vector,
 {
     type Output = bool;
 
@@ -72,6 +112,10 @@ where
             return &false;
         }
 
-        self.vector.index(index)
+        if self.vector.extract_element(index).is_ok() != self.complement {
+            &true
+        } else {
+            &false
+        }
     }
 }
@@ -1,7 +1,9 @@
 use crate::{
     algebra::BinaryOperator,
+    context::{self, WaitMode},
+    scalar::Scalar,
     types::{IndexType, NoValue},
-    GblasResult,
+    ApiError, GblasError, GblasResult,
 };
 
 /// Frontend Vector.
@@ -10,12 +12,13 @@ pub trait Vector: Clone + PartialEq + Sized {
 
     fn new(size: IndexType) -> GblasResult<Self>;
     fn dup(&self) -> GblasResult<Self> {
+        context::check_init()?;
         Ok(self.clone())
     }
     fn resize(&mut self, size: IndexType) -> GblasResult<NoValue>;
     fn clear(&mut self) -> GblasResult<NoValue>;
     fn size(&self) -> IndexType;
-    fn nvals(&self) -> IndexType;
+    fn nvals(&self) -> GblasResult<IndexType>;
     fn build(
         self,
         indices: impl Iterator<Item = IndexType>,
@@ -26,7 +29,48 @@ pub trait Vector: Clone + PartialEq + Sized {
     fn set_element(&mut self, index: IndexType, val: Self::Scalar) -> GblasResult<NoValue>;
     fn remove_element(&mut self, index: IndexType) -> GblasResult<NoValue>;
     fn extract_element(&self, index: IndexType) -> GblasResult<&Self::Scalar>;
+
+    /// Same as [`Vector::extract_element`], but a missing entry gives an empty [`Scalar`] instead
+    /// of `ApiError::NoValue`.
+    fn extract_scalar(&self, index: IndexType) -> GblasResult<Scalar<Self::Scalar>>
+    where
+        Self::Scalar: Clone,
+    {
+        match self.extract_element(index) {
+            Ok(val) => Ok(Scalar::from(val.clone())),
+            Err(GblasError::ApiError(ApiError::NoValue)) => Ok(Scalar::new()),
+            Err(err) => Err(err),
+        }
+    }
+
     fn extract_tuples(self) -> GblasResult<(Vec<IndexType>, Vec<Self::Scalar>)>;
+
+    /// Borrowed `(index, value)` entries sorted by index, when they are stored that way.
+    ///
+    /// Operations read these in place instead of copying the vector. `None` by default, which
+    /// makes them go through [`Vector::extract_tuples`] on a copy.
+    fn entries(&self) -> Option<&[(IndexType, Self::Scalar)]> {
+        None
+    }
+
+    /// Copies the entries into a vector of another backend, e.g. to move a vector that became
+    /// dense to [`BitmapVec`](crate::backend::BitmapVec) or [`FullVec`](crate::backend::FullVec).
+    fn convert<V: Vector<Scalar = Self::Scalar>>(self) -> GblasResult<V> {
+        let mut res = V::new(self.size())?;
+        let (indices, values) = self.extract_tuples()?;
+        for (index, val) in indices.into_iter().zip(values) {
+            res.set_element(index, val)?;
+        }
+        Ok(res)
+    }
+
+    /// Runs the updates and operations queued on the object in nonblocking mode (`GrB_wait`).
+    ///
+    /// Objects are always complete in blocking mode, so the default only checks that the
+    /// library is initialized.
+    fn wait(&mut self, _mode: WaitMode) -> GblasResult<NoValue> {
+        context::check_init()
+    }
 }
 
 pub trait VectorUtils: Vector {
@@ -0,0 +1,92 @@
+use gblas::prelude::*;
+
+fn uninitialized<T: std::fmt::Debug>(res: GblasResult<T>) -> bool {
+    matches!(
+        res,
+        Err(GblasError::ApiError(ApiError::UninitializedObject))
+    )
+}
+
+// One test only, finalize can't be undone in the same process.
+#[test]
+fn test_init_finalize() {
+    assert!(!Context::is_initialized());
+    assert!(uninitialized(SparseMatrix::<i32>::new(2, 2)));
+    assert!(uninitialized(SimpleVec::<i32>::new(2)));
+    assert!(uninitialized(Context::mode()));
+
+    Context::init(Mode::Blocking).unwrap();
+    assert!(Context::init(Mode::Blocking).is_err());
+    assert!(Context::init(Mode::NonBlocking).is_err());
+    assert_eq!(Context::mode().unwrap(), Mode::Blocking);
+    assert!(Context::nthreads().unwrap() >= 1);
+    Context::set_nthreads(2).unwrap();
+    assert_eq!(Context::nthreads().unwrap(), 2);
+    assert!(Context::set_nthreads(0).is_err());
+
+    // the default descriptor is used by operations called with `None`
+    let mut a = SparseMatrix::<i32>::new(2, 2).unwrap();
+    a.set_element(0, 1, 1).unwrap();
+    a.set_element(1, 1, 2).unwrap();
+    let mut c = SparseMatrix::<i32>::new(2, 2).unwrap();
+    Context::set_descriptor(Descriptor::DESC_T0).unwrap();
+    c.e_wise_add_binary_op(
+        Option::<SparseMatrix<i32>>::None,
+        Option::<Addition<i32>>::None,
+        Addition::new(),
+        &a,
+        &a,
+        None,
+    )
+    .unwrap();
+    assert_eq!(c.extract_element(1, 0).unwrap(), &1);
+    Context::set_descriptor(Descriptor::default()).unwrap();
+
+    let mut u = SimpleVec::<i32>::new(2).unwrap();
+    u.set_element(0, 1).unwrap();
+    a.wait(WaitMode::Materialize).unwrap();
+    u.wait(WaitMode::Complete).unwrap();
+    assert_eq!(u.dup().unwrap(), u);
+
+    Context::finalize().unwrap();
+    assert!(!Context::is_initialized());
+    assert!(uninitialized(SparseMatrix::<i32>::new(2, 2)));
+    assert!(uninitialized(a.wait(WaitMode::Complete)));
+    assert!(uninitialized(u.dup()));
+    assert!(uninitialized(a.set_element(0, 0, 1)));
+    assert!(uninitialized(a.remove_element(0, 1)));
+    assert!(uninitialized(a.nvals()));
+    assert!(uninitialized(a.extract_element(0, 1)));
+    assert!(uninitialized(a.clone().extract_tuples()));
+    assert!(uninitialized(c.mxm(
+        Option::<SparseMatrix<i32>>::None,
+        Option::<Addition<i32>>::None,
+        PlusTimesSemiring::new(),
+        &a,
+        &a,
+        None,
+    )));
+    let mut val = 0;
+    assert!(uninitialized(a.reduce(
+        &mut val,
+        Option::<Addition<i32>>::None,
+        PlusMonoid::new(),
+        None
+    )));
+    assert!(uninitialized(u.nvals()));
+    assert!(uninitialized(u.reduce(
+        &mut val,
+        Option::<Addition<i32>>::None,
+        PlusMonoid::new(),
+        None
+    )));
+    let mut s = Scalar::new();
+    assert!(uninitialized(u.reduce_scalar(
+        &mut s,
+        Option::<Addition<i32>>::None,
+        PlusMonoid::new(),
+        None
+    )));
+    assert!(uninitialized(Context::finalize()));
+    assert!(Context::init(Mode::Blocking).is_err());
+}
@@ -0,0 +1,247 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use gblas::prelude::*;
+
+/// Builds the inputs with a mix of set/remove calls and runs a few operations on them.
+#[allow(clippy::type_complexity)]
+fn run() -> (
+    SparseMatrix<i32>,
+    SimpleVec<i32>,
+    SparseMatrix<i32>,
+    SimpleVec<i32>,
+    SparseMatrix<i32>,
+    IndexType,
+    i32,
+) {
+    let mut a = SparseMatrix::<i32>::new(4, 4).unwrap();
+    for (i, j, v) in [
+        (0, 0, 1),
+        (3, 1, 2),
+        (1, 2, 3),
+        (0, 0, 4),
+        (2, 3, 5),
+        (1, 1, 6),
+    ] {
+        a.set_element(i, j, v).unwrap();
+    }
+    a.remove_element(3, 1).unwrap();
+    a.remove_element(3, 3).unwrap();
+    a.set_element(3, 1, 7).unwrap();
+    assert!(a.set_element(4, 0, 1).is_err());
+
+    let mut u = SimpleVec::<i32>::new(4).unwrap();
+    for (i, v) in [(3, 1), (0, 2), (3, 3), (1, 4)] {
+        u.set_element(i, v).unwrap();
+    }
+    u.remove_element(0).unwrap();
+    assert!(u.remove_element(4).is_err());
+
+    let mut c = a.clone();
+    c.set_element(0, 3, 9).unwrap();
+    c.mxm(
+        Some(a.structure()),
+        Some(Addition::new()),
+        PlusTimesSemiring::new(),
+        &a,
+        &a,
+        None,
+    )
+    .unwrap();
+
+    let mut w = u.clone();
+    w.set_element(2, 1).unwrap();
+    w.mxv(
+        Option::<SimpleVec<i32>>::None,
+        Some(Addition::new()),
+        PlusTimesSemiring::new(),
+        &c,
+        &u,
+        None,
+    )
+    .unwrap();
+
+    let mut d = SparseMatrix::<i32>::new(4, 4).unwrap();
+    d.transpose(
+        Option::<SparseMatrix<i32>>::None,
+        Option::<Addition<i32>>::None,
+        &c,
+        None,
+    )
+    .unwrap();
+    d.apply(
+        Some(a.structure()),
+        Option::<Addition<i32>>::None,
+        |x: i32| x + 1,
+        &c,
+        Some(Descriptor::new().with_replace()),
+    )
+    .unwrap();
+    d.select(
+        Option::<SparseMatrix<i32>>::None,
+        Some(Addition::new()),
+        TriuOp,
+        &a,
+        0,
+        None,
+    )
+    .unwrap();
+    d.e_wise_add_binary_op(
+        Option::<SparseMatrix<i32>>::None,
+        Option::<Addition<i32>>::None,
+        Addition::new(),
+        &d.clone(),
+        &a,
+        None,
+    )
+    .unwrap();
+
+    let mut x = SimpleVec::<i32>::new(4).unwrap();
+    x.extract(
+        Some(u.structure()),
+        Option::<Addition<i32>>::None,
+        &w,
+        Indices::List(vec![3, 1, 1, 0]),
+        None,
+    )
+    .unwrap();
+    x.e_wise_mult_binary_op(
+        Option::<SimpleVec<i32>>::None,
+        Some(Addition::new()),
+        Multiplication::new(),
+        &x.clone(),
+        &x.clone(),
+        None,
+    )
+    .unwrap();
+
+    let mut e = SparseMatrix::<i32>::new(4, 4).unwrap();
+    e.assign_col(
+        Option::<SimpleVec<i32>>::None,
+        Option::<Addition<i32>>::None,
+        &w,
+        Indices::All,
+        2,
+        None,
+    )
+    .unwrap();
+    let mut block = SparseMatrix::<i32>::new(2, 2).unwrap();
+    block
+        .extract(
+            Option::<SparseMatrix<i32>>::None,
+            Option::<Addition<i32>>::None,
+            &d,
+            Indices::Range { begin: 2, end: 3 },
+            Indices::Range { begin: 0, end: 1 },
+            None,
+        )
+        .unwrap();
+    e.subassign(
+        Some(SparseMatrix::<i32>::new(2, 2).unwrap().complement()),
+        Some(Addition::new()),
+        &block,
+        Indices::Range { begin: 1, end: 2 },
+        Indices::Range { begin: 1, end: 2 },
+        None,
+    )
+    .unwrap();
+
+    // the queued operations copied their inputs and masks, changing them now is not seen
+    let nvals = a.nvals().unwrap() + u.nvals().unwrap();
+    a.clear().unwrap();
+    a.set_element(3, 3, 1).unwrap();
+    u.set_element(0, 1).unwrap();
+    let mut sum = 0;
+    w.reduce(
+        &mut sum,
+        Option::<Addition<i32>>::None,
+        PlusMonoid::new(),
+        None,
+    )
+    .unwrap();
+
+    c.wait(WaitMode::Materialize).unwrap();
+    w.wait(WaitMode::Complete).unwrap();
+    d.wait(WaitMode::Materialize).unwrap();
+    x.wait(WaitMode::Materialize).unwrap();
+    e.wait(WaitMode::Materialize).unwrap();
+    (c, w, d, x, e, nvals, sum)
+}
+
+// Both modes run in one test since the mode is global to the process.
+#[test]
+fn test_nonblocking_matches_blocking() {
+    Context::init(Mode::Blocking).unwrap();
+    let blocking = run();
+
+    Context::set_mode(Mode::NonBlocking).unwrap();
+    assert_eq!(Context::mode().unwrap(), Mode::NonBlocking);
+    let nonblocking = run();
+
+    let mut v = SimpleVec::<i32>::new(3).unwrap();
+    v.set_element(1, 5).unwrap();
+    v.set_element(1, 6).unwrap();
+    assert_eq!(v.extract_element(1).unwrap(), &6);
+    v.remove_element(1).unwrap();
+    assert_eq!(v.nvals().unwrap(), 0);
+    v.set_element(2, 1).unwrap();
+    v.clear().unwrap();
+    assert_eq!(v.extract_tuples().unwrap(), (vec![], vec![]));
+
+    let mut m = SparseMatrix::<i32>::new(2, 2).unwrap();
+    m.set_element(1, 1, 3).unwrap();
+    m.resize(3, 1).unwrap();
+    m.set_element(2, 0, 4).unwrap();
+    assert_eq!(m.extract_tuples().unwrap(), (vec![2], vec![0], vec![4]));
+
+    let mut csr = CsrMatrix::<i32>::new(3, 3).unwrap();
+    for (i, j, v) in [(2, 2, 1), (0, 1, 2), (2, 0, 3), (0, 1, 4)] {
+        csr.set_element(i, j, v).unwrap();
+    }
+    csr.remove_element(2, 2).unwrap();
+    assert_eq!(csr.nvals().unwrap(), 2);
+    csr.set_element(1, 1, 5).unwrap();
+    assert_eq!(
+        csr.extract_tuples().unwrap(),
+        (vec![0, 1, 2], vec![1, 1, 0], vec![4, 5, 3])
+    );
+
+    let n = 1 << 40;
+    let mut dcsr = DcsrMatrix::<i32>::new(n, n).unwrap();
+    for (i, j, v) in [(n - 1, 2, 1), (0, 1, 2), (n - 1, 0, 3), (0, 1, 4)] {
+        dcsr.set_element(i, j, v).unwrap();
+    }
+    dcsr.remove_element(n - 1, 2).unwrap();
+    dcsr.remove_element(0, 1).unwrap();
+    assert_eq!(dcsr.nvals().unwrap(), 1);
+    dcsr.set_element(1, n - 1, 5).unwrap();
+    assert_eq!(
+        dcsr.extract_tuples().unwrap(),
+        (vec![1, n - 1], vec![n - 1, 0], vec![5, 3])
+    );
+
+    // operations only run once the output is read
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    let mut m = SparseMatrix::<i32>::new(3, 1).unwrap();
+    m.set_element(2, 0, 4).unwrap();
+    let mut lazy = SparseMatrix::<i32>::new(3, 1).unwrap();
+    lazy.apply(
+        Option::<SparseMatrix<i32>>::None,
+        Option::<Addition<i32>>::None,
+        |x: i32| {
+            CALLS.fetch_add(1, Ordering::Relaxed);
+            x * 2
+        },
+        &m,
+        None,
+    )
+    .unwrap();
+    m.clear().unwrap();
+    assert_eq!(CALLS.load(Ordering::Relaxed), 0);
+    assert_eq!(lazy.nvals().unwrap(), 1);
+    assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+    assert_eq!(lazy.extract_element(2, 0).unwrap(), &8);
+
+    Context::set_mode(Mode::Blocking).unwrap();
+    assert_eq!(blocking, nonblocking);
+    assert_eq!(blocking.5, 7);
+}
@@ -0,0 +1,142 @@
+use std::sync::Once;
+
+use gblas::prelude::*;
+
+/// The tests share the process, the library is initialized by the first one.
+fn init() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| Context::init(Mode::Blocking).unwrap());
+}
+
+fn matrix(
+    nrows: IndexType,
+    ncols: IndexType,
+    values: &[(IndexType, IndexType, f64)],
+) -> SparseMatrix<f64> {
+    let mut mat = SparseMatrix::new(nrows, ncols).unwrap();
+    for (i, j, v) in values {
+        mat.set_element(*i, *j, *v).unwrap();
+    }
+    mat
+}
+
+#[test]
+fn test_version() {
+    assert_eq!(gblas::version(), "1.3");
+}
+
+#[test]
+fn test_prelude_matrix() {
+    init();
+    let a = matrix(2, 2, &[(0, 0, 1.0), (0, 1, 2.0), (1, 1, 3.0)]);
+    let mut c = SparseMatrix::<f64>::new(2, 2).unwrap();
+
+    c.mxm(
+        Option::<SparseMatrix<f64>>::None,
+        Option::<Addition<f64>>::None,
+        PlusTimesSemiring::new(),
+        &a,
+        &a,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(c.nvals().unwrap(), 3);
+    assert_eq!(c.extract_element(0, 1).unwrap(), &8.0);
+    assert!(matches!(
+        c.extract_element(1, 0),
+        Err(GblasError::ApiError(ApiError::NoValue))
+    ));
+
+    let mask = a.structure();
+    assert!(mask[(0, 0)]);
+    assert!(!a.complement()[(0, 0)]);
+}
+
+#[test]
+fn test_prelude_vector() {
+    init();
+    let mut u = SimpleVec::<f64>::new(3).unwrap();
+    u.set_element(2, 4.0).unwrap();
+    let res: GblasResult<NoValue> = u.set_element(3, 1.0);
+
+    assert!(res.is_err());
+    assert_eq!(u.nvals().unwrap(), 1);
+    assert_eq!(u.extract_element(2).unwrap(), &4.0);
+    assert!(u.structure()[2]);
+    assert!(u.complement()[0]);
+
+    let a = matrix(2, 3, &[(0, 0, 1.0), (1, 2, 2.0)]);
+    let mut w = SimpleVec::<f64>::new(2).unwrap();
+    w.mxv(
+        Option::<SimpleVec<f64>>::None,
+        Option::<Addition<f64>>::None,
+        PlusTimesSemiring::new(),
+        &a,
+        &u,
+        None,
+    )
+    .unwrap();
+    assert_eq!(w.extract_element(1).unwrap(), &8.0);
+
+    assert_eq!(u.extract_tuples().unwrap(), (vec![2], vec![4.0]));
+}
+
+#[test]
+fn test_prelude_algebra() {
+    assert_eq!(PlusMonoid::<i32>::new().identity(), 0);
+    assert_eq!(MinMonoid::<i32>::default().operate(3, 2), 2);
+    assert_eq!(Maximum::new().op(3, 2), 3);
+    assert_eq!(MinPlusSemiring::<i32>::new().zero(), i32::MAX);
+    assert_eq!(
+        Indices::Range { begin: 1, end: 3 }.resolve(4).unwrap(),
+        vec![1, 2, 3]
+    );
+}
+
+#[test]
+fn test_closure_operators() {
+    init();
+    let a = matrix(2, 2, &[(0, 0, 1.0), (1, 1, 2.0)]);
+    let mut c = matrix(2, 2, &[(0, 0, 10.0)]);
+
+    // keep the largest value
+    c.mxm(
+        Option::<SparseMatrix<f64>>::None,
+        Some(|x: f64, y: f64| if x > y { x } else { y }),
+        PlusTimesSemiring::new(),
+        &a,
+        &a,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(c.extract_element(0, 0).unwrap(), &10.0);
+    assert_eq!(c.extract_element(1, 1).unwrap(), &4.0);
+
+    let threshold = 0.5;
+    assert!((move |x: f64| x > threshold).op(0.7));
+}
+
+#[test]
+fn test_user_defined_semiring() {
+    init();
+    // 0 -> 1 -> 2 (0.9 * 0.9) beats 0 -> 2 (0.5)
+    let a = matrix(3, 3, &[(0, 1, 0.9), (0, 2, 0.5), (1, 2, 0.9)]);
+    let reliability =
+        SemiringFrom::new(MonoidFrom::new(Maximum::new(), 0.0), Multiplication::new());
+
+    let mut c = a.clone();
+    c.mxm(
+        Option::<SparseMatrix<f64>>::None,
+        Some(Maximum::new()),
+        reliability,
+        &a,
+        &a,
+        None,
+    )
+    .unwrap();
+
+    assert!((c.extract_element(0, 2).unwrap() - 0.81).abs() < 1e-12);
+    assert_eq!(c.extract_element(0, 1).unwrap(), &0.9);
+}